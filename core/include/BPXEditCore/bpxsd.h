@@ -31,9 +31,58 @@
 
 #include <BPXEditCore/common.h>
 #include <BPXEditCore/tree/tree.h>
+#include <BPXEditCore/dictionary.h>
+
+typedef struct bpxsd_reader_options_s {
+    uint32_t max_depth;
+    uint32_t max_nodes;
+    uint32_t max_string_length;
+} bpxsd_reader_options_t;
+
+typedef void bpxsd_iter_t;
+
+BPX_API void bpxsd_reader_options_default(BPX_NONNULL bpxsd_reader_options_t* options);
 
 BPX_NULLABLE BPX_API bpx_node_t* bpxsd_read_from_bytes(bpx_bytes_const_t buffer);
 
 BPX_NULLABLE BPX_API bpx_node_t* bpxsd_read_from_section(BPX_NONNULL const bpx_container_t* container, bpx_section_handle_t section);
 
+BPX_NULLABLE BPX_API bpx_node_t* bpxsd_read_from_bytes_with_dictionary(bpx_bytes_const_t buffer, BPX_NONNULL const bpxsd_dictionary_t* dictionary);
+
+BPX_NULLABLE BPX_API bpx_node_t* bpxsd_read_from_section_with_dictionary(BPX_NONNULL const bpx_container_t* container, bpx_section_handle_t section, BPX_NONNULL const bpxsd_dictionary_t* dictionary);
+
+BPX_NULLABLE BPX_API bpx_node_t* bpxsd_read_from_bytes_with_options(bpx_bytes_const_t buffer, BPX_NONNULL const bpxsd_reader_options_t* options, BPX_NULLABLE const bpxsd_dictionary_t* dictionary);
+
+BPX_NULLABLE BPX_API bpx_node_t* bpxsd_read_from_section_with_options(BPX_NONNULL const bpx_container_t* container, bpx_section_handle_t section, BPX_NONNULL const bpxsd_reader_options_t* options, BPX_NULLABLE const bpxsd_dictionary_t* dictionary);
+
+BPX_NULLABLE BPX_API bpx_node_t* bpxsd_read_from_section_at(BPX_NONNULL const bpx_container_t* container, bpx_section_handle_t section, uint64_t offset, BPX_NONNULL const bpxsd_reader_options_t* options, BPX_NULLABLE const bpxsd_dictionary_t* dictionary, BPX_NULLABLE uint64_t* consumed);
+
+BPX_NULLABLE BPX_API bpxsd_iter_t* bpxsd_iter_section(BPX_NONNULL const bpx_container_t* container, bpx_section_handle_t section, uint64_t offset, BPX_NONNULL const bpxsd_reader_options_t* options, BPX_NULLABLE const bpxsd_dictionary_t* dictionary);
+
+BPX_NULLABLE BPX_API bpx_node_t* bpxsd_iter_next(BPX_NONNULL bpxsd_iter_t* iter);
+
+BPX_API bool bpxsd_iter_is_end(BPX_NONNULL const bpxsd_iter_t* iter);
+
+BPX_API uint64_t bpxsd_iter_get_offset(BPX_NONNULL const bpxsd_iter_t* iter);
+
+BPX_API void bpxsd_iter_free(BPX_NONNULL bpxsd_iter_t* iter);
+
+BPX_NULLABLE BPX_API char* bpxsd_to_json_from_bytes(bpx_bytes_const_t buffer, bool pretty);
+
+BPX_NULLABLE BPX_API char* bpxsd_to_json_from_section(BPX_NONNULL const bpx_container_t* container, bpx_section_handle_t section, bool pretty);
+
+BPX_NULLABLE BPX_API char* bpxsd_to_json_from_bytes_with_options(bpx_bytes_const_t buffer, BPX_NONNULL const bpxsd_reader_options_t* options, bool pretty);
+
+BPX_NULLABLE BPX_API char* bpxsd_to_json_from_section_with_options(BPX_NONNULL const bpx_container_t* container, bpx_section_handle_t section, BPX_NONNULL const bpxsd_reader_options_t* options, bool pretty);
+
+BPX_API void bpxsd_json_free(BPX_NONNULL char* json);
+
+BPX_API ssize_t bpxsd_from_json(BPX_NONNULL const char* json, bpx_bytes_t buffer);
+
+BPX_API ssize_t bpxsd_from_json_with_options(BPX_NONNULL const char* json, BPX_NONNULL const bpxsd_reader_options_t* options, bpx_bytes_t buffer);
+
+BPX_API bool bpxsd_write_json_to_section(BPX_NONNULL const bpx_container_t* container, bpx_section_handle_t section, BPX_NONNULL const char* json);
+
+BPX_API bool bpxsd_write_json_to_section_with_options(BPX_NONNULL const bpx_container_t* container, bpx_section_handle_t section, BPX_NONNULL const char* json, BPX_NONNULL const bpxsd_reader_options_t* options);
+
 #endif
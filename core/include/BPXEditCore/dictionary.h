@@ -0,0 +1,50 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#ifndef BPX_DICTIONARY_H
+#define BPX_DICTIONARY_H
+
+#include <BPXEditCore/common.h>
+
+typedef void bpxsd_dictionary_t;
+
+BPX_SLICE(name_list, const char* const, names);
+
+BPX_NONNULL BPX_API bpxsd_dictionary_t* bpxsd_dictionary_create();
+
+BPX_API void bpxsd_dictionary_free(BPX_NONNULL bpxsd_dictionary_t* dictionary);
+
+BPX_API void bpxsd_dictionary_add(BPX_NONNULL bpxsd_dictionary_t* dictionary, BPX_NONNULL const char* name);
+
+BPX_API void bpxsd_dictionary_add_list(BPX_NONNULL bpxsd_dictionary_t* dictionary, bpx_name_list_t names);
+
+BPX_API bool bpxsd_dictionary_load(BPX_NONNULL bpxsd_dictionary_t* dictionary, BPX_NONNULL const char* path);
+
+BPX_API size_t bpxsd_dictionary_get_count(BPX_NONNULL const bpxsd_dictionary_t* dictionary);
+
+#endif
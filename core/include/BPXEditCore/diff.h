@@ -0,0 +1,85 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#ifndef BPX_DIFF_H
+#define BPX_DIFF_H
+
+#include <BPXEditCore/tree/tree.h>
+#include <BPXEditCore/bpxsd.h>
+
+typedef void bpx_diff_t;
+
+typedef enum bpx_diff_level_e {
+    BPX_DIFF_LEVEL_HEADER = 0,
+    BPX_DIFF_LEVEL_SECTION,
+    BPX_DIFF_LEVEL_BYTES,
+    BPX_DIFF_LEVEL_NODE,
+    BPX_DIFF_LEVEL_ROW
+} bpx_diff_level_t;
+
+typedef enum bpx_change_kind_e {
+    BPX_CHANGE_KIND_ADDED = 0,
+    BPX_CHANGE_KIND_REMOVED,
+    BPX_CHANGE_KIND_CHANGED,
+    BPX_CHANGE_KIND_MOVED
+} bpx_change_kind_t;
+
+typedef struct bpx_diff_options_s {
+    uint32_t a_strings;
+    uint32_t b_strings;
+    bpxsd_reader_options_t reader;
+} bpx_diff_options_t;
+
+typedef struct bpx_diff_change_s {
+    bpx_diff_level_t level;
+    bpx_change_kind_t kind;
+    uint32_t section_a;
+    uint32_t section_b;
+    uint64_t offset;
+    uint64_t length;
+    BPX_NONNULL const char* path;
+    BPX_NULLABLE const char* from;
+    BPX_NULLABLE const bpx_value_t* old_value;
+    BPX_NULLABLE const bpx_value_t* new_value;
+} bpx_diff_change_t;
+
+BPX_SLICE(diff_change_list, const bpx_diff_change_t, changes);
+
+BPX_API void bpx_diff_options_default(BPX_NONNULL bpx_diff_options_t* options);
+
+BPX_NULLABLE BPX_API bpx_diff_t* bpx_container_diff(BPX_NONNULL const bpx_container_t* a, BPX_NONNULL const bpx_container_t* b, BPX_NONNULL const bpx_diff_options_t* options);
+
+BPX_NONNULL BPX_API bpx_diff_t* bpx_node_diff(BPX_NONNULL const bpx_node_t* a, BPX_NONNULL const bpx_node_t* b);
+
+BPX_API bpx_diff_change_list_t bpx_diff_get_changes(BPX_NONNULL const bpx_diff_t* diff);
+
+BPX_NONNULL BPX_API const char* bpx_diff_get_text(BPX_NONNULL const bpx_diff_t* diff);
+
+BPX_API void bpx_diff_free(BPX_NONNULL bpx_diff_t* diff);
+
+#endif
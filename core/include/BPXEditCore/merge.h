@@ -0,0 +1,57 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#ifndef BPX_MERGE_H
+#define BPX_MERGE_H
+
+#include <BPXEditCore/common.h>
+
+typedef enum bpx_merge_policy_e {
+    BPX_MERGE_POLICY_KEEP_BOTH = 0,
+    BPX_MERGE_POLICY_REPLACE,
+    BPX_MERGE_POLICY_MERGE_ROWS
+} bpx_merge_policy_t;
+
+typedef enum bpx_strings_policy_e {
+    BPX_STRINGS_POLICY_KEEP_BOTH = 0,
+    BPX_STRINGS_POLICY_REPLACE,
+    BPX_STRINGS_POLICY_MERGE
+} bpx_strings_policy_t;
+
+typedef struct bpx_merge_options_s {
+    bpx_merge_policy_t tables;
+    bpx_strings_policy_t strings;
+    uint32_t dst_strings;
+    uint32_t src_strings;
+} bpx_merge_options_t;
+
+BPX_API void bpx_merge_options_default(BPX_NONNULL bpx_merge_options_t* options);
+
+BPX_API bool bpx_container_merge(BPX_NONNULL bpx_container_t* dst, BPX_NONNULL const bpx_container_t* src, BPX_NONNULL const bpx_merge_options_t* options);
+
+#endif
@@ -0,0 +1,102 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#ifndef BPX_SEARCH_H
+#define SEARCH_H
+
+#include <BPXEditCore/container.h>
+#include <BPXEditCore/dictionary.h>
+#include <BPXEditCore/tree/tree.h>
+
+#define SEARCH_ENCODING_UTF8 0x1
+#define SEARCH_ENCODING_UTF16LE 0x2
+#define SEARCH_ENCODING_UTF16BE 0x4
+#define SEARCH_ENCODING_LATIN1 0x8
+
+#define SEARCH_SCOPE_RAW 0x1
+#define SEARCH_SCOPE_SD 0x2
+#define SEARCH_SCOPE_STRINGS 0x4
+#define SEARCH_SCOPE_TABLES 0x8
+
+typedef void bpx_search_result_t;
+
+typedef enum bpx_search_mode_e {
+    BPX_SEARCH_MODE_BYTES = 0,
+    BPX_SEARCH_MODE_TEXT,
+    BPX_SEARCH_MODE_REGEX
+} bpx_search_mode_t;
+
+typedef enum bpx_hit_kind_e {
+    BPX_HIT_KIND_BYTES = 0,
+    BPX_HIT_KIND_KEY,
+    BPX_HIT_KIND_VALUE,
+    BPX_HIT_KIND_STRING,
+    BPX_HIT_KIND_CELL
+} bpx_hit_kind_t;
+
+typedef struct bpx_search_options_s {
+    bpx_search_mode_t mode;
+    uint32_t encodings;
+    uint32_t scopes;
+    bool case_insensitive;
+    uint32_t strings;
+    uint32_t max_hits;
+} bpx_search_options_t;
+
+typedef struct bpx_search_progress_s {
+    BPX_NONNULL void* userdata;
+    void(*release)(BPX_NONNULL void* userdata);
+    bool(*progress)(BPX_NONNULL void* userdata, uint64_t done, uint64_t total);
+} bpx_search_progress_t;
+
+typedef struct bpx_search_hit_s {
+    bpx_hit_kind_t kind;
+    bpx_section_handle_t section;
+    uint64_t offset;
+    uint64_t length;
+    uint64_t row;
+    uint32_t column;
+    BPX_NULLABLE const char* path;
+    BPX_NULLABLE const bpx_value_t* value;
+} bpx_search_hit_t;
+
+BPX_SLICE(search_hit_list, const bpx_search_hit_t, hits);
+
+BPX_API void bpx_search_options_default(BPX_NONNULL bpx_search_options_t* options);
+
+BPX_NULLABLE BPX_API bpx_search_result_t* bpx_container_search(BPX_NONNULL const bpx_container_t* container, bpx_bytes_const_t pattern, BPX_NONNULL const bpx_search_options_t* options, BPX_NULLABLE const bpxsd_dictionary_t* dictionary);
+
+BPX_NULLABLE BPX_API bpx_search_result_t* bpx_container_search_with_progress(BPX_NONNULL const bpx_container_t* container, bpx_bytes_const_t pattern, BPX_NONNULL const bpx_search_options_t* options, BPX_NULLABLE const bpxsd_dictionary_t* dictionary, bpx_search_progress_t progress);
+
+BPX_API bpx_search_hit_list_t bpx_search_get_hits(BPX_NONNULL const bpx_search_result_t* result);
+
+BPX_API bool bpx_search_is_cancelled(BPX_NONNULL const bpx_search_result_t* result);
+
+BPX_API void bpx_search_free(BPX_NONNULL bpx_search_result_t* result);
+
+#endif
@@ -49,6 +49,8 @@ BPX_API void bpx_section_options_default(BPX_NONNULL bpx_section_options_t* opti
 
 BPX_API bpx_section_handle_t bpx_section_create(BPX_NONNULL bpx_container_t* container, BPX_NONNULL const bpx_section_options_t* options);
 
+BPX_API uint32_t bpx_section_copy(BPX_NONNULL const bpx_container_t* src, bpx_section_handle_t section, BPX_NONNULL bpx_container_t* dst);
+
 BPX_API void bpx_section_remove(BPX_NONNULL bpx_container_t* container, bpx_section_handle_t section);
 
 BPX_API ssize_t bpx_section_size(BPX_NONNULL const bpx_container_t* container, bpx_section_handle_t section);
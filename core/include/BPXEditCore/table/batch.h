@@ -0,0 +1,43 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#ifndef BPX_TABLE_BATCH_H
+#define BPX_TABLE_BATCH_H
+
+#include <BPXEditCore/table/core.h>
+
+BPX_SLICE(column_buffer_list, bpx_bytes_t, columns);
+BPX_SLICE(column_buffer_list_const, const bpx_bytes_const_t, columns);
+
+BPX_API ssize_t bpx_table_read_batch(BPX_NONNULL const bpx_table_t* table, size_t start, size_t count, bpx_column_buffer_list_t columns, bpx_bytes_t free, bpx_bytes_t strings, BPX_NONNULL size_t* strings_len);
+
+BPX_API bool bpx_table_write_batch(BPX_NONNULL const bpx_table_t* table, size_t start, size_t count, bpx_column_buffer_list_const_t columns, bpx_bytes_const_t free, bpx_bytes_const_t strings);
+
+BPX_API ssize_t bpx_table_append_batch(BPX_NONNULL const bpx_table_t* table, size_t count, bpx_column_buffer_list_const_t columns, bpx_bytes_const_t free, bpx_bytes_const_t strings);
+
+#endif
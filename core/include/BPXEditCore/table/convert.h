@@ -0,0 +1,60 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#ifndef BPX_TABLE_CONVERT_H
+#define BPX_TABLE_CONVERT_H
+
+#include <BPXEditCore/table/core.h>
+#include <BPXEditCore/tree/tree.h>
+#include <BPXEditCore/bpxsd.h>
+
+typedef void bpx_table_import_t;
+
+typedef struct bpx_row_error_s {
+    uint64_t row;
+    BPX_NONNULL const char* column;
+    BPX_NONNULL const char* message;
+} bpx_row_error_t;
+
+BPX_SLICE(row_error_list, const bpx_row_error_t, errors);
+
+BPX_NULLABLE BPX_API bpx_node_t* bpx_table_to_node(BPX_NONNULL const bpx_table_t* table, bool debug);
+
+BPX_API ssize_t bpx_table_to_bpxsd(BPX_NONNULL const bpx_table_t* table, BPX_NONNULL const char* key, bool debug, bpx_bytes_t buffer);
+
+BPX_NULLABLE BPX_API bpx_table_import_t* bpx_table_from_node(BPX_NONNULL bpx_table_t* table, BPX_NONNULL const bpx_node_t* node);
+
+BPX_NULLABLE BPX_API bpx_table_import_t* bpx_table_from_bpxsd(BPX_NONNULL bpx_table_t* table, bpx_bytes_const_t buffer, BPX_NONNULL const char* key, BPX_NULLABLE const bpxsd_dictionary_t* dictionary);
+
+BPX_API uint64_t bpx_table_import_get_row_count(BPX_NONNULL const bpx_table_import_t* import);
+
+BPX_API bpx_row_error_list_t bpx_table_import_get_errors(BPX_NONNULL const bpx_table_import_t* import);
+
+BPX_API void bpx_table_import_free(BPX_NONNULL bpx_table_import_t* import);
+
+#endif
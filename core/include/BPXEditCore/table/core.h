@@ -40,14 +40,24 @@ BPX_NULLABLE BPX_API bpx_table_t* bpx_table_open(BPX_NONNULL const bpx_container
 
 BPX_NONNULL BPX_API const char* bpx_table_get_name(BPX_NONNULL const bpx_table_t* table);
 
+BPX_API void bpx_table_set_strict(BPX_NONNULL bpx_table_t* table, bool strict);
+
+BPX_API bool bpx_table_is_strict(BPX_NONNULL const bpx_table_t* table);
+
 BPX_API bool bpx_table_save(BPX_NONNULL bpx_table_t* table);
 
 BPX_API ssize_t bpx_table_column_create(BPX_NONNULL bpx_table_t* table, BPX_NONNULL const char* name, bpx_value_type_t ty, uint16_t len);
 
-BPX_API void bpx_table_column_remove_at(BPX_NONNULL bpx_table_t* table, ssize_t index);
+BPX_API bool bpx_table_column_remove_at(BPX_NONNULL bpx_table_t* table, ssize_t index);
 
 BPX_API size_t bpx_table_get_columns(BPX_NONNULL const bpx_table_t* table);
 
+BPX_API bool bpx_table_column_get_type(BPX_NONNULL const bpx_table_t* table, size_t index, BPX_NONNULL bpx_value_type_t* out);
+
+BPX_API bool bpx_table_column_get_len(BPX_NONNULL const bpx_table_t* table, size_t index, BPX_NONNULL uint16_t* out);
+
+BPX_API bool bpx_table_column_get_element_count(BPX_NONNULL const bpx_table_t* table, size_t index, BPX_NONNULL size_t* out);
+
 BPX_API size_t bpx_table_get_row_size(BPX_NONNULL const bpx_table_t* table);
 
 BPX_API size_t bpx_table_get_actual_row_size(BPX_NONNULL const bpx_table_t* table);
@@ -60,6 +70,8 @@ BPX_API bool bpx_table_write(BPX_NONNULL const bpx_table_t* table, BPX_NONNULL b
 
 BPX_API ssize_t bpx_table_append(BPX_NONNULL const bpx_table_t* table, BPX_NONNULL bpx_table_row_t* row);
 
+BPX_API bool bpx_table_delete(BPX_NONNULL const bpx_table_t* table, ssize_t index);
+
 BPX_API ssize_t bpx_table_get_row_count(BPX_NONNULL const bpx_table_t* table, BPX_NONNULL const bpx_table_row_t* row);
 
 BPX_API ssize_t bpx_table_get_column_index(BPX_NONNULL const bpx_table_t* table, BPX_NONNULL const char* name);
@@ -0,0 +1,52 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#ifndef BPX_TABLE_INDEX_H
+#define BPX_TABLE_INDEX_H
+
+#include <BPXEditCore/table/query.h>
+
+typedef enum bpx_index_kind_e {
+    BPX_INDEX_KIND_HASH = 0,
+    BPX_INDEX_KIND_BTREE
+} bpx_index_kind_t;
+
+BPX_SLICE(column_index_list, const size_t, columns);
+BPX_SLICE(value_list, const bpx_value_t* const, values);
+
+BPX_API ssize_t bpx_table_index_create(BPX_NONNULL bpx_table_t* table, bpx_column_index_list_t columns, bpx_index_kind_t kind, bool unique);
+
+BPX_API void bpx_table_index_remove_at(BPX_NONNULL bpx_table_t* table, size_t index);
+
+BPX_API size_t bpx_table_get_indexes(BPX_NONNULL const bpx_table_t* table);
+
+BPX_NULLABLE BPX_API bpx_result_set_t* bpx_table_index_lookup(BPX_NONNULL const bpx_table_t* table, size_t index, bpx_value_list_t key);
+
+BPX_NULLABLE BPX_API bpx_result_set_t* bpx_table_index_lookup_range(BPX_NONNULL const bpx_table_t* table, size_t index, bpx_value_list_t low, bpx_value_list_t high);
+
+#endif
@@ -0,0 +1,76 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#ifndef BPX_TABLE_PROFILE_H
+#define BPX_TABLE_PROFILE_H
+
+#include <BPXEditCore/table/core.h>
+
+typedef void bpx_profile_t;
+
+typedef struct bpx_profile_options_s {
+    uint32_t top_values;
+    uint32_t exact_limit;
+} bpx_profile_options_t;
+
+typedef struct bpx_value_count_s {
+    BPX_NONNULL const bpx_value_t* value;
+    uint64_t count;
+} bpx_value_count_t;
+
+BPX_SLICE(value_count_list, const bpx_value_count_t, values);
+
+typedef struct bpx_column_profile_s {
+    BPX_NONNULL const char* name;
+    bpx_value_type_t ty;
+    uint16_t len;
+    uint64_t count;
+    BPX_NULLABLE const bpx_value_t* min;
+    BPX_NULLABLE const bpx_value_t* max;
+    BPX_NULLABLE const bpx_value_t* mean;
+    uint64_t distinct;
+    bool exact;
+    uint64_t empty_strings;
+    bpx_value_count_list_t top;
+} bpx_column_profile_t;
+
+BPX_SLICE(column_profile_list, const bpx_column_profile_t, columns);
+
+BPX_API void bpx_profile_options_default(BPX_NONNULL bpx_profile_options_t* options);
+
+BPX_NULLABLE BPX_API bpx_profile_t* bpx_table_profile(BPX_NONNULL const bpx_table_t* table, BPX_NONNULL const bpx_profile_options_t* options);
+
+BPX_API uint64_t bpx_profile_get_row_count(BPX_NONNULL const bpx_profile_t* profile);
+
+BPX_API uint64_t bpx_profile_get_free_row_count(BPX_NONNULL const bpx_profile_t* profile);
+
+BPX_API bpx_column_profile_list_t bpx_profile_get_columns(BPX_NONNULL const bpx_profile_t* profile);
+
+BPX_API void bpx_profile_free(BPX_NONNULL bpx_profile_t* profile);
+
+#endif
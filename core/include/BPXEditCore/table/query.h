@@ -0,0 +1,84 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#ifndef BPX_TABLE_QUERY_H
+#define BPX_TABLE_QUERY_H
+
+#include <BPXEditCore/table/core.h>
+
+typedef void bpx_table_query_t;
+typedef void bpx_result_set_t;
+
+typedef enum bpx_compare_op_e {
+    BPX_COMPARE_OP_EQ = 0,
+    BPX_COMPARE_OP_NE,
+    BPX_COMPARE_OP_LT,
+    BPX_COMPARE_OP_LE,
+    BPX_COMPARE_OP_GT,
+    BPX_COMPARE_OP_GE
+} bpx_compare_op_t;
+
+BPX_SLICE(row_index_list, const uint64_t, indices);
+
+BPX_NONNULL BPX_API bpx_table_query_t* bpx_table_query_create(BPX_NONNULL const bpx_table_t* table);
+
+BPX_API bool bpx_table_query_where(BPX_NONNULL bpx_table_query_t* query, size_t column, bpx_compare_op_t op, BPX_NONNULL const bpx_value_t* value);
+
+BPX_API bool bpx_table_query_where_range(BPX_NONNULL bpx_table_query_t* query, size_t column, BPX_NONNULL const bpx_value_t* low, BPX_NONNULL const bpx_value_t* high);
+
+BPX_API bool bpx_table_query_where_prefix(BPX_NONNULL bpx_table_query_t* query, size_t column, BPX_NONNULL const char* prefix);
+
+BPX_API bool bpx_table_query_where_contains(BPX_NONNULL bpx_table_query_t* query, size_t column, BPX_NONNULL const char* text);
+
+BPX_API bool bpx_table_query_where_null(BPX_NONNULL bpx_table_query_t* query, size_t column, bool null);
+
+BPX_API bool bpx_table_query_order_by(BPX_NONNULL bpx_table_query_t* query, size_t column, bool descending);
+
+BPX_API bool bpx_table_query_select(BPX_NONNULL bpx_table_query_t* query, size_t column);
+
+BPX_API void bpx_table_query_set_offset(BPX_NONNULL bpx_table_query_t* query, size_t offset);
+
+BPX_API void bpx_table_query_set_limit(BPX_NONNULL bpx_table_query_t* query, size_t limit);
+
+BPX_NULLABLE BPX_API bpx_result_set_t* bpx_table_query_run(BPX_NONNULL const bpx_table_query_t* query);
+
+BPX_API void bpx_table_query_free(BPX_NONNULL bpx_table_query_t* query);
+
+BPX_API size_t bpx_result_set_get_row_count(BPX_NONNULL const bpx_result_set_t* result);
+
+BPX_API size_t bpx_result_set_get_column_count(BPX_NONNULL const bpx_result_set_t* result);
+
+BPX_NULLABLE BPX_API const char* bpx_result_set_get_column_name(BPX_NONNULL const bpx_result_set_t* result, size_t column);
+
+BPX_API bpx_row_index_list_t bpx_result_set_get_indices(BPX_NONNULL const bpx_result_set_t* result);
+
+BPX_NULLABLE BPX_API const bpx_value_t* bpx_result_set_get_value(BPX_NONNULL const bpx_result_set_t* result, size_t row, size_t column);
+
+BPX_API void bpx_result_set_free(BPX_NONNULL bpx_result_set_t* result);
+
+#endif
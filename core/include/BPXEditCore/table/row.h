@@ -37,6 +37,12 @@ BPX_NONNULL BPX_API const bpx_value_t* bpx_table_row_get_value_const(BPX_NONNULL
 
 BPX_NONNULL BPX_API bpx_value_t* bpx_table_row_get_value(BPX_NONNULL bpx_table_row_t* row, size_t index);
 
+BPX_API size_t bpx_table_row_get_element_count(BPX_NONNULL const bpx_table_row_t* row, size_t index);
+
+BPX_NULLABLE BPX_API const bpx_value_t* bpx_table_row_get_element_const(BPX_NONNULL const bpx_table_row_t* row, size_t index, size_t element);
+
+BPX_NULLABLE BPX_API bpx_value_t* bpx_table_row_get_element(BPX_NONNULL bpx_table_row_t* row, size_t index, size_t element);
+
 BPX_API bool bpx_table_row_is_free(BPX_NONNULL const bpx_table_row_t* row);
 
 BPX_API void bpx_table_row_set_free(BPX_NONNULL bpx_table_row_t* row, bool free);
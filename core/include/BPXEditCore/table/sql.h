@@ -1,4 +1,4 @@
-// Copyright (c) 2025, BlockProject 3D
+// Copyright (c) 2026, BlockProject 3D
 //
 // All rights reserved.
 //
@@ -26,21 +26,11 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use safer_ffi::prelude::*;
-use crate::common::{try_with_section, Container};
-use crate::error::unwrap_result;
-use crate::tree::model::Node;
+#ifndef BPX_TABLE_SQL_H
+#define BPX_TABLE_SQL_H
 
-#[ffi_export]
-pub fn bpxsd_read_from_bytes(buffer: c_slice::Ref<'_, u8>) -> Option<repr_c::Box<Node>> {
-    let value = unwrap_result(bpx::sd::Value::read(buffer.as_slice(), 4))?;
-    unwrap_result(Node::try_from(value.as_object()?).map(|v| Box::new(v).into()))
-}
+#include <BPXEditCore/table/query.h>
 
-#[ffi_export]
-pub fn bpxsd_read_from_section(container: &Container, handle: u32) -> Option<repr_c::Box<Node>> {
-    let value = try_with_section(container, handle, |v| {
-        bpx::sd::Value::read(v, 4)
-    })?;
-    unwrap_result(Node::try_from(value.as_object()?).map(|v| Box::new(v).into()))
-}
+BPX_NULLABLE BPX_API bpx_result_set_t* bpx_sql_execute(BPX_NONNULL const bpx_container_t* container, uint32_t strings, BPX_NONNULL const char* sql);
+
+#endif
@@ -0,0 +1,62 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#ifndef BPX_TABLE_VALIDATE_H
+#define BPX_TABLE_VALIDATE_H
+
+#include <BPXEditCore/table/core.h>
+
+typedef void bpx_table_validation_t;
+
+typedef enum bpx_issue_kind_e {
+    BPX_ISSUE_KIND_TABLE_NAME = 0,
+    BPX_ISSUE_KIND_COLUMN_NAME,
+    BPX_ISSUE_KIND_CELL
+} bpx_issue_kind_t;
+
+typedef struct bpx_issue_s {
+    bpx_issue_kind_t kind;
+    uint64_t row;
+    uint64_t column;
+    uint64_t element;
+    BPX_NONNULL const char* message;
+} bpx_issue_t;
+
+BPX_SLICE(issue_list, const bpx_issue_t, issues);
+
+BPX_NULLABLE BPX_API bpx_table_validation_t* bpx_table_validate(BPX_NONNULL const bpx_table_t* table);
+
+BPX_API bool bpx_table_validation_is_valid(BPX_NONNULL const bpx_table_validation_t* validation);
+
+BPX_API uint64_t bpx_table_validation_get_row_count(BPX_NONNULL const bpx_table_validation_t* validation);
+
+BPX_API bpx_issue_list_t bpx_table_validation_get_issues(BPX_NONNULL const bpx_table_validation_t* validation);
+
+BPX_API void bpx_table_validation_free(BPX_NONNULL bpx_table_validation_t* validation);
+
+#endif
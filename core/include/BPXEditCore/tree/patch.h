@@ -0,0 +1,53 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#ifndef BPX_PATCH_H
+#define BPX_PATCH_H
+
+#include <BPXEditCore/tree/tree.h>
+#include <BPXEditCore/bpxsd.h>
+
+typedef void bpx_patch_t;
+
+BPX_NONNULL BPX_API bpx_patch_t* bpx_patch_create(BPX_NONNULL const bpx_node_t* a, BPX_NONNULL const bpx_node_t* b);
+
+BPX_NULLABLE BPX_API bpx_patch_t* bpx_patch_from_json(BPX_NONNULL const char* json);
+
+BPX_NONNULL BPX_API char* bpx_patch_to_json(BPX_NONNULL const bpx_patch_t* patch, bool pretty);
+
+BPX_API void bpx_patch_json_free(BPX_NONNULL char* json);
+
+BPX_API size_t bpx_patch_get_count(BPX_NONNULL const bpx_patch_t* patch);
+
+BPX_API bool bpx_patch_apply(BPX_NONNULL const bpx_patch_t* patch, BPX_NONNULL bpx_node_t* node);
+
+BPX_API bool bpx_patch_apply_to_section(BPX_NONNULL const bpx_patch_t* patch, BPX_NONNULL const bpx_container_t* container, bpx_section_handle_t section, BPX_NONNULL const bpxsd_reader_options_t* options, BPX_NULLABLE const bpxsd_dictionary_t* dictionary);
+
+BPX_API void bpx_patch_free(BPX_NONNULL bpx_patch_t* patch);
+
+#endif
@@ -0,0 +1,50 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#ifndef BPX_QUERY_H
+#define BPX_QUERY_H
+
+#include <BPXEditCore/tree/tree.h>
+
+typedef void bpx_query_result_t;
+
+BPX_NULLABLE BPX_API bpx_query_result_t* bpx_node_query(BPX_NONNULL const bpx_node_t* node, BPX_NONNULL const char* query);
+
+BPX_API size_t bpx_query_result_get_count(BPX_NONNULL const bpx_query_result_t* result);
+
+BPX_NULLABLE BPX_API const bpx_node_t* bpx_query_result_get_node(BPX_NONNULL const bpx_query_result_t* result, BPX_NONNULL const bpx_node_t* root, size_t index);
+
+BPX_NULLABLE BPX_API const char* bpx_query_result_get_path(BPX_NONNULL const bpx_query_result_t* result, size_t index);
+
+BPX_API void bpx_query_result_free(BPX_NONNULL bpx_query_result_t* result);
+
+BPX_NULLABLE BPX_API const bpx_node_t* bpx_node_get_by_path(BPX_NONNULL const bpx_node_t* node, BPX_NONNULL const char* path);
+
+BPX_NULLABLE BPX_API bpx_value_t* bpx_node_get_value_by_path_mut(BPX_NONNULL bpx_node_t* node, BPX_NONNULL const char* path);
+
+#endif
@@ -0,0 +1,72 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#ifndef BPX_SCHEMA_H
+#define BPX_SCHEMA_H
+
+#include <BPXEditCore/tree/tree.h>
+#include <BPXEditCore/bpxsd.h>
+
+typedef void bpx_schema_t;
+typedef void bpx_validation_t;
+
+typedef enum bpx_violation_kind_e {
+    BPX_VIOLATION_KIND_TYPE = 0,
+    BPX_VIOLATION_KIND_RANGE,
+    BPX_VIOLATION_KIND_LENGTH,
+    BPX_VIOLATION_KIND_MISSING,
+    BPX_VIOLATION_KIND_UNEXPECTED
+} bpx_violation_kind_t;
+
+typedef struct bpx_violation_s {
+    bpx_violation_kind_t kind;
+    BPX_NONNULL const char* path;
+    BPX_NONNULL const char* message;
+} bpx_violation_t;
+
+BPX_SLICE(violation_list, const bpx_violation_t, violations);
+
+BPX_NULLABLE BPX_API bpx_schema_t* bpx_schema_load_json(BPX_NONNULL const char* json);
+
+BPX_NULLABLE BPX_API bpx_schema_t* bpx_schema_load_bpxsd(bpx_bytes_const_t buffer);
+
+BPX_API void bpx_schema_free(BPX_NONNULL bpx_schema_t* schema);
+
+BPX_NONNULL BPX_API bpx_validation_t* bpx_schema_validate(BPX_NONNULL const bpx_schema_t* schema, BPX_NONNULL const bpx_node_t* node);
+
+BPX_NULLABLE BPX_API bpx_validation_t* bpx_schema_validate_section(BPX_NONNULL const bpx_schema_t* schema, BPX_NONNULL const bpx_container_t* container, bpx_section_handle_t section);
+
+BPX_NULLABLE BPX_API bpx_validation_t* bpx_schema_validate_section_with_options(BPX_NONNULL const bpx_schema_t* schema, BPX_NONNULL const bpx_container_t* container, bpx_section_handle_t section, BPX_NONNULL const bpxsd_reader_options_t* options, BPX_NULLABLE const bpxsd_dictionary_t* dictionary);
+
+BPX_API bool bpx_validation_is_valid(BPX_NONNULL const bpx_validation_t* validation);
+
+BPX_API bpx_violation_list_t bpx_validation_get_violations(BPX_NONNULL const bpx_validation_t* validation);
+
+BPX_API void bpx_validation_free(BPX_NONNULL bpx_validation_t* validation);
+
+#endif
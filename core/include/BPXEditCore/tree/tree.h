@@ -45,4 +45,8 @@ BPX_NULLABLE BPX_API const bpx_node_t* bpx_node_get_details(BPX_NONNULL const bp
 
 BPX_API bpx_node_children_list_t bpx_node_get_children(BPX_NONNULL const bpx_node_t* node);
 
+BPX_API bool bpx_node_equals(BPX_NONNULL const bpx_node_t* a, BPX_NONNULL const bpx_node_t* b);
+
+BPX_API uint64_t bpx_node_hash(BPX_NONNULL const bpx_node_t* node);
+
 #endif
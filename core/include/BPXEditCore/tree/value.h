@@ -49,8 +49,24 @@ typedef enum bpx_value_type_e {
     BPX_VALUE_TYPE_STRING
 } bpx_value_type_t;
 
+typedef struct bpx_value_format_options_s {
+    bool hex;
+    bool uppercase;
+    bool prefix;
+    bool exponent;
+    int32_t precision;
+} bpx_value_format_options_t;
+
 BPX_API bpx_value_type_t bpx_value_get_type(BPX_NONNULL const bpx_value_t* value);
 
+/* Comparison */
+
+BPX_API int32_t bpx_value_compare(BPX_NONNULL const bpx_value_t* a, BPX_NONNULL const bpx_value_t* b);
+
+BPX_API bool bpx_value_equals(BPX_NONNULL const bpx_value_t* a, BPX_NONNULL const bpx_value_t* b);
+
+BPX_API uint64_t bpx_value_hash(BPX_NONNULL const bpx_value_t* value);
+
 /* Getters */
 
 BPX_API bool bpx_value_is_null(BPX_NONNULL const bpx_value_t* value);
@@ -79,6 +95,42 @@ BPX_API bool bpx_value_get_boolean(BPX_NONNULL const bpx_value_t* value);
 
 BPX_NULLABLE BPX_API const char* bpx_value_get_string(BPX_NONNULL const bpx_value_t* value);
 
+/* Checked getters */
+
+BPX_API bool bpx_value_try_get_int8(BPX_NONNULL const bpx_value_t* value, BPX_NONNULL int8_t* out);
+
+BPX_API bool bpx_value_try_get_uint8(BPX_NONNULL const bpx_value_t* value, BPX_NONNULL uint8_t* out);
+
+BPX_API bool bpx_value_try_get_int16(BPX_NONNULL const bpx_value_t* value, BPX_NONNULL int16_t* out);
+
+BPX_API bool bpx_value_try_get_uint16(BPX_NONNULL const bpx_value_t* value, BPX_NONNULL uint16_t* out);
+
+BPX_API bool bpx_value_try_get_int32(BPX_NONNULL const bpx_value_t* value, BPX_NONNULL int32_t* out);
+
+BPX_API bool bpx_value_try_get_uint32(BPX_NONNULL const bpx_value_t* value, BPX_NONNULL uint32_t* out);
+
+BPX_API bool bpx_value_try_get_int64(BPX_NONNULL const bpx_value_t* value, BPX_NONNULL int64_t* out);
+
+BPX_API bool bpx_value_try_get_uint64(BPX_NONNULL const bpx_value_t* value, BPX_NONNULL uint64_t* out);
+
+BPX_API bool bpx_value_try_get_float(BPX_NONNULL const bpx_value_t* value, BPX_NONNULL float32_t* out);
+
+BPX_API bool bpx_value_try_get_double(BPX_NONNULL const bpx_value_t* value, BPX_NONNULL float64_t* out);
+
+BPX_API bool bpx_value_try_get_boolean(BPX_NONNULL const bpx_value_t* value, BPX_NONNULL bool* out);
+
+BPX_API bool bpx_value_convert(BPX_NONNULL bpx_value_t* value, bpx_value_type_t type);
+
+/* Text */
+
+BPX_API void bpx_value_format_options_default(BPX_NONNULL bpx_value_format_options_t* options);
+
+BPX_NONNULL BPX_API char* bpx_value_format(BPX_NONNULL const bpx_value_t* value, BPX_NULLABLE const bpx_value_format_options_t* options);
+
+BPX_API void bpx_value_format_free(BPX_NONNULL char* text);
+
+BPX_API bool bpx_value_parse(BPX_NONNULL bpx_value_t* value, BPX_NONNULL const char* text, bpx_value_type_t type);
+
 /* Setters */
 
 BPX_API void bpx_value_set_null(BPX_NONNULL bpx_value_t* value);
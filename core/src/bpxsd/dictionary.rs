@@ -0,0 +1,90 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::HashMap;
+use std::path::Path;
+use bpx::util::hash::hash;
+use safer_ffi::prelude::*;
+use crate::error::unwrap_result;
+
+#[derive_ReprC]
+#[repr(opaque)]
+#[derive(Default, Clone)]
+pub struct Dictionary {
+    names: HashMap<u64, String>
+}
+
+impl Dictionary {
+    pub fn add(&mut self, name: &str) {
+        self.names.insert(hash(name), name.into());
+    }
+
+    pub fn load(&mut self, path: &Path) -> std::io::Result<()> {
+        let text = std::fs::read_to_string(path)?;
+        for line in text.lines().map(|v| v.trim()).filter(|v| !v.is_empty()) {
+            self.add(line);
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, hash: u64) -> Option<&str> {
+        self.names.get(&hash).map(|v| &**v)
+    }
+}
+
+#[ffi_export]
+pub fn bpxsd_dictionary_create() -> repr_c::Box<Dictionary> {
+    Box::<Dictionary>::default().into()
+}
+
+#[ffi_export]
+pub fn bpxsd_dictionary_free(dictionary: repr_c::Box<Dictionary>) {
+    drop(dictionary);
+}
+
+#[ffi_export]
+pub fn bpxsd_dictionary_add(dictionary: &mut Dictionary, name: char_p::Ref<'_>) {
+    dictionary.add(name.to_str());
+}
+
+#[ffi_export]
+pub fn bpxsd_dictionary_add_list(dictionary: &mut Dictionary, names: c_slice::Ref<'_, char_p::Ref<'_>>) {
+    for name in names.as_slice() {
+        dictionary.add(name.to_str());
+    }
+}
+
+#[ffi_export]
+pub fn bpxsd_dictionary_load(dictionary: &mut Dictionary, path: char_p::Ref<'_>) -> bool {
+    unwrap_result(dictionary.load(Path::new(path.to_str()))).is_some()
+}
+
+#[ffi_export]
+pub fn bpxsd_dictionary_get_count(dictionary: &Dictionary) -> usize {
+    dictionary.names.len()
+}
@@ -0,0 +1,115 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use safer_ffi::prelude::*;
+use crate::bpxsd::dictionary::Dictionary;
+use crate::bpxsd::reader::{Error, Reader, ReaderOptions};
+use crate::common::{read_all, try_with_section, Container};
+use crate::error::unwrap_result;
+use crate::tree::model::Node;
+
+/// Iterates over BPXSD objects stored one after the other. A zero byte encodes an object without
+/// entries, so zero padding between or after objects yields empty objects. The iteration ends at
+/// the first object which fails to decode, and the offset stays at the start of that object.
+#[derive_ReprC]
+#[repr(opaque)]
+pub struct ObjectIter {
+    data: Vec<u8>,
+    pos: usize,
+    failed: bool,
+    options: ReaderOptions,
+    dictionary: Option<Dictionary>
+}
+
+impl ObjectIter {
+    pub fn next_object(&mut self) -> Result<Option<Node>, Error> {
+        if self.is_end() {
+            return Ok(None);
+        }
+        let mut reader = Reader::new(&self.data, &self.options, self.dictionary.as_ref());
+        reader.set_position(self.pos);
+        let node = reader.read_root().inspect_err(|_| self.failed = true)?;
+        self.pos = reader.position();
+        Ok(Some(node))
+    }
+
+    pub fn is_end(&self) -> bool {
+        self.failed || self.pos >= self.data.len()
+    }
+}
+
+pub fn read_at(data: &[u8], offset: usize, options: &ReaderOptions, dictionary: Option<&Dictionary>) -> Result<(Node, usize), Error> {
+    let mut reader = Reader::new(data, options, dictionary);
+    reader.set_position(offset);
+    let node = reader.read_root()?;
+    Ok((node, reader.position() - offset))
+}
+
+#[ffi_export]
+pub fn bpxsd_read_from_section_at(container: &Container, handle: u32, offset: u64, options: &ReaderOptions,
+                                  dictionary: Option<&Dictionary>, consumed: Option<&mut u64>) -> Option<repr_c::Box<Node>> {
+    let data = try_with_section(container, handle, read_all)?;
+    let (node, size) = unwrap_result(read_at(&data, offset as _, options, dictionary))?;
+    if let Some(consumed) = consumed {
+        *consumed = size as _;
+    }
+    Some(Box::new(node).into())
+}
+
+#[ffi_export]
+pub fn bpxsd_iter_section(container: &Container, handle: u32, offset: u64, options: &ReaderOptions,
+                          dictionary: Option<&Dictionary>) -> Option<repr_c::Box<ObjectIter>> {
+    let data = try_with_section(container, handle, read_all)?;
+    Some(Box::new(ObjectIter {
+        pos: (offset as usize).min(data.len()),
+        failed: false,
+        data,
+        options: *options,
+        dictionary: dictionary.cloned()
+    }).into())
+}
+
+#[ffi_export]
+pub fn bpxsd_iter_next(iter: &mut ObjectIter) -> Option<repr_c::Box<Node>> {
+    unwrap_result(iter.next_object())?.map(|v| Box::new(v).into())
+}
+
+#[ffi_export]
+pub fn bpxsd_iter_is_end(iter: &ObjectIter) -> bool {
+    iter.is_end()
+}
+
+#[ffi_export]
+pub fn bpxsd_iter_get_offset(iter: &ObjectIter) -> u64 {
+    iter.pos as _
+}
+
+#[ffi_export]
+pub fn bpxsd_iter_free(iter: repr_c::Box<ObjectIter>) {
+    drop(iter);
+}
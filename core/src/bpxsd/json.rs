@@ -0,0 +1,406 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Lossless conversion between BPXSD objects and JSON.
+//!
+//! Objects are decoded with the BPXSD reader, which keeps object keys in the order in which they
+//! are stored, so a document converted to JSON and back encodes to the same bytes.
+//!
+//! JSON cannot hold every BPXSD type, so the following convention is used:
+//!
+//! * null, bool, string, array and object values map to the matching JSON types.
+//! * int64 values are written as plain JSON integers and double values as plain JSON numbers
+//!   with a fractional part or an exponent. When reading, a plain integer becomes an int64 (or a
+//!   uint64 when it does not fit) and any other number becomes a double.
+//! * Every other scalar is wrapped in an object with a single key naming its BPXSD type, for
+//!   example `{"$uint8": 5}` or `{"$float": 0.5}`. Non finite floats are written as the strings
+//!   `"NaN"`, `"inf"` and `"-inf"`, for example `{"$double": "NaN"}`.
+//! * Object keys are the names found in the `__debug__` symbol list of the object. Keys without
+//!   a name are written as `#` followed by the 16 hexadecimal digits of the key hash.
+//! * Names starting with `$` or `#` are escaped with an extra leading `$`.
+
+use std::ffi::CString;
+use bp3d_util::simple_error;
+use bpx::util::hash::hash;
+use serde_json::{Map, Number};
+use crate::bpxsd::reader::{self, set_key_details, type_details, Reader, ReaderOptions, CODE_ARRAY, CODE_OBJECT, TYPE_NAMES};
+use crate::bpxsd::writer;
+use crate::tree::model::{Node, Value};
+
+simple_error! {
+    pub Error {
+        CapacityExceeded(usize) => "capacity exceeded ({} > 255)",
+        NotAnObject => "JSON root value is not an object",
+        InvalidAnnotation(String) => "invalid type annotation ({})",
+        (impl From) Reader(reader::Error) => "{}",
+        (impl From) Json(serde_json::Error) => "JSON error: {}"
+    }
+}
+
+pub const DEBUG_KEY: &str = "__debug__";
+
+fn detail<'a>(node: &'a Node, name: &str) -> Option<&'a Value> {
+    node.details.as_ref()?.children.iter().find(|v| v.name.to_str() == name).map(|v| &v.value)
+}
+
+fn key_name(node: &Node) -> String {
+    let key = match detail(node, "hash") {
+        Some(Value::UInt64(v)) => *v,
+        _ => 0
+    };
+    let name = node.name.to_str();
+    // Names which do not hash back to the key (for example because the reader dropped a NUL from
+    // them) are written as hashes so that the key survives the round trip.
+    match detail(node, "name_origin").map(|v| v.as_str()) == Some("debug") && hash(name) == key {
+        true if name == DEBUG_KEY => DEBUG_KEY.into(),
+        true => escape_name(name),
+        false => format!("#{:016X}", key)
+    }
+}
+
+fn to_value(node: &Node) -> serde_json::Value {
+    use serde_json::Value as Json;
+    match &node.value {
+        Value::Null => match detail(node, "type").map(|v| v.as_str()) {
+            Some("array") => Json::Array(node.children.iter().map(to_value).collect()),
+            Some("object") => Json::Object(to_object(node)),
+            _ => Json::Null
+        },
+        Value::Boolean(v) => Json::Bool(*v),
+        Value::UInt8(v) => annotate(0x2, (*v).into()),
+        Value::UInt16(v) => annotate(0x3, (*v).into()),
+        Value::UInt32(v) => annotate(0x4, (*v).into()),
+        Value::UInt64(v) => annotate(0x5, (*v).into()),
+        Value::Int8(v) => annotate(0x6, (*v).into()),
+        Value::Int16(v) => annotate(0x7, (*v).into()),
+        Value::Int32(v) => annotate(0x8, (*v).into()),
+        Value::Int64(v) => (*v).into(),
+        Value::Float(v) => match v.is_finite() {
+            true => annotate(0xA, Json::Number(format!("{}", v).parse().unwrap())),
+            false => annotate(0xA, non_finite(*v as f64))
+        },
+        Value::Double(v) => match Number::from_f64(*v) {
+            Some(v) => Json::Number(v),
+            None => annotate(0xB, non_finite(*v))
+        },
+        Value::String(v) => Json::String(v.to_str().into())
+    }
+}
+
+fn to_object(node: &Node) -> Map<String, serde_json::Value> {
+    node.children.iter().map(|v| (key_name(v), to_value(v))).collect()
+}
+
+fn annotate(code: u8, value: serde_json::Value) -> serde_json::Value {
+    let mut map = Map::new();
+    map.insert(format!("${}", TYPE_NAMES[code as usize]), value);
+    serde_json::Value::Object(map)
+}
+
+fn non_finite(v: f64) -> serde_json::Value {
+    if v.is_nan() {
+        "NaN".into()
+    } else if v > 0.0 {
+        "inf".into()
+    } else {
+        "-inf".into()
+    }
+}
+
+fn escape_name(name: &str) -> String {
+    match name.starts_with('$') || name.starts_with('#') {
+        true => format!("${}", name),
+        false => name.into()
+    }
+}
+
+/// Returns the key hash for a JSON object key following the naming convention of this module.
+pub fn key_hash(key: &str) -> u64 {
+    if let Some(name) = key.strip_prefix('$') {
+        return hash(name);
+    }
+    if let Some(hex) = key.strip_prefix('#') {
+        if hex.len() == 16 {
+            if let Ok(v) = u64::from_str_radix(hex, 16) {
+                return v;
+            }
+        }
+    }
+    hash(key)
+}
+
+/// Converts the BPXSD object at the start of `data` to JSON, returning the JSON value and the
+/// number of bytes consumed.
+pub fn to_json(data: &[u8], options: &ReaderOptions) -> Result<(serde_json::Value, usize), Error> {
+    let mut reader = Reader::new(data, options, None).keep_debug();
+    let root = reader.read_root()?;
+    Ok((serde_json::Value::Object(to_object(&root)), reader.position()))
+}
+
+fn parse_float(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::String(v) => match &**v {
+            "NaN" => Some(f64::NAN),
+            "inf" => Some(f64::INFINITY),
+            "-inf" => Some(f64::NEG_INFINITY),
+            _ => None
+        },
+        v => v.as_f64()
+    }
+}
+
+fn annotated_value(ty: &str, value: &serde_json::Value) -> Result<Value, Error> {
+    let invalid = || Error::InvalidAnnotation(format!("${}: {}", ty, value));
+    let code = TYPE_NAMES.iter().position(|v| *v == ty).ok_or_else(invalid)?;
+    Ok(match code {
+        0x2 => Value::UInt8(value.as_u64().and_then(|v| v.try_into().ok()).ok_or_else(invalid)?),
+        0x3 => Value::UInt16(value.as_u64().and_then(|v| v.try_into().ok()).ok_or_else(invalid)?),
+        0x4 => Value::UInt32(value.as_u64().and_then(|v| v.try_into().ok()).ok_or_else(invalid)?),
+        0x5 => Value::UInt64(value.as_u64().ok_or_else(invalid)?),
+        0x6 => Value::Int8(value.as_i64().and_then(|v| v.try_into().ok()).ok_or_else(invalid)?),
+        0x7 => Value::Int16(value.as_i64().and_then(|v| v.try_into().ok()).ok_or_else(invalid)?),
+        0x8 => Value::Int32(value.as_i64().and_then(|v| v.try_into().ok()).ok_or_else(invalid)?),
+        0x9 => Value::Int64(value.as_i64().ok_or_else(invalid)?),
+        0xA => {
+            let v = match value {
+                serde_json::Value::Number(v) => v.to_string().parse::<f32>().ok(),
+                v => parse_float(v).map(|v| v as f32)
+            };
+            Value::Float(v.ok_or_else(invalid)?)
+        },
+        0xB => Value::Double(parse_float(value).ok_or_else(invalid)?),
+        _ => return Err(invalid())
+    })
+}
+
+struct Writer {
+    out: Vec<u8>,
+    depth: u32
+}
+
+impl Writer {
+    fn enter(&mut self) -> Result<(), Error> {
+        if self.depth == 0 {
+            return Err(reader::Error::MaxDepthExceeded.into());
+        }
+        self.depth -= 1;
+        Ok(())
+    }
+
+    fn write_annotated(&mut self, ty: &str, value: &serde_json::Value) -> Result<u8, Error> {
+        Ok(writer::write_scalar(&mut self.out, &annotated_value(ty, value)?))
+    }
+
+    fn write_value(&mut self, value: &serde_json::Value) -> Result<u8, Error> {
+        use serde_json::Value as Json;
+        Ok(match value {
+            Json::Null => 0x0,
+            Json::Bool(v) => {
+                self.out.push(*v as u8);
+                0x1
+            },
+            Json::Number(v) => match (v.as_i64(), v.as_u64()) {
+                (Some(v), _) => {
+                    self.out.extend_from_slice(&v.to_le_bytes());
+                    0x9
+                },
+                (None, Some(v)) => {
+                    self.out.extend_from_slice(&v.to_le_bytes());
+                    0x5
+                },
+                _ => {
+                    self.out.extend_from_slice(&v.as_f64().unwrap_or_default().to_le_bytes());
+                    0xB
+                }
+            },
+            Json::String(v) => {
+                self.out.extend_from_slice(v.as_bytes());
+                self.out.push(0);
+                0xC
+            },
+            Json::Array(v) => {
+                if v.len() > 255 {
+                    return Err(Error::CapacityExceeded(v.len()));
+                }
+                self.enter()?;
+                self.out.push(v.len() as u8);
+                for item in v {
+                    let pos = self.out.len();
+                    self.out.push(0);
+                    self.out[pos] = self.write_value(item)?;
+                }
+                self.depth += 1;
+                0xD
+            },
+            Json::Object(v) => {
+                if let Some((ty, value)) = annotation(value) {
+                    return self.write_annotated(ty, value);
+                }
+                self.write_object(v)?;
+                0xE
+            }
+        })
+    }
+
+    fn write_object(&mut self, object: &Map<String, serde_json::Value>) -> Result<(), Error> {
+        if object.len() > 255 {
+            return Err(Error::CapacityExceeded(object.len()));
+        }
+        self.enter()?;
+        self.out.push(object.len() as u8);
+        for (key, value) in object {
+            self.out.extend_from_slice(&key_hash(key).to_le_bytes());
+            let pos = self.out.len();
+            self.out.push(0);
+            self.out[pos] = self.write_value(value)?;
+        }
+        self.depth += 1;
+        Ok(())
+    }
+}
+
+/// Encodes a JSON object following the convention of this module to BPXSD. Only the depth limit
+/// of `options` applies.
+pub fn from_json(value: &serde_json::Value, options: &ReaderOptions) -> Result<Vec<u8>, Error> {
+    let object = value.as_object().ok_or(Error::NotAnObject)?;
+    let mut writer = Writer { out: Vec::new(), depth: options.max_depth };
+    writer.write_object(object)?;
+    Ok(writer.out)
+}
+
+/// Converts the BPXSD object at the start of `data` to JSON text.
+pub fn encode(data: &[u8], options: &ReaderOptions, pretty: bool) -> Result<String, Error> {
+    let (value, _) = to_json(data, options)?;
+    Ok(match pretty {
+        true => serde_json::to_string_pretty(&value)?,
+        false => serde_json::to_string(&value)?
+    })
+}
+
+/// Converts JSON text to a BPXSD object.
+pub fn decode(text: &str, options: &ReaderOptions) -> Result<Vec<u8>, Error> {
+    from_json(&serde_json::from_str(text)?, options)
+}
+
+fn annotation(value: &serde_json::Value) -> Option<(&str, &serde_json::Value)> {
+    let object = value.as_object().filter(|v| v.len() == 1)?;
+    let (key, value) = object.iter().next().unwrap();
+    key.strip_prefix('$').filter(|v| !v.starts_with('$')).map(|ty| (ty, value))
+}
+
+fn new_node(name: &str, value: Value, code: u8) -> Node {
+    Node {
+        name: CString::new(name.replace('\0', "")).unwrap().into(),
+        value,
+        details: Some(Box::new(type_details(code)).into()),
+        children: Vec::new()
+    }
+}
+
+/// Converts a JSON value following the convention of this module to a node named `name`.
+pub fn to_node(name: &str, value: &serde_json::Value) -> Result<Node, Error> {
+    use serde_json::Value as Json;
+    if let Some((ty, value)) = annotation(value) {
+        let value = annotated_value(ty, value)?;
+        let code = writer::write_scalar(&mut Vec::new(), &value);
+        return Ok(new_node(name, value, code));
+    }
+    Ok(match value {
+        Json::Null => new_node(name, Value::Null, 0x0),
+        Json::Bool(v) => new_node(name, Value::Boolean(*v), 0x1),
+        Json::Number(v) => match (v.as_i64(), v.as_u64()) {
+            (Some(v), _) => new_node(name, Value::Int64(v), 0x9),
+            (None, Some(v)) => new_node(name, Value::UInt64(v), 0x5),
+            _ => new_node(name, Value::Double(v.as_f64().unwrap_or_default()), 0xB)
+        },
+        Json::String(v) => new_node(name, Value::String(CString::new(v.replace('\0', "")).unwrap().into()), 0xC),
+        Json::Array(v) => {
+            let mut node = new_node(name, Value::Null, CODE_ARRAY);
+            node.children = v.iter().map(|v| to_node("", v)).collect::<Result<_, _>>()?;
+            node
+        },
+        Json::Object(v) => {
+            let mut node = new_node(name, Value::Null, CODE_OBJECT);
+            for (key, value) in v {
+                let key_hash = key_hash(key);
+                let child = match key.starts_with('#') && key_hash != hash(key) {
+                    true => {
+                        let mut child = to_node(&format!("{:X}", key_hash), value)?;
+                        if let Some(details) = &mut child.details {
+                            set_key_details(details, key_hash, "hash");
+                        }
+                        child
+                    },
+                    false => to_node(key.strip_prefix('$').unwrap_or(key), value)?
+                };
+                node.children.push(child);
+            }
+            node
+        }
+    })
+}
+
+fn scalar_json(value: &Value) -> serde_json::Value {
+    use serde_json::Value as Json;
+    let code = writer::write_scalar(&mut Vec::new(), value);
+    match value {
+        Value::Null => Json::Null,
+        Value::Boolean(v) => Json::Bool(*v),
+        Value::Int64(v) => (*v).into(),
+        Value::Double(v) => match Number::from_f64(*v) {
+            Some(v) => Json::Number(v),
+            None => annotate(code, non_finite(*v))
+        },
+        Value::Float(v) => match v.is_finite() {
+            true => annotate(code, Json::Number(format!("{}", v).parse().unwrap())),
+            false => annotate(code, non_finite(*v as f64))
+        },
+        Value::String(v) => Json::String(v.to_str().into()),
+        Value::UInt64(v) => annotate(code, (*v).into()),
+        v if code <= 0x5 => annotate(code, v.as_u64().into()),
+        v => annotate(code, v.as_i64().into())
+    }
+}
+
+/// Converts a node to a JSON value following the convention of this module.
+pub fn from_node(node: &Node) -> serde_json::Value {
+    use serde_json::Value as Json;
+    match writer::type_code(node) {
+        CODE_ARRAY => Json::Array(node.children.iter().map(from_node).collect()),
+        CODE_OBJECT => Json::Object(node.children.iter()
+            .map(|v| {
+                let key = match writer::unresolved_hash(v) {
+                    Some(hash) => format!("#{:016X}", hash),
+                    None => escape_name(v.name.to_str())
+                };
+                (key, from_node(v))
+            })
+            .collect()),
+        _ => scalar_json(&node.value)
+    }
+}
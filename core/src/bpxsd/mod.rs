@@ -0,0 +1,153 @@
+// Copyright (c) 2025, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+pub mod json;
+pub mod dictionary;
+pub mod reader;
+pub mod iter;
+pub mod writer;
+
+use std::ffi::CString;
+use std::io::{Seek, SeekFrom, Write};
+use bpx::core::SectionData;
+use safer_ffi::prelude::*;
+use crate::common::{read_all, try_with_section, try_with_section_mut, Container};
+use crate::error::unwrap_result;
+use crate::tree::model::Node;
+use crate::bpxsd::dictionary::Dictionary;
+use crate::bpxsd::reader::{Reader, ReaderOptions};
+
+fn read_node(data: &[u8], options: &ReaderOptions, dictionary: Option<&Dictionary>) -> Option<repr_c::Box<Node>> {
+    unwrap_result(Reader::new(data, options, dictionary).read_root()).map(|v| Box::new(v).into())
+}
+
+#[ffi_export]
+pub unsafe fn bpxsd_reader_options_default(options: *mut ReaderOptions) {
+    *options = ReaderOptions::default();
+}
+
+#[ffi_export]
+pub fn bpxsd_read_from_bytes(buffer: c_slice::Ref<'_, u8>) -> Option<repr_c::Box<Node>> {
+    read_node(buffer.as_slice(), &ReaderOptions::default(), None)
+}
+
+#[ffi_export]
+pub fn bpxsd_read_from_section(container: &Container, handle: u32) -> Option<repr_c::Box<Node>> {
+    let data = try_with_section(container, handle, read_all)?;
+    read_node(&data, &ReaderOptions::default(), None)
+}
+
+#[ffi_export]
+pub fn bpxsd_read_from_bytes_with_dictionary(buffer: c_slice::Ref<'_, u8>, dictionary: &Dictionary) -> Option<repr_c::Box<Node>> {
+    read_node(buffer.as_slice(), &ReaderOptions::default(), Some(dictionary))
+}
+
+#[ffi_export]
+pub fn bpxsd_read_from_section_with_dictionary(container: &Container, handle: u32, dictionary: &Dictionary) -> Option<repr_c::Box<Node>> {
+    let data = try_with_section(container, handle, read_all)?;
+    read_node(&data, &ReaderOptions::default(), Some(dictionary))
+}
+
+#[ffi_export]
+pub fn bpxsd_read_from_bytes_with_options(buffer: c_slice::Ref<'_, u8>, options: &ReaderOptions, dictionary: Option<&Dictionary>) -> Option<repr_c::Box<Node>> {
+    read_node(buffer.as_slice(), options, dictionary)
+}
+
+#[ffi_export]
+pub fn bpxsd_read_from_section_with_options(container: &Container, handle: u32, options: &ReaderOptions, dictionary: Option<&Dictionary>) -> Option<repr_c::Box<Node>> {
+    let data = try_with_section(container, handle, read_all)?;
+    read_node(&data, options, dictionary)
+}
+
+fn json_to_c(text: String) -> char_p::Box {
+    CString::new(text).unwrap().into()
+}
+
+#[ffi_export]
+pub fn bpxsd_to_json_from_bytes(buffer: c_slice::Ref<'_, u8>, pretty: bool) -> Option<char_p::Box> {
+    bpxsd_to_json_from_bytes_with_options(buffer, &ReaderOptions::default(), pretty)
+}
+
+#[ffi_export]
+pub fn bpxsd_to_json_from_section(container: &Container, handle: u32, pretty: bool) -> Option<char_p::Box> {
+    bpxsd_to_json_from_section_with_options(container, handle, &ReaderOptions::default(), pretty)
+}
+
+#[ffi_export]
+pub fn bpxsd_to_json_from_bytes_with_options(buffer: c_slice::Ref<'_, u8>, options: &ReaderOptions, pretty: bool) -> Option<char_p::Box> {
+    unwrap_result(json::encode(buffer.as_slice(), options, pretty)).map(json_to_c)
+}
+
+#[ffi_export]
+pub fn bpxsd_to_json_from_section_with_options(container: &Container, handle: u32, options: &ReaderOptions, pretty: bool) -> Option<char_p::Box> {
+    let data = try_with_section(container, handle, read_all)?;
+    unwrap_result(json::encode(&data, options, pretty)).map(json_to_c)
+}
+
+#[ffi_export]
+pub fn bpxsd_json_free(json: char_p::Box) {
+    drop(json);
+}
+
+#[ffi_export]
+pub fn bpxsd_from_json(json: char_p::Ref<'_>, buffer: c_slice::Mut<'_, u8>) -> isize {
+    bpxsd_from_json_with_options(json, &ReaderOptions::default(), buffer)
+}
+
+#[ffi_export]
+pub fn bpxsd_from_json_with_options(json: char_p::Ref<'_>, options: &ReaderOptions, mut buffer: c_slice::Mut<'_, u8>) -> isize {
+    match unwrap_result(json::decode(json.to_str(), options)) {
+        Some(data) => {
+            if let Some(out) = buffer.get_mut(..data.len()) {
+                out.copy_from_slice(&data);
+            }
+            data.len() as _
+        },
+        None => -1
+    }
+}
+
+#[ffi_export]
+pub fn bpxsd_write_json_to_section(container: &Container, handle: u32, json: char_p::Ref<'_>) -> bool {
+    bpxsd_write_json_to_section_with_options(container, handle, json, &ReaderOptions::default())
+}
+
+#[ffi_export]
+pub fn bpxsd_write_json_to_section_with_options(container: &Container, handle: u32, json: char_p::Ref<'_>, options: &ReaderOptions) -> bool {
+    let data = match unwrap_result(json::decode(json.to_str(), options)) {
+        Some(v) => v,
+        None => return false
+    };
+    try_with_section_mut(container, handle, |v| {
+        let size = v.size();
+        v.truncate(size)?;
+        v.seek(SeekFrom::Start(0))?;
+        v.write_all(&data)?;
+        v.seek(SeekFrom::Start(0))
+    }).is_some()
+}
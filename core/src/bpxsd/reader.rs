@@ -0,0 +1,300 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! A BPXSD reader which builds a Node tree directly from the encoded data.
+//!
+//! Unlike `bpx::sd::Value::read` this keeps the order of object entries and records, for each
+//! node, where it came from. The details node attached to every node has the following children:
+//!
+//! * `hash`: the key hash (object entries only).
+//! * `name_origin`: `debug` when the name comes from the `__debug__` table of the object,
+//!   `dictionary` when it was resolved from a key-name dictionary and `hash` when no name is known.
+//! * `type`: the encoded BPXSD type name.
+//! * `count`: the number of encoded entries (arrays and objects only).
+//! * `debug`: true when the object has a `__debug__` table (objects only). The table itself is
+//!   only used to resolve names and never shows up as a child node.
+//! * `offset`: the byte offset of the encoded value in the source data.
+//! * `size`: the size in bytes of the encoded value.
+
+use std::ffi::CString;
+use bp3d_util::simple_error;
+use bpx::util::hash::hash;
+use safer_ffi::prelude::*;
+use crate::bpxsd::dictionary::Dictionary;
+use crate::bpxsd::json::DEBUG_KEY;
+use crate::tree::model::{Node, Value};
+
+simple_error! {
+    pub Error {
+        Truncated => "BPXSD data is truncated",
+        BadTypeCode(u8) => "unknown BPXSD type code ({})",
+        Utf8 => "BPXSD string is not valid UTF-8",
+        MaxDepthExceeded => "maximum depth for nested values exceeded",
+        MaxNodesExceeded => "maximum number of nodes exceeded",
+        StringTooLong(u32) => "string exceeds maximum length ({} bytes)"
+    }
+}
+
+pub const TYPE_NAMES: [&str; 15] = [
+    "null", "bool", "uint8", "uint16", "uint32", "uint64", "int8", "int16", "int32", "int64",
+    "float", "double", "string", "array", "object"
+];
+
+#[derive_ReprC]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct ReaderOptions {
+    pub max_depth: u32,
+    pub max_nodes: u32,
+    pub max_string_length: u32
+}
+
+impl Default for ReaderOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 32,
+            max_nodes: 65536,
+            max_string_length: 65536
+        }
+    }
+}
+
+pub const CODE_ARRAY: u8 = 0xD;
+pub const CODE_OBJECT: u8 = 0xE;
+
+fn new_node(name: &str, value: Value) -> Node {
+    Node {
+        name: CString::new(name.replace('\0', "")).unwrap().into(),
+        value,
+        details: None,
+        children: Vec::new()
+    }
+}
+
+struct Details {
+    code: u8,
+    count: Option<u8>,
+    debug: bool,
+    offset: usize,
+    size: usize
+}
+
+impl Details {
+    fn into_node(self) -> Node {
+        let mut node = new_node("details", Value::Null);
+        node.children.push(new_node("type", Value::String(CString::new(TYPE_NAMES[self.code as usize]).unwrap().into())));
+        if let Some(count) = self.count {
+            node.children.push(new_node("count", Value::UInt8(count)));
+        }
+        if self.debug {
+            set_debug_details(&mut node);
+        }
+        node.children.push(new_node("offset", Value::UInt64(self.offset as _)));
+        node.children.push(new_node("size", Value::UInt64(self.size as _)));
+        node
+    }
+}
+
+/// Returns a details node recording only the encoded type, for nodes which were not read from
+/// BPXSD data.
+pub fn type_details(code: u8) -> Node {
+    let mut node = new_node("details", Value::Null);
+    node.children.push(new_node("type", Value::String(CString::new(TYPE_NAMES[code as usize]).unwrap().into())));
+    node
+}
+
+/// Records in the details of an object node that it has a `__debug__` table.
+pub fn set_debug_details(details: &mut Node) {
+    details.children.push(new_node("debug", Value::Boolean(true)));
+}
+
+/// Records the key hash of an object entry and where its name came from in its details.
+pub fn set_key_details(details: &mut Node, hash: u64, origin: &str) {
+    details.children.insert(0, new_node("name_origin", Value::String(CString::new(origin).unwrap().into())));
+    details.children.insert(0, new_node("hash", Value::UInt64(hash)));
+}
+
+pub struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    depth: u32,
+    nodes: u32,
+    max_string_length: u32,
+    dictionary: Option<&'a Dictionary>,
+    keep_debug: bool
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(data: &'a [u8], options: &ReaderOptions, dictionary: Option<&'a Dictionary>) -> Self {
+        Self {
+            data,
+            pos: 0,
+            depth: options.max_depth,
+            nodes: options.max_nodes,
+            max_string_length: options.max_string_length,
+            dictionary,
+            keep_debug: false
+        }
+    }
+
+    /// Keeps the `__debug__` table of objects as a child node named `__debug__`, at the place it
+    /// has in the encoded object.
+    pub fn keep_debug(mut self) -> Self {
+        self.keep_debug = true;
+        self
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let bytes = self.data.get(self.pos..self.pos + len).ok_or(Error::Truncated)?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn read_string(&mut self) -> Result<char_p::Box, Error> {
+        let data = &self.data[self.pos..];
+        let limit = data.len().min(self.max_string_length as usize + 1);
+        let len = match data[..limit].iter().position(|v| *v == 0) {
+            Some(len) => len,
+            None if limit < data.len() => return Err(Error::StringTooLong(self.max_string_length)),
+            None => return Err(Error::Truncated)
+        };
+        let s = std::str::from_utf8(self.take(len)?).map_err(|_| Error::Utf8)?;
+        self.pos += 1;
+        Ok(CString::new(s).unwrap().into())
+    }
+
+    fn read_value(&mut self, code: u8) -> Result<Node, Error> {
+        if self.nodes == 0 {
+            return Err(Error::MaxNodesExceeded);
+        }
+        self.nodes -= 1;
+        let offset = self.pos;
+        let mut node = new_node("", Value::Null);
+        let mut count = None;
+        let mut debug = false;
+        node.value = match code {
+            0x0 => Value::Null,
+            0x1 => Value::Boolean(self.take(1)?[0] == 1),
+            0x2 => Value::UInt8(self.take(1)?[0]),
+            0x3 => Value::UInt16(u16::from_le_bytes(self.take_array()?)),
+            0x4 => Value::UInt32(u32::from_le_bytes(self.take_array()?)),
+            0x5 => Value::UInt64(u64::from_le_bytes(self.take_array()?)),
+            0x6 => Value::Int8(self.take(1)?[0] as i8),
+            0x7 => Value::Int16(i16::from_le_bytes(self.take_array()?)),
+            0x8 => Value::Int32(i32::from_le_bytes(self.take_array()?)),
+            0x9 => Value::Int64(i64::from_le_bytes(self.take_array()?)),
+            0xA => Value::Float(f32::from_le_bytes(self.take_array()?)),
+            0xB => Value::Double(f64::from_le_bytes(self.take_array()?)),
+            0xC => Value::String(self.read_string()?),
+            CODE_ARRAY => {
+                count = Some(self.read_array(&mut node)?);
+                Value::Null
+            },
+            CODE_OBJECT => {
+                let (n, d) = self.read_object(&mut node)?;
+                (count, debug) = (Some(n), d);
+                Value::Null
+            },
+            _ => return Err(Error::BadTypeCode(code))
+        };
+        let size = self.pos - offset;
+        node.details = Some(Box::new(Details { code, count, debug, offset, size }.into_node()).into());
+        Ok(node)
+    }
+
+    fn enter(&mut self) -> Result<u8, Error> {
+        if self.depth == 0 {
+            return Err(Error::MaxDepthExceeded);
+        }
+        self.depth -= 1;
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_array(&mut self, node: &mut Node) -> Result<u8, Error> {
+        let count = self.enter()?;
+        for _ in 0..count {
+            let code = self.take(1)?[0];
+            node.children.push(self.read_value(code)?);
+        }
+        self.depth += 1;
+        Ok(count)
+    }
+
+    fn read_object(&mut self, node: &mut Node) -> Result<(u8, bool), Error> {
+        let count = self.enter()?;
+        let mut entries = Vec::with_capacity(count as _);
+        for _ in 0..count {
+            let hash = u64::from_le_bytes(self.take_array()?);
+            let code = self.take(1)?[0];
+            entries.push((hash, self.read_value(code)?));
+        }
+        self.depth += 1;
+        let debug = hash(DEBUG_KEY);
+        let symbols: Option<Vec<String>> = entries.iter()
+            .find(|(hash, _)| *hash == debug)
+            .map(|(_, v)| v.children.iter().map(|v| v.value.as_str().into()).collect());
+        for (key, mut child) in entries {
+            if key == debug && !self.keep_debug {
+                continue;
+            }
+            let (name, origin) = match symbols.iter().flatten().find(|v| hash(v) == key) {
+                Some(name) => (name.clone(), "debug"),
+                None if key == debug => (DEBUG_KEY.into(), "debug"),
+                None => match self.dictionary.and_then(|v| v.get(key)) {
+                    Some(name) => (name.into(), "dictionary"),
+                    None => (format!("{:X}", key), "hash")
+                }
+            };
+            child.name = CString::new(name.replace('\0', "")).unwrap().into();
+            if let Some(details) = &mut child.details {
+                set_key_details(details, key, origin);
+            }
+            node.children.push(child);
+        }
+        Ok((count, symbols.is_some()))
+    }
+
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub fn set_position(&mut self, pos: usize) {
+        self.pos = pos.min(self.data.len());
+    }
+
+    /// Reads the BPXSD object at the current position and returns it as a node named `root`.
+    pub fn read_root(&mut self) -> Result<Node, Error> {
+        let mut node = self.read_value(CODE_OBJECT)?;
+        node.name = CString::from(c"root").into();
+        Ok(node)
+    }
+}
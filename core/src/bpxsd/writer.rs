@@ -0,0 +1,206 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Encodes Node trees to BPXSD.
+//!
+//! The encoded type of a node is taken from its value. Nodes with a null value use the `type`
+//! entry of their details when present, as produced by the reader, and otherwise become an array
+//! when all their children are unnamed, an object when they have named children and null when
+//! they have no children.
+//!
+//! Object keys are hashed from node names, except for entries whose name could not be resolved
+//! by the reader which keep their original hash. When an object has a `__debug__` entry, or its
+//! details record one as produced by the reader, the table is rebuilt from the names of the other
+//! entries so that added and renamed keys stay readable.
+
+use bp3d_util::simple_error;
+use bpx::util::hash::hash;
+use crate::bpxsd::json::DEBUG_KEY;
+use crate::bpxsd::reader::{CODE_ARRAY, CODE_OBJECT};
+use crate::tree::model::{Node, Value};
+
+simple_error! {
+    pub Error {
+        CapacityExceeded(usize) => "capacity exceeded ({} > 255)",
+        NotAnObject => "root node is not an object"
+    }
+}
+
+fn detail<'a>(node: &'a Node, name: &str) -> Option<&'a Value> {
+    node.details.as_ref()
+        .and_then(|v| v.children.iter().find(|v| v.name.to_str() == name))
+        .map(|v| &v.value)
+}
+
+fn scalar_code(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0x0,
+        Value::Boolean(_) => 0x1,
+        Value::UInt8(_) => 0x2,
+        Value::UInt16(_) => 0x3,
+        Value::UInt32(_) => 0x4,
+        Value::UInt64(_) => 0x5,
+        Value::Int8(_) => 0x6,
+        Value::Int16(_) => 0x7,
+        Value::Int32(_) => 0x8,
+        Value::Int64(_) => 0x9,
+        Value::Float(_) => 0xA,
+        Value::Double(_) => 0xB,
+        Value::String(_) => 0xC
+    }
+}
+
+/// Returns the BPXSD type code `node` is encoded with.
+pub fn type_code(node: &Node) -> u8 {
+    match &node.value {
+        Value::Null => match detail(node, "type").map(|v| v.as_str()) {
+            Some("array") => CODE_ARRAY,
+            Some("object") => CODE_OBJECT,
+            _ if node.children.is_empty() => 0x0,
+            _ if node.children.iter().all(|v| v.name.to_str().is_empty()) => CODE_ARRAY,
+            _ => CODE_OBJECT
+        },
+        v => scalar_code(v)
+    }
+}
+
+/// Returns the hash of the key `node` is stored under in its parent object, or None when the
+/// reader could not resolve its name.
+pub fn unresolved_hash(node: &Node) -> Option<u64> {
+    match detail(node, "name_origin").map(|v| v.as_str()) {
+        Some("hash") => detail(node, "hash").map(|v| v.as_u64()),
+        _ => None
+    }
+}
+
+fn is_debug(node: &Node) -> bool {
+    node.name.to_str() == DEBUG_KEY && unresolved_hash(node).is_none()
+}
+
+/// Appends the encoding of a scalar value to `out` and returns its type code.
+pub fn write_scalar(out: &mut Vec<u8>, value: &Value) -> u8 {
+    match value {
+        Value::Null => (),
+        Value::Boolean(v) => out.push(*v as u8),
+        Value::UInt8(v) => out.push(*v),
+        Value::UInt16(v) => out.extend_from_slice(&v.to_le_bytes()),
+        Value::UInt32(v) => out.extend_from_slice(&v.to_le_bytes()),
+        Value::UInt64(v) => out.extend_from_slice(&v.to_le_bytes()),
+        Value::Int8(v) => out.push(*v as u8),
+        Value::Int16(v) => out.extend_from_slice(&v.to_le_bytes()),
+        Value::Int32(v) => out.extend_from_slice(&v.to_le_bytes()),
+        Value::Int64(v) => out.extend_from_slice(&v.to_le_bytes()),
+        Value::Float(v) => out.extend_from_slice(&v.to_le_bytes()),
+        Value::Double(v) => out.extend_from_slice(&v.to_le_bytes()),
+        Value::String(v) => {
+            out.extend_from_slice(v.to_str().as_bytes());
+            out.push(0);
+        }
+    }
+    scalar_code(value)
+}
+
+fn write_count(out: &mut Vec<u8>, count: usize) -> Result<(), Error> {
+    let count = u8::try_from(count).map_err(|_| Error::CapacityExceeded(count))?;
+    out.push(count);
+    Ok(())
+}
+
+fn write_debug(out: &mut Vec<u8>, parent: &Node) -> Result<u8, Error> {
+    let names: Vec<&str> = parent.children.iter()
+        .filter(|v| !is_debug(v) && unresolved_hash(v).is_none())
+        .map(|v| v.name.to_str())
+        .collect();
+    write_count(out, names.len())?;
+    for name in names {
+        out.push(0xC);
+        out.extend_from_slice(name.as_bytes());
+        out.push(0);
+    }
+    Ok(CODE_ARRAY)
+}
+
+fn write_value(out: &mut Vec<u8>, node: &Node) -> Result<(), Error> {
+    let pos = out.len();
+    out.push(0);
+    out[pos] = write_node(out, node)?;
+    Ok(())
+}
+
+fn write_debug_entry(out: &mut Vec<u8>, parent: &Node) -> Result<(), Error> {
+    out.push(0);
+    let pos = out.len() - 1;
+    out[pos] = write_debug(out, parent)?;
+    Ok(())
+}
+
+fn write_object(out: &mut Vec<u8>, node: &Node) -> Result<(), Error> {
+    let debug = detail(node, "debug").is_some_and(|v| v.as_bool()) && !node.children.iter().any(is_debug);
+    write_count(out, node.children.len() + debug as usize)?;
+    for child in &node.children {
+        let key = unresolved_hash(child).unwrap_or_else(|| hash(child.name.to_str()));
+        out.extend_from_slice(&key.to_le_bytes());
+        match is_debug(child) {
+            true => write_debug_entry(out, node)?,
+            false => write_value(out, child)?
+        }
+    }
+    if debug {
+        out.extend_from_slice(&hash(DEBUG_KEY).to_le_bytes());
+        write_debug_entry(out, node)?;
+    }
+    Ok(())
+}
+
+fn write_node(out: &mut Vec<u8>, node: &Node) -> Result<u8, Error> {
+    let code = type_code(node);
+    match code {
+        CODE_ARRAY => {
+            write_count(out, node.children.len())?;
+            for child in &node.children {
+                write_value(out, child)?;
+            }
+        },
+        CODE_OBJECT => write_object(out, node)?,
+        _ => {
+            write_scalar(out, &node.value);
+        }
+    }
+    Ok(code)
+}
+
+/// Encodes a node tree to a BPXSD object. A null node without children encodes as an empty
+/// object.
+pub fn write(root: &Node) -> Result<Vec<u8>, Error> {
+    if !matches!(type_code(root), CODE_OBJECT | 0x0) {
+        return Err(Error::NotAnObject);
+    }
+    let mut out = Vec::new();
+    write_object(&mut out, root)?;
+    Ok(out)
+}
@@ -26,7 +26,9 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use bpx::core::{AutoSectionData, Handle};
+use std::cell::Cell;
+use std::io::{Read, Seek, SeekFrom};
+use bpx::core::{AutoSectionData, Handle, SectionData};
 use crate::stream::Stream;
 use safer_ffi::prelude::*;
 use crate::error::{unwrap_result, IntoBPXError, RustError};
@@ -118,7 +120,8 @@ impl From<bpx::core::header::MainHeader> for MainHeader {
 pub struct Container {
     pub underlying: bpx::core::Container<Stream>,
     pub sections: Vec<SectionInfo>,
-    pub main_header: MainHeader
+    pub main_header: MainHeader,
+    revision: Cell<u64>
 }
 
 impl Container {
@@ -130,6 +133,16 @@ impl Container {
         let main_header = MainHeader::from(self.underlying.main_header());
         self.sections = sections;
         self.main_header = main_header;
+        self.touch();
+    }
+
+    /// Records that section data or the section list may have changed.
+    pub fn touch(&self) {
+        self.revision.set(self.revision.get() + 1);
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision.get()
     }
 }
 
@@ -143,7 +156,8 @@ impl From<bpx::core::Container<Stream>> for Container {
         Self {
             sections,
             main_header,
-            underlying: value
+            underlying: value,
+            revision: Cell::new(0)
         }
     }
 }
@@ -154,8 +168,32 @@ pub fn try_with_section<E: IntoBPXError + Into<RustError>, T, F: FnOnce(&mut Aut
     unwrap_result(closure(&mut v))
 }
 
+/// Same as [try_with_section] but for closures which modify the section: the container revision
+/// is bumped once the closure has run, even when it failed part way through.
+pub fn try_with_section_mut<E: IntoBPXError + Into<RustError>, T, F: FnOnce(&mut AutoSectionData) -> Result<T, E>>(container: &Container, handle: u32, closure: F) -> Option<T> {
+    let handle = unsafe { Handle::from_raw(handle) };
+    let mut v = unwrap_result(container.underlying.sections().load(handle))?;
+    let res = closure(&mut v);
+    container.touch();
+    unwrap_result(res)
+}
+
 pub fn with_section<T, F: FnOnce(&mut AutoSectionData) -> T>(container: &Container, handle: u32, closure: F) -> Option<T> {
     let handle = unsafe { Handle::from_raw(handle) };
     let mut v = unwrap_result(container.underlying.sections().load(handle))?;
     Some(closure(&mut v))
 }
+
+pub fn read_all(data: &mut AutoSectionData) -> std::io::Result<Vec<u8>> {
+    let mut buffer = Vec::with_capacity(data.size());
+    data.seek(SeekFrom::Start(0))?;
+    data.read_to_end(&mut buffer)?;
+    data.seek(SeekFrom::Start(0))?;
+    Ok(buffer)
+}
+
+pub fn find_sections(container: &Container, ty: u8) -> Vec<Handle> {
+    container.underlying.sections().iter()
+        .filter(|v| container.underlying.sections()[*v].header().ty == ty)
+        .collect()
+}
@@ -0,0 +1,442 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::ffi::CString;
+use std::fmt::Write;
+use bp3d_util::simple_error;
+use bpx::core::Handle;
+use bpx::core::header::{SECTION_TYPE_SD, SECTION_TYPE_TABLE};
+use safer_ffi::prelude::*;
+use crate::bpxsd::reader::{Reader, ReaderOptions};
+use crate::common::{read_all, Container, MainHeader};
+use crate::error::unwrap_result;
+use crate::strings::{resolve_strings, StringPool};
+use crate::table::layout::{open_raw, Layout};
+use crate::table::row::{element_count, read_elements};
+use crate::tree::diff::ChangeKind;
+use crate::tree::model::{Node, Value};
+
+simple_error! {
+    pub Error {
+        (impl From) Bpx(bpx::core::error::Error) => "BPX error: {}",
+        (impl From) Io(std::io::Error) => "io error: {}"
+    }
+}
+
+#[derive_ReprC]
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum DiffLevel {
+    Header = 0,
+    Section,
+    Bytes,
+    Node,
+    Row
+}
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct DiffOptions {
+    pub a_strings: u32,
+    pub b_strings: u32,
+    pub reader: ReaderOptions
+}
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct Change {
+    pub level: DiffLevel,
+    pub kind: ChangeKind,
+    pub section_a: u32,
+    pub section_b: u32,
+    pub offset: u64,
+    pub length: u64,
+    pub path: char_p::Box,
+    pub from: Option<char_p::Box>,
+    pub old_value: Option<repr_c::Box<Value>>,
+    pub new_value: Option<repr_c::Box<Value>>
+}
+
+impl Change {
+    fn new(level: DiffLevel, kind: ChangeKind, path: &str) -> Self {
+        Self {
+            level,
+            kind,
+            section_a: 0,
+            section_b: 0,
+            offset: 0,
+            length: 0,
+            path: CString::new(path.replace('\0', "")).unwrap().into(),
+            from: None,
+            old_value: None,
+            new_value: None
+        }
+    }
+
+    fn sections(mut self, a: Option<Handle>, b: Option<Handle>) -> Self {
+        self.section_a = a.map(|v| v.into_raw()).unwrap_or(0);
+        self.section_b = b.map(|v| v.into_raw()).unwrap_or(0);
+        self
+    }
+
+    fn range(mut self, offset: usize, length: usize) -> Self {
+        self.offset = offset as _;
+        self.length = length as _;
+        self
+    }
+
+    fn from(mut self, from: Option<String>) -> Self {
+        self.from = from.map(|v| CString::new(v.replace('\0', "")).unwrap().into());
+        self
+    }
+
+    fn values(mut self, old: Option<Value>, new: Option<Value>) -> Self {
+        self.old_value = old.map(|v| Box::new(v).into());
+        self.new_value = new.map(|v| Box::new(v).into());
+        self
+    }
+}
+
+#[derive_ReprC]
+#[repr(opaque)]
+pub struct Diff {
+    changes: Vec<Change>,
+    text: char_p::Box
+}
+
+struct Side<'a> {
+    container: &'a Container,
+    strings: Option<StringPool>
+}
+
+impl<'a> Side<'a> {
+    fn new(container: &'a Container, strings: u32) -> Self {
+        let strings = resolve_strings(container, strings)
+            .and_then(|v| StringPool::load(container, v).ok());
+        Self { container, strings }
+    }
+
+    fn load(&self, handle: Handle) -> Result<Vec<u8>, Error> {
+        Ok(read_all(&mut *self.container.underlying.sections().load(handle)?)?)
+    }
+
+    fn column_name(&self, layout: &Layout, index: usize) -> String {
+        self.strings.as_ref()
+            .and_then(|v| v.get(layout.columns[index].name).ok())
+            .map(String::from)
+            .unwrap_or_else(|| format!("#{}", index))
+    }
+
+    fn section_type(&self, handle: Handle) -> u8 {
+        self.container.underlying.sections()[handle].header().ty
+    }
+}
+
+fn diff_header(a: &MainHeader, b: &MainHeader, out: &mut Vec<Change>) {
+    let mut field = |name: &str, old: Value, new: Value, changed: bool| {
+        if changed {
+            out.push(Change::new(DiffLevel::Header, ChangeKind::Changed, name).values(Some(old), Some(new)));
+        }
+    };
+    let hex = |v: &[u8]| -> Value {
+        let s: String = v.iter().map(|v| format!("{:02X}", v)).collect();
+        Value::String(CString::new(s).unwrap().into())
+    };
+    field("signature", hex(&a.signature), hex(&b.signature), a.signature != b.signature);
+    field("type", Value::UInt8(a.ty), Value::UInt8(b.ty), a.ty != b.ty);
+    field("chksum", Value::UInt32(a.chksum), Value::UInt32(b.chksum), a.chksum != b.chksum);
+    field("file_size", Value::UInt64(a.file_size), Value::UInt64(b.file_size), a.file_size != b.file_size);
+    field("section_num", Value::UInt32(a.section_num), Value::UInt32(b.section_num), a.section_num != b.section_num);
+    field("version", Value::UInt32(a.version), Value::UInt32(b.version), a.version != b.version);
+    field("type_ext", hex(&a.type_ext), hex(&b.type_ext), a.type_ext != b.type_ext);
+}
+
+fn diff_bytes(a: &[u8], b: &[u8]) -> Vec<(ChangeKind, usize, usize)> {
+    let mut ranges = Vec::new();
+    let len = a.len().min(b.len());
+    let mut start = None;
+    for i in 0..len {
+        match (a[i] != b[i], start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                ranges.push((ChangeKind::Changed, s, i - s));
+                start = None;
+            },
+            _ => ()
+        }
+    }
+    if let Some(s) = start {
+        ranges.push((ChangeKind::Changed, s, len - s));
+    }
+    if b.len() > len {
+        ranges.push((ChangeKind::Added, len, b.len() - len));
+    }
+    if a.len() > len {
+        ranges.push((ChangeKind::Removed, len, a.len() - len));
+    }
+    ranges
+}
+
+
+fn diff_table(a: &Side, b: &Side, (ha, hb): (Handle, Handle), (da, db): (&[u8], &[u8]), out: &mut Vec<Change>) -> bool {
+    let (la, lb) = match (Layout::read(da), Layout::read(db)) {
+        (Ok(la), Ok(lb)) => (la, lb),
+        _ => return false
+    };
+    let names_a: Vec<String> = (0..la.columns.len()).map(|v| a.column_name(&la, v)).collect();
+    let names_b: Vec<String> = (0..lb.columns.len()).map(|v| b.column_name(&lb, v)).collect();
+    if !la.is_compatible(&lb) || names_a != names_b {
+        out.push(Change::new(DiffLevel::Row, ChangeKind::Changed, "columns")
+            .sections(Some(ha), Some(hb))
+            .values(Some(Value::UInt32(la.columns.len() as _)), Some(Value::UInt32(lb.columns.len() as _))));
+        return true;
+    }
+    let (free, row_size) = match open_raw(a.container, ha) {
+        Ok(v) => (v.get_row_size(), v.get_actual_row_size()),
+        Err(_) => return false
+    };
+    let rows_a: Vec<&[u8]> = da[la.size()..].chunks_exact(row_size).collect();
+    let rows_b: Vec<&[u8]> = db[lb.size()..].chunks_exact(row_size).collect();
+    for (index, (ra, rb)) in rows_a.iter().zip(rows_b.iter()).enumerate() {
+        if ra == rb {
+            continue;
+        }
+        let mut offset = 0;
+        for (column, name) in la.columns.iter().zip(names_a.iter()) {
+            let size = column.get_size();
+            let (ca, cb) = (&ra[offset..offset + size], &rb[offset..offset + size]);
+            if ca != cb {
+                // Array cells report every element which changed.
+                let count = element_count(column);
+                let step = size / count.max(1);
+                let (ea, eb) = (read_elements(column.ty, count, ca).ok(), read_elements(column.ty, count, cb).ok());
+                for element in (0..count).filter(|i| ca[i * step..(i + 1) * step] != cb[i * step..(i + 1) * step]) {
+                    out.push(Change::new(DiffLevel::Row, ChangeKind::Changed, name)
+                        .sections(Some(ha), Some(hb))
+                        .range(index, 1)
+                        .values(ea.as_ref().map(|v| v[element].clone()), eb.as_ref().map(|v| v[element].clone())));
+                }
+            }
+            offset += size;
+        }
+        if ra[free] != rb[free] {
+            out.push(Change::new(DiffLevel::Row, ChangeKind::Changed, "free")
+                .sections(Some(ha), Some(hb))
+                .range(index, 1)
+                .values(Some(Value::Boolean(ra[free] == 1)), Some(Value::Boolean(rb[free] == 1))));
+        }
+    }
+    for index in rows_b.len()..rows_a.len() {
+        out.push(Change::new(DiffLevel::Row, ChangeKind::Removed, "").sections(Some(ha), Some(hb)).range(index, 1));
+    }
+    for index in rows_a.len()..rows_b.len() {
+        out.push(Change::new(DiffLevel::Row, ChangeKind::Added, "").sections(Some(ha), Some(hb)).range(index, 1));
+    }
+    true
+}
+
+fn diff_section(a: &Side, b: &Side, (ha, hb): (Handle, Handle), options: &ReaderOptions, out: &mut Vec<Change>) -> Result<(), Error> {
+    let (da, db) = (a.load(ha)?, b.load(hb)?);
+    let ty = a.section_type(ha);
+    if da == db {
+        return Ok(());
+    }
+    out.push(Change::new(DiffLevel::Section, ChangeKind::Changed, "")
+        .sections(Some(ha), Some(hb))
+        .values(Some(Value::UInt32(da.len() as _)), Some(Value::UInt32(db.len() as _))));
+    if ty == SECTION_TYPE_SD {
+        // A section which does not decode on either side is compared byte by byte instead.
+        let na = Reader::new(&da, options, None).read_root();
+        let nb = Reader::new(&db, options, None).read_root();
+        if let (Ok(na), Ok(nb)) = (na, nb) {
+            out.extend(node_changes(&na, &nb).into_iter().map(|v| v.sections(Some(ha), Some(hb))));
+            return Ok(());
+        }
+    }
+    if ty == SECTION_TYPE_TABLE && diff_table(a, b, (ha, hb), (&da, &db), out) {
+        return Ok(());
+    }
+    for (kind, offset, length) in diff_bytes(&da, &db) {
+        out.push(Change::new(DiffLevel::Bytes, kind, "").sections(Some(ha), Some(hb)).range(offset, length));
+    }
+    Ok(())
+}
+
+fn node_changes(a: &Node, b: &Node) -> Vec<Change> {
+    crate::tree::diff::diff(a, b).into_iter()
+        .map(|v| Change::new(DiffLevel::Node, v.kind, &v.path).from(v.from).values(v.old, v.new))
+        .collect()
+}
+
+pub fn diff(a: &Container, b: &Container, options: &DiffOptions) -> Result<Vec<Change>, Error> {
+    let mut out = Vec::new();
+    diff_header(&a.main_header, &b.main_header, &mut out);
+    let a = Side::new(a, options.a_strings);
+    let b = Side::new(b, options.b_strings);
+    // Sections are paired by type, in order of appearance: the n-th section of a given type in a
+    // is compared against the n-th section of the same type in b.
+    let mut types: Vec<u8> = a.container.sections.iter().chain(b.container.sections.iter())
+        .map(|v| v.header.ty)
+        .collect();
+    types.sort();
+    types.dedup();
+    for ty in types {
+        let list_a: Vec<Handle> = crate::common::find_sections(a.container, ty);
+        let list_b: Vec<Handle> = crate::common::find_sections(b.container, ty);
+        for (ha, hb) in list_a.iter().zip(list_b.iter()) {
+            diff_section(&a, &b, (*ha, *hb), &options.reader, &mut out)?;
+        }
+        for ha in list_a.iter().skip(list_b.len()) {
+            let size = a.load(*ha)?.len();
+            out.push(Change::new(DiffLevel::Section, ChangeKind::Removed, "").sections(Some(*ha), None).range(0, size));
+        }
+        for hb in list_b.iter().skip(list_a.len()) {
+            let size = b.load(*hb)?.len();
+            out.push(Change::new(DiffLevel::Section, ChangeKind::Added, "").sections(None, Some(*hb)).range(0, size));
+        }
+    }
+    Ok(out)
+}
+
+fn describe_section(container: &Container, handle: u32) -> String {
+    match container.sections.iter().find(|v| v.handle == handle) {
+        Some(v) => format!("section #{} (type 0x{:02X})", v.index, v.header.ty),
+        None => String::from("section ?")
+    }
+}
+
+fn format_value(value: &Option<repr_c::Box<Value>>) -> String {
+    match value.as_deref() {
+        Some(Value::String(v)) => format!("{:?}", v.to_str()),
+        Some(v) => v.to_string(),
+        None => String::from("?")
+    }
+}
+
+pub fn to_text(a: &Container, b: &Container, changes: &[Change]) -> String {
+    let mut text = String::new();
+    for change in changes {
+        let sign = match change.kind {
+            ChangeKind::Added => '+',
+            ChangeKind::Removed => '-',
+            ChangeKind::Changed => '~',
+            ChangeKind::Moved => '>'
+        };
+        let section = match change.kind {
+            ChangeKind::Added => describe_section(b, change.section_b),
+            _ => describe_section(a, change.section_a)
+        };
+        let path = change.path.to_str();
+        let _ = match change.level {
+            DiffLevel::Header => writeln!(text, "{} header.{}: {} -> {}", sign, path,
+                                          format_value(&change.old_value), format_value(&change.new_value)),
+            DiffLevel::Section => match change.kind {
+                ChangeKind::Changed => writeln!(text, "{} {}: {} -> {} bytes", sign, section,
+                                                format_value(&change.old_value), format_value(&change.new_value)),
+                _ => writeln!(text, "{} {}: {} bytes", sign, section, change.length)
+            },
+            DiffLevel::Bytes => writeln!(text, "{} {} bytes [0x{:X}, 0x{:X})", sign, section,
+                                         change.offset, change.offset + change.length),
+            DiffLevel::Node => match change.kind {
+                ChangeKind::Added => writeln!(text, "{} {} {} = {}", sign, section, path, format_value(&change.new_value)),
+                ChangeKind::Removed => writeln!(text, "{} {} {} = {}", sign, section, path, format_value(&change.old_value)),
+                ChangeKind::Changed => writeln!(text, "{} {} {}: {} -> {}", sign, section, path,
+                                                format_value(&change.old_value), format_value(&change.new_value)),
+                ChangeKind::Moved => writeln!(text, "{} {} {} -> {}", sign, section,
+                                              change.from.as_ref().map(|v| v.to_str()).unwrap_or("?"), path)
+            },
+            DiffLevel::Row => match change.kind {
+                ChangeKind::Changed if change.length == 0 => writeln!(text, "{} {} columns: {} -> {}", sign, section,
+                                                format_value(&change.old_value), format_value(&change.new_value)),
+                ChangeKind::Changed => writeln!(text, "{} {} row {} {}: {} -> {}", sign, section, change.offset, path,
+                                                format_value(&change.old_value), format_value(&change.new_value)),
+                _ => writeln!(text, "{} {} row {}", sign, section, change.offset)
+            }
+        };
+    }
+    text
+}
+
+fn node_text(changes: &[Change]) -> String {
+    let mut text = String::new();
+    for change in changes {
+        let path = change.path.to_str();
+        let _ = match change.kind {
+            ChangeKind::Added => writeln!(text, "+ {} = {}", path, format_value(&change.new_value)),
+            ChangeKind::Removed => writeln!(text, "- {} = {}", path, format_value(&change.old_value)),
+            ChangeKind::Changed => writeln!(text, "~ {}: {} -> {}", path,
+                                            format_value(&change.old_value), format_value(&change.new_value)),
+            ChangeKind::Moved => writeln!(text, "> {} -> {}", change.from.as_ref().map(|v| v.to_str()).unwrap_or("?"), path)
+        };
+    }
+    text
+}
+
+#[ffi_export]
+pub unsafe fn bpx_diff_options_default(options: *mut DiffOptions) {
+    *options = DiffOptions {
+        a_strings: 0,
+        b_strings: 0,
+        reader: ReaderOptions::default()
+    }
+}
+
+#[ffi_export]
+pub fn bpx_container_diff(a: &Container, b: &Container, options: &DiffOptions) -> Option<repr_c::Box<Diff>> {
+    let changes = unwrap_result(diff(a, b, options))?;
+    let text = to_text(a, b, &changes);
+    Some(Box::new(Diff {
+        changes,
+        text: CString::new(text).unwrap().into()
+    }).into())
+}
+
+#[ffi_export]
+pub fn bpx_node_diff(a: &Node, b: &Node) -> repr_c::Box<Diff> {
+    let changes = node_changes(a, b);
+    let text = node_text(&changes);
+    Box::new(Diff {
+        changes,
+        text: CString::new(text).unwrap().into()
+    }).into()
+}
+
+#[ffi_export]
+pub fn bpx_diff_get_changes(diff: &Diff) -> c_slice::Ref<'_, Change> {
+    diff.changes.as_slice().into()
+}
+
+#[ffi_export]
+pub fn bpx_diff_get_text(diff: &Diff) -> char_p::Ref<'_> {
+    diff.text.as_ref()
+}
+
+#[ffi_export]
+pub fn bpx_diff_free(diff: repr_c::Box<Diff>) {
+    drop(diff);
+}
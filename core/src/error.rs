@@ -33,7 +33,7 @@ use std::fmt::{Display, Formatter};
 use safer_ffi::prelude::*;
 use std::io::Cursor;
 use std::io::Write;
-use crate::tree;
+use crate::{bpxsd, diff, merge, search, table, tree, unpack};
 
 pub trait IntoBPXError where Self: Sized + Error {
     const CODE: i32;
@@ -75,6 +75,121 @@ impl IntoBPXError for bpx::strings::Error {
     const DOMAIN: &'static CStr = c"BPX String";
 }
 
+impl IntoBPXError for merge::Error {
+    const CODE: i32 = 8;
+    const DOMAIN: &'static CStr = c"Merge";
+}
+
+impl IntoBPXError for diff::Error {
+    const CODE: i32 = 9;
+    const DOMAIN: &'static CStr = c"Diff";
+}
+
+impl IntoBPXError for unpack::Error {
+    const CODE: i32 = 10;
+    const DOMAIN: &'static CStr = c"Unpack";
+}
+
+impl IntoBPXError for bpxsd::json::Error {
+    const CODE: i32 = 11;
+    const DOMAIN: &'static CStr = c"BPXSD JSON";
+}
+
+impl IntoBPXError for bpxsd::reader::Error {
+    const CODE: i32 = 12;
+    const DOMAIN: &'static CStr = c"BPXSD Reader";
+}
+
+impl IntoBPXError for tree::query::Error {
+    const CODE: i32 = 13;
+    const DOMAIN: &'static CStr = c"Query";
+}
+
+impl IntoBPXError for tree::schema::Error {
+    const CODE: i32 = 14;
+    const DOMAIN: &'static CStr = c"Schema";
+}
+
+impl IntoBPXError for tree::convert::Error {
+    const CODE: i32 = 15;
+    const DOMAIN: &'static CStr = c"Conversion";
+}
+
+impl IntoBPXError for tree::text::Error {
+    const CODE: i32 = 16;
+    const DOMAIN: &'static CStr = c"Value Parse";
+}
+
+impl IntoBPXError for tree::patch::Error {
+    const CODE: i32 = 17;
+    const DOMAIN: &'static CStr = c"Patch";
+}
+
+impl IntoBPXError for search::Error {
+    const CODE: i32 = 18;
+    const DOMAIN: &'static CStr = c"Search";
+}
+
+impl IntoBPXError for table::batch::Error {
+    const CODE: i32 = 19;
+    const DOMAIN: &'static CStr = c"Table Batch";
+}
+
+impl IntoBPXError for table::cursor::Error {
+    const CODE: i32 = 20;
+    const DOMAIN: &'static CStr = c"Table Cursor";
+}
+
+impl IntoBPXError for table::query::Error {
+    const CODE: i32 = 21;
+    const DOMAIN: &'static CStr = c"Table Query";
+}
+
+impl IntoBPXError for table::sql::ParseError {
+    const CODE: i32 = 22;
+    const DOMAIN: &'static CStr = c"SQL Parse";
+}
+
+impl IntoBPXError for table::sql::Error {
+    const CODE: i32 = 23;
+    const DOMAIN: &'static CStr = c"SQL";
+}
+
+impl IntoBPXError for table::index::Error {
+    const CODE: i32 = 24;
+    const DOMAIN: &'static CStr = c"Table Index";
+}
+
+impl IntoBPXError for table::profile::Error {
+    const CODE: i32 = 25;
+    const DOMAIN: &'static CStr = c"Table Profile";
+}
+
+impl IntoBPXError for table::convert::Error {
+    const CODE: i32 = 26;
+    const DOMAIN: &'static CStr = c"Table Convert";
+}
+
+impl IntoBPXError for table::row::Error {
+    const CODE: i32 = 27;
+    const DOMAIN: &'static CStr = c"Table Row";
+}
+
+impl IntoBPXError for table::validate::Error {
+    const CODE: i32 = 28;
+    const DOMAIN: &'static CStr = c"Table Validate";
+}
+
+impl IntoBPXError for table::Error {
+    const CODE: i32 = 29;
+    const DOMAIN: &'static CStr = c"Table";
+}
+
+impl IntoBPXError for table::transaction::Error {
+    const CODE: i32 = 30;
+    const DOMAIN: &'static CStr = c"Table Transaction";
+}
+
 #[derive(Debug)]
 pub enum RustError {
     Bpx(bpx::core::error::Error),
@@ -83,7 +198,30 @@ pub enum RustError {
     Io(std::io::Error),
     Tree(tree::util::Error),
     Table(bpx::table::error::Error),
-    String(bpx::strings::Error)
+    String(bpx::strings::Error),
+    Merge(merge::Error),
+    Diff(diff::Error),
+    Unpack(unpack::Error),
+    Json(bpxsd::json::Error),
+    Reader(bpxsd::reader::Error),
+    Query(tree::query::Error),
+    Schema(tree::schema::Error),
+    Conversion(tree::convert::Error),
+    Parse(tree::text::Error),
+    Patch(tree::patch::Error),
+    Search(search::Error),
+    Batch(table::batch::Error),
+    Cursor(table::cursor::Error),
+    TableQuery(table::query::Error),
+    SqlParse(table::sql::ParseError),
+    Sql(table::sql::Error),
+    TableIndex(table::index::Error),
+    TableProfile(table::profile::Error),
+    TableConvert(table::convert::Error),
+    TableRow(table::row::Error),
+    TableValidate(table::validate::Error),
+    TableCore(table::Error),
+    TableTransaction(table::transaction::Error)
 }
 
 impl Display for RustError {
@@ -96,6 +234,29 @@ impl Display for RustError {
             RustError::Tree(e) => write!(f, "Tree error: {}", e),
             RustError::Table(e) => write!(f, "Table error: {}", e),
             RustError::String(e) => write!(f, "String error: {}", e),
+            RustError::Merge(e) => write!(f, "Merge error: {}", e),
+            RustError::Diff(e) => write!(f, "Diff error: {}", e),
+            RustError::Unpack(e) => write!(f, "Unpack error: {}", e),
+            RustError::Json(e) => write!(f, "BPXSD JSON error: {}", e),
+            RustError::Reader(e) => write!(f, "BPXSD reader error: {}", e),
+            RustError::Query(e) => write!(f, "Query error: {}", e),
+            RustError::Schema(e) => write!(f, "Schema error: {}", e),
+            RustError::Conversion(e) => write!(f, "Conversion error: {}", e),
+            RustError::Parse(e) => write!(f, "Value parse error: {}", e),
+            RustError::Patch(e) => write!(f, "Patch error: {}", e),
+            RustError::Search(e) => write!(f, "Search error: {}", e),
+            RustError::Batch(e) => write!(f, "Table batch error: {}", e),
+            RustError::Cursor(e) => write!(f, "Table cursor error: {}", e),
+            RustError::TableQuery(e) => write!(f, "Table query error: {}", e),
+            RustError::SqlParse(e) => write!(f, "SQL parse error: {}", e),
+            RustError::Sql(e) => write!(f, "SQL error: {}", e),
+            RustError::TableIndex(e) => write!(f, "Table index error: {}", e),
+            RustError::TableProfile(e) => write!(f, "Table profile error: {}", e),
+            RustError::TableConvert(e) => write!(f, "Table conversion error: {}", e),
+            RustError::TableRow(e) => write!(f, "Table row error: {}", e),
+            RustError::TableValidate(e) => write!(f, "Table validation error: {}", e),
+            RustError::TableCore(e) => write!(f, "Table error: {}", e),
+            RustError::TableTransaction(e) => write!(f, "Table transaction error: {}", e),
         }
     }
 }
@@ -108,7 +269,30 @@ bpx::impl_err_conversion! (
         std::io::Error => Io,
         tree::util::Error => Tree,
         bpx::table::error::Error => Table,
-        bpx::strings::Error => String
+        bpx::strings::Error => String,
+        merge::Error => Merge,
+        diff::Error => Diff,
+        unpack::Error => Unpack,
+        bpxsd::json::Error => Json,
+        bpxsd::reader::Error => Reader,
+        tree::query::Error => Query,
+        tree::schema::Error => Schema,
+        tree::convert::Error => Conversion,
+        tree::text::Error => Parse,
+        tree::patch::Error => Patch,
+        search::Error => Search,
+        table::batch::Error => Batch,
+        table::cursor::Error => Cursor,
+        table::query::Error => TableQuery,
+        table::sql::ParseError => SqlParse,
+        table::sql::Error => Sql,
+        table::index::Error => TableIndex,
+        table::profile::Error => TableProfile,
+        table::convert::Error => TableConvert,
+        table::row::Error => TableRow,
+        table::validate::Error => TableValidate,
+        table::Error => TableCore,
+        table::transaction::Error => TableTransaction
     }
 );
 
@@ -129,7 +313,7 @@ impl BPXError {
 }
 
 thread_local! {
-    pub static LAST_ERROR: RefCell<BPXError> = RefCell::new(BPXError::none());
+    pub static LAST_ERROR: RefCell<BPXError> = const { RefCell::new(BPXError::none()) };
 }
 
 pub fn set_last_error<E: IntoBPXError + Into<RustError>>(error: E) {
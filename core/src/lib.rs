@@ -35,3 +35,7 @@ mod tree;
 mod bpxsd;
 mod table;
 mod strings;
+mod merge;
+mod diff;
+mod unpack;
+mod search;
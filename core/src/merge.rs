@@ -0,0 +1,259 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Whole container merge.
+//!
+//! BPX table varchar cells are stored inline in the rows, so the only table data pointing into
+//! a strings section are the table name and the column names; these are what gets remapped when
+//! the strings sections of both containers are merged.
+
+use std::io::{Seek, SeekFrom, Write};
+use bp3d_util::simple_error;
+use bpx::core::Handle;
+use bpx::core::header::SECTION_TYPE_TABLE;
+use safer_ffi::prelude::*;
+use crate::common::{find_sections, read_all, Container};
+use crate::error::unwrap_result;
+use crate::section::{create_section, SectionOptions};
+use crate::strings::{resolve_strings, StringPool};
+use crate::table::layout::Layout;
+
+simple_error! {
+    pub Error {
+        MissingStrings => "the source container has tables but no strings section",
+        IncompatibleTables(String) => "cannot merge rows of table '{}': column layouts differ",
+        (impl From) Bpx(bpx::core::error::Error) => "BPX error: {}",
+        (impl From) Strings(bpx::strings::Error) => "strings error: {}",
+        (impl From) Table(bpx::table::error::Error) => "table error: {}",
+        (impl From) Io(std::io::Error) => "io error: {}"
+    }
+}
+
+#[derive_ReprC]
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum MergePolicy {
+    KeepBoth = 0,
+    Replace,
+    MergeRows
+}
+
+#[derive_ReprC]
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum StringsPolicy {
+    KeepBoth = 0,
+    Replace,
+    Merge
+}
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct MergeOptions {
+    pub tables: MergePolicy,
+    pub strings: StringsPolicy,
+    pub dst_strings: u32,
+    pub src_strings: u32
+}
+
+/// A change to apply to the destination container once the whole merge has been staged.
+enum Step {
+    Create(SectionOptions, Vec<u8>),
+    Append(Handle, Vec<u8>),
+    Rewrite(Handle, Vec<u8>),
+    Remove(Handle)
+}
+
+fn stage_section(container: &Container, handle: Handle) -> Result<(SectionOptions, Vec<u8>), Error> {
+    let header = container.underlying.sections()[handle].header();
+    let data = read_all(&mut *container.underlying.sections().load(handle)?)?;
+    Ok((SectionOptions::new(header.ty, header.flags), data))
+}
+
+fn load_table(container: &Container, handle: Handle) -> Result<(Layout, Vec<u8>), Error> {
+    let data = read_all(&mut *container.underlying.sections().load(handle)?)?;
+    let layout = Layout::read(&data)?;
+    Ok((layout, data))
+}
+
+fn remap_table(data: &mut [u8], from: &StringPool, to: &mut StringPool) -> Result<(), Error> {
+    let mut layout = Layout::read(data)?;
+    layout.header.name = to.put(from.get(layout.header.name)?);
+    for column in &mut layout.columns {
+        column.name = to.put(from.get(column.name)?);
+    }
+    layout.write(data);
+    Ok(())
+}
+
+fn find_table_by_name(container: &Container, tables: &[Handle], strings: &StringPool, name: &str) -> Result<Option<Handle>, Error> {
+    for handle in tables {
+        let (layout, _) = load_table(container, *handle)?;
+        if strings.get(layout.header.name)? == name {
+            return Ok(Some(*handle));
+        }
+    }
+    Ok(None)
+}
+
+fn stage_rows(dst: &Container, handle: Handle, dst_strings: &StringPool, src_data: &[u8], src_strings: &StringPool, name: &str) -> Result<Vec<u8>, Error> {
+    let (layout, _) = load_table(dst, handle)?;
+    let src_layout = Layout::read(src_data)?;
+    if !layout.is_compatible(&src_layout) {
+        return Err(Error::IncompatibleTables(name.into()));
+    }
+    for (a, b) in layout.columns.iter().zip(src_layout.columns.iter()) {
+        if dst_strings.get(a.name)? != src_strings.get(b.name)? {
+            return Err(Error::IncompatibleTables(name.into()));
+        }
+    }
+    Ok(src_data[src_layout.size()..].into())
+}
+
+fn apply(dst: &mut Container, step: Step) -> Result<(), Error> {
+    match step {
+        Step::Create(options, data) => {
+            create_section(dst, &options, &data)?;
+        },
+        Step::Append(handle, rows) => {
+            let mut data = dst.underlying.sections().load(handle)?;
+            data.seek(SeekFrom::End(0))?;
+            data.write_all(&rows)?;
+            data.seek(SeekFrom::Start(0))?;
+        },
+        Step::Rewrite(handle, buffer) => {
+            let mut data = dst.underlying.sections().load(handle)?;
+            data.seek(SeekFrom::Start(0))?;
+            data.write_all(&buffer)?;
+            data.seek(SeekFrom::Start(0))?;
+        },
+        Step::Remove(handle) => dst.underlying.sections_mut().remove(handle)
+    }
+    Ok(())
+}
+
+pub fn merge(dst: &mut Container, src: &Container, options: &MergeOptions) -> Result<(), Error> {
+    // Everything is first read, remapped and checked in memory, including the content of the
+    // target strings section; dst is only modified once the whole merge is known to succeed.
+    let mut steps = Vec::new();
+    let src_strings = match resolve_strings(src, options.src_strings) {
+        Some(v) => v,
+        None => {
+            if !find_sections(src, SECTION_TYPE_TABLE).is_empty() {
+                return Err(Error::MissingStrings);
+            }
+            for handle in src.underlying.sections() {
+                let (options, data) = stage_section(src, handle)?;
+                steps.push(Step::Create(options, data));
+            }
+            for step in steps {
+                apply(dst, step)?;
+            }
+            return Ok(());
+        }
+    };
+    let dst_strings = resolve_strings(dst, options.dst_strings);
+    let mut dst_tables = find_sections(dst, SECTION_TYPE_TABLE);
+    let src_pool = StringPool::load(src, src_strings)?;
+    let dst_pool = dst_strings.map(|v| StringPool::load(dst, v)).transpose()?;
+    // Tables coming from src are remapped only when their strings are merged into the existing
+    // strings section of dst; in all other cases they keep pointing to a copy of their own
+    // strings section.
+    let (target, mut target_pool) = match dst_strings {
+        Some(v) if options.strings == StringsPolicy::Merge => (Some(v), StringPool::load(dst, v)?),
+        _ => (None, StringPool::load(src, src_strings)?)
+    };
+    for handle in src.underlying.sections() {
+        if handle == src_strings {
+            continue;
+        }
+        let (section, mut data) = stage_section(src, handle)?;
+        if src.underlying.sections()[handle].header().ty != SECTION_TYPE_TABLE {
+            steps.push(Step::Create(section, data));
+            continue;
+        }
+        let layout = Layout::read(&data)?;
+        let name = src_pool.get(layout.header.name)?;
+        let existing = match &dst_pool {
+            Some(pool) if options.tables != MergePolicy::KeepBoth => find_table_by_name(dst, &dst_tables, pool, name)?,
+            _ => None
+        };
+        match (existing, &dst_pool) {
+            (Some(existing), Some(pool)) if options.tables == MergePolicy::MergeRows => {
+                let rows = stage_rows(dst, existing, pool, &data, &src_pool, name)?;
+                steps.push(Step::Append(existing, rows));
+            },
+            (existing, _) => {
+                if let Some(existing) = existing {
+                    steps.push(Step::Remove(existing));
+                    dst_tables.retain(|v| *v != existing);
+                }
+                if target.is_some() {
+                    remap_table(&mut data, &src_pool, &mut target_pool)?;
+                }
+                steps.push(Step::Create(section, data));
+            }
+        }
+    }
+    if let (Some(strings), Some(pool)) = (dst_strings, &dst_pool) {
+        if options.strings == StringsPolicy::Replace {
+            for handle in &dst_tables {
+                let (_, mut data) = load_table(dst, *handle)?;
+                remap_table(&mut data, pool, &mut target_pool)?;
+                steps.push(Step::Rewrite(*handle, data));
+            }
+            steps.push(Step::Remove(strings));
+        }
+    }
+    // The strings section goes first so that a new one keeps the place it had in dst.
+    steps.insert(0, match target {
+        Some(v) => Step::Rewrite(v, target_pool.into_bytes()),
+        None => Step::Create(stage_section(src, src_strings)?.0, target_pool.into_bytes())
+    });
+    for step in steps {
+        apply(dst, step)?;
+    }
+    Ok(())
+}
+
+#[ffi_export]
+pub unsafe fn bpx_merge_options_default(options: *mut MergeOptions) {
+    *options = MergeOptions {
+        tables: MergePolicy::KeepBoth,
+        strings: StringsPolicy::Merge,
+        dst_strings: 0,
+        src_strings: 0
+    }
+}
+
+#[ffi_export]
+pub fn bpx_container_merge(dst: &mut Container, src: &Container, options: &MergeOptions) -> bool {
+    let res = unwrap_result(merge(dst, src, options)).is_some();
+    dst.refresh();
+    res
+}
@@ -0,0 +1,603 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Search across every section of a container.
+//!
+//! The pattern is compiled to a single byte regex. In bytes mode it matches the pattern bytes
+//! literally, in text mode it matches the pattern text in every requested encoding and in regex
+//! mode it is used as is. Sections are searched according to their type: BPXSD sections by key
+//! name and value, string sections by string and table sections by cell, with values formatted
+//! as text. Any other section, or a section whose type scope is disabled, is searched as raw
+//! bytes when the raw scope is enabled.
+//!
+//! Raw bytes are scanned by stepping a lazy DFA built from the same pattern, so that progress is
+//! reported and cancellation checked every `RAW_PROGRESS_BYTES` bytes even when nothing matches;
+//! the start of each match is then found by a search limited to the matched span. BPXSD and
+//! string sections report progress and check cancellation every `SD_PROGRESS_NODES` nodes and
+//! `STRINGS_PROGRESS_COUNT` strings, and table sections every `TABLE_PROGRESS_ROWS` rows.
+//!
+//! Key names and formatted values do not appear as such in BPXSD data, so a key hit covers the
+//! 8 byte hash of the key and a value hit the whole encoded value.
+
+use std::ffi::CString;
+use std::fmt::Write;
+use bp3d_util::simple_error;
+use bpx::core::Handle;
+use bpx::core::header::{SECTION_TYPE_SD, SECTION_TYPE_STRING, SECTION_TYPE_TABLE};
+use regex::bytes::Regex;
+use regex_automata::hybrid::dfa::{Cache, DFA};
+use regex_automata::nfa::thompson;
+use regex_automata::util::syntax;
+use regex_automata::{meta, Input};
+use safer_ffi::prelude::*;
+use crate::bpxsd::dictionary::Dictionary;
+use crate::bpxsd::reader::{Reader, ReaderOptions};
+use crate::common::{read_all, Container};
+use crate::error::unwrap_result;
+use crate::strings::{resolve_strings, StringPool};
+use crate::table::layout::{open_raw, Layout};
+use crate::table::row::{element_count, read_elements};
+use crate::tree::model::{Node, Value};
+use crate::tree::query::push_segment;
+use crate::tree::text::{format, FormatOptions};
+
+simple_error! {
+    pub Error {
+        EmptyPattern => "search pattern is empty",
+        Utf8 => "search pattern is not valid UTF-8",
+        NoEncoding => "search pattern cannot be encoded in any of the requested encodings",
+        InvalidRegex(String) => "invalid regular expression: {}",
+        (impl From) Bpx(bpx::core::error::Error) => "BPX error: {}",
+        (impl From) Io(std::io::Error) => "io error: {}"
+    }
+}
+
+pub const ENCODING_UTF8: u32 = 0x1;
+pub const ENCODING_UTF16LE: u32 = 0x2;
+pub const ENCODING_UTF16BE: u32 = 0x4;
+pub const ENCODING_LATIN1: u32 = 0x8;
+
+pub const SCOPE_RAW: u32 = 0x1;
+pub const SCOPE_SD: u32 = 0x2;
+pub const SCOPE_STRINGS: u32 = 0x4;
+pub const SCOPE_TABLES: u32 = 0x8;
+
+const TABLE_PROGRESS_ROWS: usize = 4096;
+const SD_PROGRESS_NODES: usize = 4096;
+const STRINGS_PROGRESS_COUNT: usize = 4096;
+const RAW_PROGRESS_BYTES: usize = 1 << 20;
+
+#[derive_ReprC]
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum SearchMode {
+    Bytes = 0,
+    Text,
+    Regex
+}
+
+#[derive_ReprC]
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum HitKind {
+    Bytes = 0,
+    Key,
+    Value,
+    String,
+    Cell
+}
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct SearchOptions {
+    pub mode: SearchMode,
+    pub encodings: u32,
+    pub scopes: u32,
+    pub case_insensitive: bool,
+    pub strings: u32,
+    pub max_hits: u32
+}
+
+#[derive_ReprC(dyn)]
+pub trait SearchProgress {
+    fn progress(&mut self, done: u64, total: u64) -> bool;
+}
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct Hit {
+    pub kind: HitKind,
+    pub section: u32,
+    pub offset: u64,
+    pub length: u64,
+    pub row: u64,
+    pub column: u32,
+    pub path: Option<char_p::Box>,
+    pub value: Option<repr_c::Box<Value>>
+}
+
+impl Hit {
+    fn new(kind: HitKind, section: Handle, offset: usize, length: usize) -> Self {
+        Self {
+            kind,
+            section: section.into_raw(),
+            offset: offset as _,
+            length: length as _,
+            row: 0,
+            column: 0,
+            path: None,
+            value: None
+        }
+    }
+
+    fn path(mut self, path: &str) -> Self {
+        self.path = Some(CString::new(path.replace('\0', "")).unwrap().into());
+        self
+    }
+
+    fn cell(mut self, row: usize, column: usize) -> Self {
+        self.row = row as _;
+        self.column = column as _;
+        self
+    }
+
+    fn value(mut self, value: Value) -> Self {
+        self.value = Some(Box::new(value).into());
+        self
+    }
+}
+
+#[derive_ReprC]
+#[repr(opaque)]
+pub struct SearchResult {
+    hits: Vec<Hit>,
+    cancelled: bool
+}
+
+fn escape_bytes(bytes: &[u8]) -> String {
+    let mut out = String::from("(?-u:");
+    for b in bytes {
+        write!(out, "\\x{:02X}", b).unwrap();
+    }
+    out.push(')');
+    out
+}
+
+fn case_variants(c: char, case_insensitive: bool) -> Vec<char> {
+    let mut variants = vec![c];
+    if case_insensitive {
+        for v in c.to_lowercase().chain(c.to_uppercase()) {
+            if !variants.contains(&v) {
+                variants.push(v);
+            }
+        }
+    }
+    variants
+}
+
+fn encode_text(text: &str, case_insensitive: bool, encode: impl Fn(char) -> Option<Vec<u8>>) -> Option<String> {
+    let mut out = String::new();
+    for c in text.chars() {
+        let variants: Vec<String> = case_variants(c, case_insensitive).into_iter()
+            .filter_map(|v| encode(v).map(|v| escape_bytes(&v)))
+            .collect();
+        if variants.is_empty() {
+            return None;
+        }
+        write!(out, "(?:{})", variants.join("|")).unwrap();
+    }
+    Some(out)
+}
+
+fn utf16(c: char, unit: fn(u16) -> [u8; 2]) -> Option<Vec<u8>> {
+    Some(c.encode_utf16(&mut [0; 2]).iter().flat_map(|v| unit(*v)).collect())
+}
+
+fn text_pattern(text: &str, options: &SearchOptions) -> Result<String, Error> {
+    let ci = options.case_insensitive;
+    let mut alternatives = Vec::new();
+    if options.encodings & ENCODING_UTF8 != 0 {
+        alternatives.push(format!("(?{}:{})", if ci { "i" } else { "" }, regex::escape(text)));
+    }
+    if options.encodings & ENCODING_UTF16LE != 0 {
+        alternatives.extend(encode_text(text, ci, |c| utf16(c, u16::to_le_bytes)));
+    }
+    if options.encodings & ENCODING_UTF16BE != 0 {
+        alternatives.extend(encode_text(text, ci, |c| utf16(c, u16::to_be_bytes)));
+    }
+    if options.encodings & ENCODING_LATIN1 != 0 {
+        alternatives.extend(encode_text(text, ci, |c| u8::try_from(c).ok().map(|v| vec![v])));
+    }
+    match alternatives.is_empty() {
+        true => Err(Error::NoEncoding),
+        false => Ok(alternatives.join("|"))
+    }
+}
+
+pub fn compile(pattern: &[u8], options: &SearchOptions) -> Result<Regex, Error> {
+    if pattern.is_empty() {
+        return Err(Error::EmptyPattern);
+    }
+    let text = || std::str::from_utf8(pattern).map_err(|_| Error::Utf8);
+    let source = match options.mode {
+        SearchMode::Bytes if options.case_insensitive => format!("(?i){}", escape_bytes(pattern)),
+        SearchMode::Bytes => escape_bytes(pattern),
+        SearchMode::Text => text_pattern(text()?, options)?,
+        SearchMode::Regex if options.case_insensitive => format!("(?i:{})", text()?),
+        SearchMode::Regex => text()?.into()
+    };
+    Regex::new(&source).map_err(|e| Error::InvalidRegex(e.to_string()))
+}
+
+struct RawScanner {
+    dfa: DFA,
+    cache: Cache,
+    regex: meta::Regex
+}
+
+impl RawScanner {
+    fn new(pattern: &str) -> Option<Self> {
+        let syntax = syntax::Config::new().utf8(false);
+        let dfa = DFA::builder()
+            .configure(DFA::config().unicode_word_boundary(true))
+            .syntax(syntax)
+            .thompson(thompson::Config::new().utf8(false))
+            .build(pattern)
+            .ok()?;
+        let regex = meta::Regex::builder()
+            .configure(meta::Regex::config().utf8_empty(false))
+            .syntax(syntax)
+            .build(pattern)
+            .ok()?;
+        Some(Self { cache: dfa.create_cache(), dfa, regex })
+    }
+}
+
+/// The end of the next match found by a raw scan.
+enum MatchEnd {
+    Found(usize),
+    None,
+    /// The DFA cannot handle the data (for example a Unicode word boundary next to a non ASCII
+    /// byte); the regex must be run on the whole data instead.
+    Unsupported
+}
+
+struct Search<'a, 'p> {
+    container: &'a Container,
+    regex: Regex,
+    raw: Option<RawScanner>,
+    options: &'a SearchOptions,
+    dictionary: Option<&'a Dictionary>,
+    strings: Option<StringPool>,
+    progress: Option<&'p mut dyn FnMut(u64, u64) -> bool>,
+    hits: Vec<Hit>,
+    done: u64,
+    total: u64,
+    nodes: usize,
+    cancelled: bool
+}
+
+fn detail(node: &Node, name: &str) -> Option<usize> {
+    node.details.as_ref()?.children.iter()
+        .find(|v| v.name.to_str() == name)
+        .map(|v| v.value.as_u64() as usize)
+}
+
+impl Search<'_, '_> {
+    fn is_full(&self) -> bool {
+        self.options.max_hits != 0 && self.hits.len() >= self.options.max_hits as usize
+    }
+
+    fn push(&mut self, hit: Hit) {
+        if !self.is_full() {
+            self.hits.push(hit);
+        }
+    }
+
+    fn should_stop(&self) -> bool {
+        self.cancelled || self.is_full()
+    }
+
+    fn report(&mut self, done: u64) {
+        if let Some(progress) = &mut self.progress {
+            if !progress(done, self.total) {
+                self.cancelled = true;
+            }
+        }
+    }
+
+    fn find(&self, text: &str) -> Option<usize> {
+        self.regex.find(text.as_bytes()).map(|v| v.len())
+    }
+
+    fn match_end(&mut self, raw: &mut RawScanner, data: &[u8], pos: usize) -> MatchEnd {
+        let (dfa, cache) = (&raw.dfa, &mut raw.cache);
+        let mut sid = match dfa.start_state_forward(cache, &Input::new(data).range(pos..)) {
+            Ok(v) => v,
+            Err(_) => return MatchEnd::Unsupported
+        };
+        let mut end = MatchEnd::None;
+        for (i, byte) in data[pos..].iter().enumerate() {
+            if i % RAW_PROGRESS_BYTES == RAW_PROGRESS_BYTES - 1 {
+                self.report(self.done + (pos + i) as u64);
+                if self.should_stop() {
+                    return MatchEnd::None;
+                }
+            }
+            sid = match dfa.next_state(cache, sid, *byte) {
+                Ok(v) => v,
+                Err(_) => return MatchEnd::Unsupported
+            };
+            if sid.is_tagged() {
+                // Match states are delayed by one byte: the match ends before the current byte.
+                if sid.is_match() {
+                    end = MatchEnd::Found(pos + i);
+                } else if sid.is_dead() {
+                    return end;
+                } else if sid.is_quit() {
+                    return MatchEnd::Unsupported;
+                }
+            }
+        }
+        match dfa.next_eoi_state(cache, sid) {
+            Ok(v) if v.is_match() => MatchEnd::Found(data.len()),
+            Ok(_) => end,
+            Err(_) => MatchEnd::Unsupported
+        }
+    }
+
+    fn next_raw_match(&mut self, raw: Option<&mut RawScanner>, data: &[u8], pos: usize) -> Option<(usize, usize)> {
+        let end = match raw {
+            Some(raw) => match self.match_end(raw, data, pos) {
+                MatchEnd::Found(end) => return raw.regex.search(&Input::new(data).range(pos..end)).map(|m| (m.start(), m.end())),
+                v => v
+            },
+            None => MatchEnd::Unsupported
+        };
+        match end {
+            MatchEnd::Unsupported => self.regex.find_at(data, pos).map(|m| (m.start(), m.end())),
+            _ => None
+        }
+    }
+
+    fn search_bytes(&mut self, handle: Handle, data: &[u8]) {
+        let mut raw = self.raw.take();
+        // Same iteration rules as Regex::find_iter: an empty match right after the previous
+        // match is skipped and the scan moves one byte forward after an empty match.
+        let mut pos = 0;
+        let mut last = None;
+        while pos <= data.len() && !self.should_stop() {
+            let (start, end) = match self.next_raw_match(raw.as_mut(), data, pos) {
+                Some(v) => v,
+                None => break
+            };
+            pos = if start == end { end + 1 } else { end };
+            if start == end && last == Some(end) {
+                continue;
+            }
+            last = Some(end);
+            self.push(Hit::new(HitKind::Bytes, handle, start, end - start));
+        }
+        self.raw = raw;
+    }
+
+    fn search_node(&mut self, handle: Handle, path: &str, node: &Node) {
+        for (index, child) in node.children.iter().enumerate() {
+            if self.should_stop() {
+                break;
+            }
+            let offset = detail(child, "offset").unwrap_or(0);
+            self.nodes += 1;
+            if self.nodes % SD_PROGRESS_NODES == SD_PROGRESS_NODES - 1 {
+                self.report(self.done + offset as u64);
+            }
+            let mut child_path = String::from(path);
+            push_segment(&mut child_path, index, child);
+            // Object entries store the key hash and the type code right before the value.
+            if detail(child, "hash").is_some() && self.find(child.name.to_str()).is_some() {
+                self.push(Hit::new(HitKind::Key, handle, offset.saturating_sub(9), 8).path(&child_path));
+            }
+            if child.children.is_empty() && !matches!(child.value, Value::Null)
+                && self.find(&format(&child.value, &FormatOptions::default())).is_some() {
+                let size = detail(child, "size").unwrap_or(0);
+                self.push(Hit::new(HitKind::Value, handle, offset, size).path(&child_path).value(child.value.clone()));
+            }
+            self.search_node(handle, &child_path, child);
+        }
+    }
+
+    fn search_sd(&mut self, handle: Handle, data: &[u8]) -> bool {
+        let root = match Reader::new(data, &ReaderOptions::default(), self.dictionary).read_root() {
+            Ok(v) => v,
+            Err(_) => return false
+        };
+        self.search_node(handle, "$", &root);
+        true
+    }
+
+    fn search_strings(&mut self, handle: Handle, data: &[u8]) {
+        let mut address = 0;
+        let mut count = 0;
+        while let Some(end) = data[address..].iter().position(|v| *v == 0) {
+            count += 1;
+            if count % STRINGS_PROGRESS_COUNT == STRINGS_PROGRESS_COUNT - 1 {
+                self.report(self.done + address as u64);
+            }
+            if self.should_stop() {
+                break;
+            }
+            if let Ok(s) = std::str::from_utf8(&data[address..address + end]) {
+                if let Some(len) = self.find(s) {
+                    self.push(Hit::new(HitKind::String, handle, address, len)
+                        .value(Value::String(CString::new(s).unwrap().into())));
+                }
+            }
+            address += end + 1;
+        }
+    }
+
+    fn search_table(&mut self, handle: Handle, data: &[u8]) -> bool {
+        let layout = match Layout::read(data) {
+            Ok(v) => v,
+            Err(_) => return false
+        };
+        let names: Vec<String> = (0..layout.columns.len())
+            .map(|v| self.strings.as_ref()
+                .and_then(|s| s.get(layout.columns[v].name).ok())
+                .map(String::from)
+                .unwrap_or_else(|| format!("#{}", v)))
+            .collect();
+        let (free, row_size) = match open_raw(self.container, handle) {
+            Ok(v) => (v.get_row_size(), v.get_actual_row_size()),
+            Err(_) => return false
+        };
+        for (index, row) in data[layout.size()..].chunks_exact(row_size).enumerate() {
+            if self.should_stop() {
+                break;
+            }
+            if index % TABLE_PROGRESS_ROWS == TABLE_PROGRESS_ROWS - 1 {
+                self.report(self.done + (layout.size() + index * row_size) as u64);
+            }
+            if row[free] == 1 {
+                continue;
+            }
+            let mut offset = 0;
+            for (column, (desc, name)) in layout.columns.iter().zip(names.iter()).enumerate() {
+                let size = desc.get_size();
+                let count = element_count(desc);
+                if let Ok(elements) = read_elements(desc.ty, count, &row[offset..offset + size]) {
+                    for (element, value) in elements.into_vec().into_iter().enumerate() {
+                        if let Some(len) = self.find(&format(&value, &FormatOptions::default())) {
+                            let pos = layout.size() + index * row_size + offset + element * (size / count);
+                            self.push(Hit::new(HitKind::Cell, handle, pos, len).cell(index, column).path(name).value(value));
+                        }
+                    }
+                }
+                offset += size;
+            }
+        }
+        true
+    }
+
+    fn search_section(&mut self, handle: Handle) -> Result<(), Error> {
+        let data = read_all(&mut *self.container.underlying.sections().load(handle)?)?;
+        let ty = self.container.underlying.sections()[handle].header().ty;
+        let scopes = self.options.scopes;
+        let handled = match ty {
+            SECTION_TYPE_SD if scopes & SCOPE_SD != 0 => self.search_sd(handle, &data),
+            SECTION_TYPE_STRING if scopes & SCOPE_STRINGS != 0 => {
+                self.search_strings(handle, &data);
+                true
+            },
+            SECTION_TYPE_TABLE if scopes & SCOPE_TABLES != 0 => self.search_table(handle, &data),
+            _ => false
+        };
+        if !handled && scopes & SCOPE_RAW != 0 {
+            self.search_bytes(handle, &data);
+        }
+        self.done += data.len() as u64;
+        Ok(())
+    }
+}
+
+pub fn search(container: &Container, pattern: &[u8], options: &SearchOptions, dictionary: Option<&Dictionary>,
+              progress: Option<&mut dyn FnMut(u64, u64) -> bool>) -> Result<SearchResult, Error> {
+    let regex = compile(pattern, options)?;
+    let strings = resolve_strings(container, options.strings)
+        .and_then(|v| StringPool::load(container, v).ok());
+    let total = container.underlying.sections().iter()
+        .map(|v| container.underlying.sections()[v].header().size as u64)
+        .sum();
+    let raw = RawScanner::new(regex.as_str());
+    let mut search = Search {
+        container,
+        regex,
+        raw,
+        options,
+        dictionary,
+        strings,
+        progress,
+        hits: Vec::new(),
+        done: 0,
+        total,
+        nodes: 0,
+        cancelled: false
+    };
+    for handle in container.underlying.sections().iter() {
+        if search.should_stop() {
+            break;
+        }
+        search.search_section(handle)?;
+        search.report(search.done);
+    }
+    Ok(SearchResult {
+        hits: search.hits,
+        cancelled: search.cancelled
+    })
+}
+
+#[ffi_export]
+pub unsafe fn bpx_search_options_default(options: *mut SearchOptions) {
+    *options = SearchOptions {
+        mode: SearchMode::Text,
+        encodings: ENCODING_UTF8,
+        scopes: SCOPE_RAW | SCOPE_SD | SCOPE_STRINGS | SCOPE_TABLES,
+        case_insensitive: false,
+        strings: 0,
+        max_hits: 0
+    }
+}
+
+#[ffi_export]
+pub fn bpx_container_search(container: &Container, pattern: c_slice::Ref<'_, u8>, options: &SearchOptions,
+                            dictionary: Option<&Dictionary>) -> Option<repr_c::Box<SearchResult>> {
+    unwrap_result(search(container, pattern.as_slice(), options, dictionary, None).map(|v| Box::new(v).into()))
+}
+
+#[ffi_export]
+pub fn bpx_container_search_with_progress(container: &Container, pattern: c_slice::Ref<'_, u8>, options: &SearchOptions,
+                                          dictionary: Option<&Dictionary>, mut progress: VirtualPtr<dyn SearchProgress>)
+                                          -> Option<repr_c::Box<SearchResult>> {
+    let mut callback = |done, total| progress.progress(done, total);
+    unwrap_result(search(container, pattern.as_slice(), options, dictionary, Some(&mut callback)).map(|v| Box::new(v).into()))
+}
+
+#[ffi_export]
+pub fn bpx_search_get_hits(result: &SearchResult) -> c_slice::Ref<'_, Hit> {
+    result.hits.as_slice().into()
+}
+
+#[ffi_export]
+pub fn bpx_search_is_cancelled(result: &SearchResult) -> bool {
+    result.cancelled
+}
+
+#[ffi_export]
+pub fn bpx_search_free(result: repr_c::Box<SearchResult>) {
+    drop(result);
+}
@@ -32,7 +32,8 @@ use bpx::core::header::{FLAG_CHECK_CRC32, FLAG_CHECK_WEAK, FLAG_COMPRESS_XZ, FLA
 use bpx::core::options::{Checksum, CompressionMethod};
 use bpx::util::traits::{ReadFill, Shift, ShiftTo};
 use safer_ffi::prelude::*;
-use crate::common::{Container, CSeekFrom, SectionInfo, with_section, try_with_section};
+use crate::common::{Container, CSeekFrom, SectionInfo, with_section, try_with_section, try_with_section_mut, read_all};
+use crate::error::unwrap_result;
 
 #[derive_ReprC]
 #[repr(C)]
@@ -43,7 +44,15 @@ pub struct SectionOptions {
 }
 
 impl SectionOptions {
-    fn to_options(&self) -> bpx::core::options::SectionOptions {
+    pub fn new(ty: u8, flags: u8) -> SectionOptions {
+        SectionOptions {
+            ty,
+            flags,
+            compression_threshold: 0
+        }
+    }
+
+    pub fn to_options(&self) -> bpx::core::options::SectionOptions {
         let mut opts = bpx::core::options::SectionOptions::default();
         opts.ty(self.ty);
         if self.flags & FLAG_CHECK_WEAK != 0 {
@@ -65,6 +74,21 @@ impl SectionOptions {
     }
 }
 
+pub fn create_section(dst: &mut Container, options: &SectionOptions, buffer: &[u8]) -> Result<Handle, bpx::core::error::Error> {
+    let handle = dst.underlying.sections_mut().create(options.to_options());
+    let mut data = dst.underlying.sections().open(handle)?;
+    data.write_all(buffer)?;
+    data.seek(SeekFrom::Start(0))?;
+    Ok(handle)
+}
+
+pub fn copy_section(src: &Container, handle: Handle, dst: &mut Container) -> Result<Handle, bpx::core::error::Error> {
+    let header = src.underlying.sections()[handle].header();
+    let options = SectionOptions::new(header.ty, header.flags);
+    let buffer = read_all(&mut *src.underlying.sections().load(handle)?)?;
+    create_section(dst, &options, &buffer)
+}
+
 #[ffi_export]
 pub unsafe fn bpx_section_options_default(options: *mut SectionOptions) {
     *options = SectionOptions {
@@ -82,6 +106,18 @@ pub fn bpx_section_create(container: &mut Container, options: &SectionOptions) -
     handle.into_raw()
 }
 
+#[ffi_export]
+pub fn bpx_section_copy(src: &Container, handle: u32, dst: &mut Container) -> u32 {
+    let handle = unsafe { Handle::from_raw(handle) };
+    match unwrap_result(copy_section(src, handle, dst)) {
+        Some(copy) => {
+            dst.refresh();
+            copy.into_raw()
+        },
+        None => 0
+    }
+}
+
 #[ffi_export]
 pub fn bpx_section_remove(container: &mut Container, handle: u32) {
     let handle = unsafe { Handle::from_raw(handle) };
@@ -123,28 +159,28 @@ pub fn bpx_section_read_exact(container: &Container, handle: u32, buffer: c_slic
 
 #[ffi_export]
 pub fn bpx_section_write(container: &Container, handle: u32, buffer: c_slice::Ref<'_, u8>) -> isize {
-    try_with_section(container, handle, |v| v.write(buffer.as_slice()))
+    try_with_section_mut(container, handle, |v| v.write(buffer.as_slice()))
         .map(|v| v as _)
         .unwrap_or(-1)
 }
 
 #[ffi_export]
 pub fn bpx_section_write_all(container: &Container, handle: u32, buffer: c_slice::Ref<'_, u8>) -> bool {
-    try_with_section(container, handle, |v| v.write_all(buffer.as_slice()))
+    try_with_section_mut(container, handle, |v| v.write_all(buffer.as_slice()))
         .map(|_| true)
         .unwrap_or(false)
 }
 
 #[ffi_export]
 pub fn bpx_section_shift_right(container: &Container, handle: u32, length: usize) -> bool {
-    try_with_section(container, handle, |v| v.shift(ShiftTo::Right(length as _)))
+    try_with_section_mut(container, handle, |v| v.shift(ShiftTo::Right(length as _)))
         .map(|_| true)
         .unwrap_or(false)
 }
 
 #[ffi_export]
 pub fn bpx_section_shift_left(container: &Container, handle: u32, length: usize) -> bool {
-    try_with_section(container, handle, |v| v.shift(ShiftTo::Left(length as _)))
+    try_with_section_mut(container, handle, |v| v.shift(ShiftTo::Left(length as _)))
         .map(|_| true)
         .unwrap_or(false)
 }
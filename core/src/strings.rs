@@ -26,12 +26,75 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::collections::HashMap;
+use std::io::{Seek, SeekFrom, Write};
 use bpx::core::Handle;
+use bpx::core::header::SECTION_TYPE_STRING;
 use bpx::strings::{load_string_section, StringSection};
 use safer_ffi::prelude::*;
-use crate::common::Container;
+use crate::common::{read_all, Container};
 use crate::error::unwrap_result;
 
+pub fn resolve_strings(container: &Container, handle: u32) -> Option<Handle> {
+    match handle {
+        0 => container.underlying.sections().find_by_type(SECTION_TYPE_STRING),
+        v => Some(unsafe { Handle::from_raw(v) })
+    }
+}
+
+pub struct StringPool {
+    data: Vec<u8>,
+    lookup: HashMap<String, u32>
+}
+
+impl StringPool {
+    pub fn load(container: &Container, handle: Handle) -> Result<Self, bpx::core::error::Error> {
+        let data = read_all(&mut *container.underlying.sections().load(handle)?)?;
+        let mut lookup = HashMap::new();
+        let mut address = 0;
+        while let Some(end) = data[address..].iter().position(|v| *v == 0) {
+            if let Ok(v) = std::str::from_utf8(&data[address..address + end]) {
+                lookup.entry(v.into()).or_insert(address as u32);
+            }
+            address += end + 1;
+        }
+        Ok(Self { data, lookup })
+    }
+
+    pub fn get(&self, address: u32) -> Result<&str, bpx::strings::Error> {
+        let data = self.data.get(address as usize..).ok_or(bpx::strings::Error::Eos)?;
+        let end = data.iter().position(|v| *v == 0).ok_or(bpx::strings::Error::Eos)?;
+        std::str::from_utf8(&data[..end]).map_err(|_| bpx::strings::Error::Utf8)
+    }
+
+    pub fn find(&self, s: &str) -> Option<u32> {
+        self.lookup.get(s).copied()
+    }
+
+    pub fn put(&mut self, s: &str) -> u32 {
+        if let Some(address) = self.find(s) {
+            return address;
+        }
+        let address = self.data.len() as u32;
+        self.data.extend_from_slice(s.as_bytes());
+        self.data.push(0);
+        self.lookup.insert(s.into(), address);
+        address
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+
+    pub fn save(&self, container: &Container, handle: Handle) -> Result<(), bpx::core::error::Error> {
+        let mut data = container.underlying.sections().load(handle)?;
+        data.seek(SeekFrom::Start(0))?;
+        data.write_all(&self.data)?;
+        data.seek(SeekFrom::Start(0))?;
+        Ok(())
+    }
+}
+
 #[ffi_export]
 pub fn bpx_strings_create(container: &mut Container) -> u32 {
     let strings = StringSection::create(&mut container.underlying);
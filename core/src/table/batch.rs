@@ -0,0 +1,283 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Bulk row access through columnar buffers.
+//!
+//! Each column is transferred through its own buffer. Fixed size columns hold the raw little
+//! endian cells of every row, back to back. Varchar columns hold `count + 1` `u32` offsets into a
+//! single UTF-8 buffer shared by all varchar columns: the string of row `i` spans from offset `i`
+//! to offset `i + 1`. Strings are laid out column by column, so every column owns a contiguous
+//! range of the shared buffer. The free flag of every row is transferred through one more buffer
+//! holding one byte per row, 1 for a free row and 0 otherwise.
+
+use std::io::{Read, Seek, SeekFrom, Write};
+use bp3d_util::simple_error;
+use bpx::core::SectionData;
+use bpx::table::column::{Column, Type, SIZE_COLUMN_STRUCTURE};
+use bpx::table::header::SIZE_HEADER_STRUCTURE;
+use safer_ffi::prelude::*;
+use crate::error::unwrap_result;
+use crate::table::core::Table;
+use crate::table::index;
+use crate::table::transaction;
+
+simple_error! {
+    pub Error {
+        ColumnCount(usize) => "expected {} column buffers",
+        BufferTooSmall(usize) => "column buffer {} is too small",
+        FreeBufferTooSmall => "free flag buffer is too small",
+        StringBufferTooSmall(usize) => "string buffer is too small ({} bytes needed)",
+        BadOffsets(usize) => "string offsets of column {} are out of bounds or not increasing",
+        Utf8(usize) => "column {} contains a string which is not valid UTF-8",
+        StringTooLong(usize) => "column {} contains a string longer than the column",
+        RowIndexOutOfBounds(usize) => "row index out of bounds ({})",
+        (impl From) Index(index::Error) => "index error: {}",
+        (impl From) Transaction(transaction::Error) => "transaction error: {}",
+        (impl From) Bpx(bpx::core::error::Error) => "BPX error: {}",
+        (impl From) Io(std::io::Error) => "io error: {}"
+    }
+}
+
+const OFFSET_SIZE: usize = std::mem::size_of::<u32>();
+
+struct Shape {
+    columns: Vec<Column>,
+    header_size: usize,
+    actual_row_size: usize
+}
+
+impl Shape {
+    fn new(table: &Table) -> Self {
+        let container = unsafe { &*table.container };
+        let columns: Vec<Column> = table.inner.columns(&container.underlying).iter().copied().collect();
+        Self {
+            header_size: SIZE_HEADER_STRUCTURE + columns.len() * SIZE_COLUMN_STRUCTURE,
+            actual_row_size: table.inner.get_actual_row_size(),
+            columns
+        }
+    }
+
+    fn row_count(&self, size: usize) -> usize {
+        size.saturating_sub(self.header_size) / self.actual_row_size
+    }
+
+    fn buffer_size(&self, index: usize, count: usize) -> usize {
+        match self.columns[index].ty {
+            Type::Varchar => (count + 1) * OFFSET_SIZE,
+            _ => count * self.columns[index].get_size()
+        }
+    }
+
+    fn check_buffers(&self, sizes: impl ExactSizeIterator<Item = usize>, free: usize, count: usize) -> Result<(), Error> {
+        if free < count {
+            return Err(Error::FreeBufferTooSmall);
+        }
+        if sizes.len() != self.columns.len() {
+            return Err(Error::ColumnCount(self.columns.len()));
+        }
+        for (index, size) in sizes.enumerate() {
+            if size < self.buffer_size(index, count) {
+                return Err(Error::BufferTooSmall(index));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn get_offset(buffer: &[u8], index: usize) -> usize {
+    u32::from_le_bytes(buffer[index * OFFSET_SIZE..(index + 1) * OFFSET_SIZE].try_into().unwrap()) as usize
+}
+
+fn set_offset(buffer: &mut [u8], index: usize, offset: usize) {
+    buffer[index * OFFSET_SIZE..(index + 1) * OFFSET_SIZE].copy_from_slice(&(offset as u32).to_le_bytes());
+}
+
+fn trim_cell(cell: &[u8]) -> &[u8] {
+    let start = cell.iter().position(|v| *v != 0).unwrap_or(cell.len());
+    let end = cell.iter().rposition(|v| *v != 0).map(|v| v + 1).unwrap_or(start);
+    &cell[start..end]
+}
+
+fn truncate_str(s: &str, len: usize) -> &str {
+    let mut end = len.min(s.len());
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+/// Reads `count` rows starting at `start` into the given buffers and returns the number of rows
+/// read, which is less than `count` when the table ends first, along with the number of bytes
+/// used in the string buffer.
+pub fn read(table: &Table, start: usize, count: usize, columns: &mut [&mut [u8]], free: &mut [u8], strings: &mut [u8]) -> Result<(usize, usize), Error> {
+    let shape = Shape::new(table);
+    let (count, rows) = table.with_data(|data| {
+        let total = shape.row_count(data.size());
+        if start > total {
+            return Err(Error::RowIndexOutOfBounds(start));
+        }
+        let count = count.min(total - start);
+        shape.check_buffers(columns.iter().map(|v| v.len()), free.len(), count)?;
+        let mut rows = vec![0; count * shape.actual_row_size];
+        data.seek(SeekFrom::Start((shape.header_size + start * shape.actual_row_size) as _))?;
+        data.read_exact(&mut rows)?;
+        Ok((count, rows))
+    })?;
+    let mut needed = 0;
+    let mut offset = 0;
+    for (index, column) in shape.columns.iter().enumerate() {
+        let size = column.get_size();
+        if column.ty == Type::Varchar {
+            for row in rows.chunks_exact(shape.actual_row_size) {
+                let s = trim_cell(&row[offset..offset + size]);
+                std::str::from_utf8(s).map_err(|_| Error::Utf8(index))?;
+                needed += s.len();
+            }
+        }
+        offset += size;
+    }
+    if needed > strings.len() {
+        return Err(Error::StringBufferTooSmall(needed));
+    }
+    let row_size = table.inner.get_row_size();
+    for (flag, row) in free.iter_mut().zip(rows.chunks_exact(shape.actual_row_size)) {
+        *flag = row[row_size];
+    }
+    let mut pos = 0;
+    let mut offset = 0;
+    for (column, buffer) in shape.columns.iter().zip(columns.iter_mut()) {
+        let size = column.get_size();
+        for (index, row) in rows.chunks_exact(shape.actual_row_size).enumerate() {
+            let cell = &row[offset..offset + size];
+            match column.ty {
+                Type::Varchar => {
+                    let s = trim_cell(cell);
+                    set_offset(buffer, index, pos);
+                    strings[pos..pos + s.len()].copy_from_slice(s);
+                    pos += s.len();
+                },
+                _ => buffer[index * size..(index + 1) * size].copy_from_slice(cell)
+            }
+        }
+        if column.ty == Type::Varchar {
+            set_offset(buffer, count, pos);
+        }
+        offset += size;
+    }
+    Ok((count, pos))
+}
+
+/// Writes `count` rows starting at `start` from the given buffers. Rows past the end of the table
+/// are appended, so `start` may be at most the current row count.
+pub fn write(table: &Table, start: usize, count: usize, columns: &[&[u8]], free: &[u8], strings: &[u8]) -> Result<(), Error> {
+    let shape = Shape::new(table);
+    shape.check_buffers(columns.iter().map(|v| v.len()), free.len(), count)?;
+    let mut rows = vec![0; count * shape.actual_row_size];
+    let row_size = table.inner.get_row_size();
+    for (row, flag) in rows.chunks_exact_mut(shape.actual_row_size).zip(free) {
+        row[row_size] = (*flag != 0) as u8;
+    }
+    let mut offset = 0;
+    for (index, (column, buffer)) in shape.columns.iter().zip(columns.iter()).enumerate() {
+        let size = column.get_size();
+        for (row, out) in rows.chunks_exact_mut(shape.actual_row_size).enumerate() {
+            let cell = &mut out[offset..offset + size];
+            match column.ty {
+                Type::Varchar => {
+                    let (a, b) = (get_offset(buffer, row), get_offset(buffer, row + 1));
+                    let bytes = strings.get(a..b).ok_or(Error::BadOffsets(index))?;
+                    let s = std::str::from_utf8(bytes).map_err(|_| Error::Utf8(index))?;
+                    if table.strict && s.len() > size {
+                        return Err(Error::StringTooLong(index));
+                    }
+                    let s = truncate_str(s, size);
+                    cell[..s.len()].copy_from_slice(s.as_bytes());
+                },
+                Type::Boolean => {
+                    for (i, v) in cell.iter_mut().enumerate() {
+                        *v = (buffer[row * size + i] != 0) as u8;
+                    }
+                },
+                _ => cell.copy_from_slice(&buffer[row * size..(row + 1) * size])
+            }
+        }
+        offset += size;
+    }
+    let total = shape.row_count(table.with_data(|data| Ok::<_, Error>(data.size()))?);
+    if start > total {
+        return Err(Error::RowIndexOutOfBounds(start));
+    }
+    if table.in_transaction() {
+        return Ok(transaction::write_rows(table, start, &rows)?);
+    }
+    let container = unsafe { &*table.container };
+    let pending = index::prepare(table, || rows.chunks_exact(shape.actual_row_size).enumerate()
+        .map(|(i, row)| index::decode_row(table, row).map(|v| (start + i, v)))
+        .collect())?;
+    container.touch();
+    let mut data = container.underlying.sections().load(table.inner.handle())?;
+    data.seek(SeekFrom::Start((shape.header_size + start * shape.actual_row_size) as _))?;
+    data.write_all(&rows)?;
+    drop(data);
+    index::commit(table, pending);
+    Ok(())
+}
+
+#[ffi_export]
+pub fn bpx_table_read_batch(table: &Table, start: usize, count: usize, columns: c_slice::Mut<'_, c_slice::Mut<'_, u8>>,
+                            free: c_slice::Mut<'_, u8>, strings: c_slice::Mut<'_, u8>, strings_len: &mut usize) -> isize {
+    let mut buffers: Vec<&mut [u8]> = columns.as_slice().iter_mut().map(|v| &mut **v).collect();
+    match unwrap_result(read(table, start, count, &mut buffers, free.as_slice(), strings.as_slice())) {
+        Some((rows, len)) => {
+            *strings_len = len;
+            rows as _
+        },
+        None => -1
+    }
+}
+
+#[ffi_export]
+pub fn bpx_table_write_batch(table: &Table, start: usize, count: usize, columns: c_slice::Ref<'_, c_slice::Ref<'_, u8>>,
+                             free: c_slice::Ref<'_, u8>, strings: c_slice::Ref<'_, u8>) -> bool {
+    let buffers: Vec<&[u8]> = columns.as_slice().iter().map(|v| v.as_slice()).collect();
+    unwrap_result(write(table, start, count, &buffers, free.as_slice(), strings.as_slice())).is_some()
+}
+
+#[ffi_export]
+pub fn bpx_table_append_batch(table: &Table, count: usize, columns: c_slice::Ref<'_, c_slice::Ref<'_, u8>>,
+                              free: c_slice::Ref<'_, u8>, strings: c_slice::Ref<'_, u8>) -> isize {
+    let start = match unwrap_result(table.with_data(|data| Ok::<_, Error>(data.size()))) {
+        Some(size) => Shape::new(table).row_count(size),
+        None => return -1
+    };
+    let buffers: Vec<&[u8]> = columns.as_slice().iter().map(|v| v.as_slice()).collect();
+    match unwrap_result(write(table, start, count, &buffers, free.as_slice(), strings.as_slice())) {
+        Some(()) => start as _,
+        None => -1
+    }
+}
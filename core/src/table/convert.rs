@@ -0,0 +1,431 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+//! Conversion between tables and arrays of objects.
+//!
+//! A table converts to an array holding one object per row which is not free, keyed by column
+//! name; the elements of array columns become arrays. Converting an array of objects fills a table
+//! which has no columns yet. Its columns are the keys of the objects in order of first appearance,
+//! and the type of a column is inferred from every value of its key: integers of different types
+//! widen to an integer type which holds both, integers mixed with floating point values become
+//! double, as do unsigned 64-bit integers mixed with signed ones, and strings become a varchar as
+//! long as the longest string. Arrays of numbers or booleans become array columns as long as the
+//! longest array. Columns are at most 65535 bytes or elements long. Keys which only ever hold null
+//! are left out.
+//!
+//! Null and missing values, as well as the missing elements of arrays shorter than their column,
+//! take the default value of their column. Any other value which does not
+//! convert to the type of its column, as well as entries which are not objects, is reported along
+//! with its row and the row is skipped.
+//!
+//! Neither conversion is possible while the table has a pending transaction.
+
+use std::collections::HashMap;
+use std::ffi::CString;
+use bp3d_util::simple_error;
+use bpx::util::hash::hash;
+use safer_ffi::prelude::*;
+use crate::bpxsd::dictionary::Dictionary;
+use crate::bpxsd::reader::{self, set_debug_details, type_details, Reader, ReaderOptions, CODE_ARRAY, CODE_OBJECT};
+use crate::bpxsd::writer;
+use crate::error::unwrap_result;
+use crate::table::core::{self, Table};
+use crate::table::row::{self, column_type, element_count, Row};
+use crate::tree::convert;
+use crate::tree::model::{Node, Value, ValueType};
+
+simple_error! {
+    pub Error {
+        NotEmpty => "table already has columns",
+        PendingTransaction => "table has a pending transaction",
+        NotAnArray => "node is not an array",
+        NoColumns => "no key holds a value to infer a column from",
+        KeyNotFound(String) => "key not found: {}",
+        (impl From) Core(core::Error) => "table error: {}",
+        (impl From) Convert(convert::Error) => "conversion error: {}",
+        (impl From) Row(row::Error) => "row error: {}",
+        (impl From) Bpx(bpx::core::error::Error) => "BPX error: {}",
+        (impl From) Table(bpx::table::error::Error) => "table error: {}",
+        (impl From) Reader(reader::Error) => "BPXSD reader error: {}",
+        (impl From) Writer(writer::Error) => "BPXSD writer error: {}"
+    }
+}
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct RowError {
+    pub row: u64,
+    pub column: char_p::Box,
+    pub message: char_p::Box
+}
+
+impl RowError {
+    fn new(row: usize, column: &str, message: String) -> Self {
+        Self {
+            row: row as _,
+            column: CString::new(column.replace('\0', "")).unwrap().into(),
+            message: CString::new(message.replace('\0', "")).unwrap().into()
+        }
+    }
+}
+
+#[derive_ReprC]
+#[repr(opaque)]
+pub struct TableImport {
+    rows: u64,
+    errors: Vec<RowError>
+}
+
+fn new_node(name: &str, value: Value, code: u8) -> Node {
+    Node {
+        name: CString::new(name.replace('\0', "")).unwrap().into(),
+        value,
+        details: Some(Box::new(type_details(code)).into()),
+        children: Vec::new()
+    }
+}
+
+fn scalar_node(name: &str, value: Value) -> Node {
+    let code = writer::write_scalar(&mut Vec::new(), &value);
+    new_node(name, value, code)
+}
+
+/// Converts the rows of a table to an array node named after the table. With `debug`, every
+/// object is marked to get a `__debug__` table when encoded so the column names can be read back
+/// without a dictionary.
+pub fn to_node(table: &Table, debug: bool) -> Result<Node, Error> {
+    let container = unsafe { &*table.container };
+    if table.in_transaction() {
+        return Err(Error::PendingTransaction);
+    }
+    let columns = table.inner.columns(&container.underlying);
+    let mut names = Vec::with_capacity(columns.len());
+    for column in columns.iter() {
+        names.push(String::from(columns.load_name(column)?));
+    }
+    let arrays: Vec<bool> = columns.iter().map(|v| element_count(v) != 1).collect();
+    let mut root = new_node(table.name(), Value::Null, CODE_ARRAY);
+    let mut row = Row::new(table.alloc_row()?, table);
+    let mut data = container.underlying.sections().load(table.inner.handle())?;
+    for index in 0..bpx::table::row::count(&*data, &row.inner) {
+        bpx::table::row::read(&mut *data, &mut row.inner, index)?;
+        if row.inner.is_free() {
+            continue;
+        }
+        row.sync_read(table)?;
+        let mut object = new_node("", Value::Null, CODE_OBJECT);
+        for ((name, cell), array) in names.iter().zip(row.cells()).zip(&arrays) {
+            object.children.push(match array {
+                true => {
+                    let mut node = new_node(name, Value::Null, CODE_ARRAY);
+                    node.children = cell.iter().map(|v| scalar_node("", v.clone())).collect();
+                    node
+                },
+                false => scalar_node(name, cell[0].clone())
+            });
+        }
+        if let Some(details) = object.details.as_mut().filter(|_| debug) {
+            set_debug_details(details);
+        }
+        root.children.push(object);
+    }
+    Ok(root)
+}
+
+/// Converts the rows of a table to a BPXSD object which holds them as an array under `key`; with
+/// `debug`, the object gets a `__debug__` table as well.
+pub fn to_bpxsd(table: &Table, key: &str, debug: bool) -> Result<Vec<u8>, Error> {
+    let mut array = to_node(table, debug)?;
+    array.name = CString::new(key.replace('\0', "")).unwrap().into();
+    let mut root = new_node("root", Value::Null, CODE_OBJECT);
+    root.children.push(array);
+    if let Some(details) = root.details.as_mut().filter(|_| debug) {
+        set_debug_details(details);
+    }
+    Ok(writer::write(&root)?)
+}
+
+enum Field<'a> {
+    Scalar(&'a Value),
+    Array(Vec<&'a Value>)
+}
+
+fn field(node: &Node) -> Result<Field<'_>, String> {
+    match writer::type_code(node) {
+        CODE_OBJECT => Err("nested objects are not supported".into()),
+        CODE_ARRAY => node.children.iter().map(|v| match writer::type_code(v) {
+            CODE_ARRAY | CODE_OBJECT => Err("nested arrays are not supported".into()),
+            _ if matches!(v.value, Value::String(_)) => Err("arrays of strings are not supported".into()),
+            _ => Ok(&v.value)
+        }).collect::<Result<_, _>>().map(Field::Array),
+        _ => Ok(Field::Scalar(&node.value))
+    }
+}
+
+fn integer(ty: ValueType) -> Option<(bool, u32)> {
+    match ty {
+        ValueType::Int8 => Some((true, 8)),
+        ValueType::UInt8 => Some((false, 8)),
+        ValueType::Int16 => Some((true, 16)),
+        ValueType::UInt16 => Some((false, 16)),
+        ValueType::Int32 => Some((true, 32)),
+        ValueType::UInt32 => Some((false, 32)),
+        ValueType::Int64 => Some((true, 64)),
+        ValueType::UInt64 => Some((false, 64)),
+        _ => None
+    }
+}
+
+fn is_number(ty: ValueType) -> bool {
+    integer(ty).is_some() || matches!(ty, ValueType::Float | ValueType::Double)
+}
+
+/// Returns the type a column holding values of both types is given; the first type is kept when
+/// they do not mix.
+fn unify(a: ValueType, b: ValueType) -> ValueType {
+    if a == b {
+        return a;
+    }
+    match (integer(a), integer(b)) {
+        (Some((sa, ba)), Some((sb, bb))) => {
+            let signed = sa || sb;
+            let bits = |s: bool, bits: u32| if signed && !s { bits * 2 } else { bits };
+            match (signed, bits(sa, ba).max(bits(sb, bb))) {
+                (true, 8) => ValueType::Int8,
+                (true, 16) => ValueType::Int16,
+                (true, 32) => ValueType::Int32,
+                (true, 64) => ValueType::Int64,
+                // No integer type holds both a signed and an unsigned 64-bit integer.
+                (true, _) => ValueType::Double,
+                (false, 8) => ValueType::UInt8,
+                (false, 16) => ValueType::UInt16,
+                (false, 32) => ValueType::UInt32,
+                (false, _) => ValueType::UInt64
+            }
+        },
+        _ if is_number(a) && is_number(b) => ValueType::Double,
+        _ => a
+    }
+}
+
+struct ColumnGuess {
+    name: String,
+    ty: Option<ValueType>,
+    array: bool,
+    len: usize
+}
+
+impl ColumnGuess {
+    fn add(&mut self, value: &Value) {
+        if let Value::String(v) = value {
+            self.len = self.len.max(v.to_str().len());
+        }
+        let ty = value.get_type();
+        if ty != ValueType::Null {
+            self.ty = Some(self.ty.map(|v| unify(v, ty)).unwrap_or(ty));
+        }
+    }
+
+    fn len(&self) -> u16 {
+        match self.array || self.ty == Some(ValueType::String) {
+            true => self.len.clamp(1, u16::MAX as _) as _,
+            false => 1
+        }
+    }
+}
+
+fn infer(rows: &[Node]) -> Vec<ColumnGuess> {
+    let mut columns: Vec<ColumnGuess> = Vec::new();
+    let mut indices = HashMap::new();
+    for row in rows.iter().filter(|v| writer::type_code(v) == CODE_OBJECT) {
+        for node in &row.children {
+            let field = match field(node) {
+                Ok(Field::Scalar(Value::Null)) | Err(_) => continue,
+                Ok(v) => v
+            };
+            let index = *indices.entry(node.name.to_str()).or_insert_with(|| {
+                columns.push(ColumnGuess { name: node.name.to_str().into(), ty: None, array: matches!(field, Field::Array(_)), len: 0 });
+                columns.len() - 1
+            });
+            let column = &mut columns[index];
+            match field {
+                Field::Scalar(v) if !column.array => column.add(v),
+                Field::Array(values) if column.array => {
+                    column.len = column.len.max(values.len());
+                    for value in values {
+                        column.add(value);
+                    }
+                },
+                _ => ()
+            }
+        }
+    }
+    columns.retain(|v| v.ty.is_some());
+    columns
+}
+
+fn convert_row(row: &Node, columns: &[ColumnGuess], cells: &mut [Box<[Value]>]) -> Vec<(String, String)> {
+    let mut errors = Vec::new();
+    for node in &row.children {
+        let name = node.name.to_str();
+        let field = match field(node) {
+            Ok(Field::Scalar(Value::Null)) => continue,
+            Ok(v) => v,
+            Err(e) => {
+                errors.push((name.into(), e));
+                continue;
+            }
+        };
+        let index = match columns.iter().position(|v| v.name == name) {
+            Some(v) => v,
+            None => continue
+        };
+        let column = &columns[index];
+        let ty = column.ty.unwrap();
+        let len = column.len() as usize;
+        let res = match field {
+            Field::Scalar(_) if column.array => Err(format!("expected an array of up to {} elements", len)),
+            Field::Array(_) if !column.array => Err("expected a single value".into()),
+            Field::Array(values) if values.len() > len => {
+                Err(format!("expected up to {} elements, found {}", len, values.len()))
+            },
+            Field::Scalar(Value::String(v)) if ty == ValueType::String && v.to_str().len() > len => {
+                Err(format!("expected a string of up to {} bytes, found {}", len, v.to_str().len()))
+            },
+            Field::Scalar(value) => value.convert(ty).map(|v| cells[index][0] = v).map_err(|e| e.to_string()),
+            Field::Array(values) => values.iter().zip(cells[index].iter_mut())
+                .filter(|(v, _)| !matches!(v, Value::Null))
+                .try_for_each(|(v, cell)| v.convert(ty).map(|v| *cell = v))
+                .map_err(|e| e.to_string())
+        };
+        if let Err(e) = res {
+            errors.push((name.into(), e));
+        }
+    }
+    errors
+}
+
+/// Infers the columns of a table without any from the objects of an array node and appends one
+/// row per object.
+pub fn from_node(table: &mut Table, node: &Node) -> Result<TableImport, Error> {
+    let container = unsafe { &*table.container };
+    if table.in_transaction() {
+        return Err(Error::PendingTransaction);
+    }
+    if !table.inner.columns(&container.underlying).is_empty() {
+        return Err(Error::NotEmpty);
+    }
+    if writer::type_code(node) != CODE_ARRAY {
+        return Err(Error::NotAnArray);
+    }
+    let columns = infer(&node.children);
+    if columns.is_empty() {
+        return Err(Error::NoColumns);
+    }
+    table.schema += 1;
+    for column in &columns {
+        table.inner.columns_mut(&container.underlying).create(&column.name, column_type(column.ty.unwrap()), column.len())?;
+    }
+    container.touch();
+    table.inner.save(&container.underlying)?;
+    let mut import = TableImport { rows: 0, errors: Vec::new() };
+    let mut data = container.underlying.sections().load(table.inner.handle())?;
+    for (index, object) in node.children.iter().enumerate() {
+        if writer::type_code(object) != CODE_OBJECT {
+            import.errors.push(RowError::new(index, "", "row is not an object".into()));
+            continue;
+        }
+        let mut row = Row::new(table.alloc_row()?, table);
+        let errors = convert_row(object, &columns, row.cells_mut());
+        if !errors.is_empty() {
+            import.errors.extend(errors.into_iter().map(|(column, message)| RowError::new(index, &column, message)));
+            continue;
+        }
+        if let Err(e) = row.sync_write(table) {
+            import.errors.push(RowError::new(index, "", e.to_string()));
+            continue;
+        }
+        bpx::table::row::append(&mut *data, &row.inner)?;
+        import.rows += 1;
+    }
+    Ok(import)
+}
+
+/// Fills a table without any columns from the array stored under `key` in a BPXSD object.
+pub fn from_bpxsd(table: &mut Table, data: &[u8], key: &str, dictionary: Option<&Dictionary>) -> Result<TableImport, Error> {
+    let root = Reader::new(data, &ReaderOptions::default(), dictionary).read_root()?;
+    let node = root.children.iter()
+        .find(|v| match writer::unresolved_hash(v) {
+            Some(v) => v == hash(key),
+            None => v.name.to_str() == key
+        })
+        .ok_or_else(|| Error::KeyNotFound(key.into()))?;
+    from_node(table, node)
+}
+
+#[ffi_export]
+pub fn bpx_table_to_node(table: &Table, debug: bool) -> Option<repr_c::Box<Node>> {
+    unwrap_result(to_node(table, debug)).map(|v| Box::new(v).into())
+}
+
+#[ffi_export]
+pub fn bpx_table_to_bpxsd(table: &Table, key: char_p::Ref<'_>, debug: bool, mut buffer: c_slice::Mut<'_, u8>) -> isize {
+    match unwrap_result(to_bpxsd(table, key.to_str(), debug)) {
+        Some(data) => {
+            if let Some(out) = buffer.get_mut(..data.len()) {
+                out.copy_from_slice(&data);
+            }
+            data.len() as _
+        },
+        None => -1
+    }
+}
+
+#[ffi_export]
+pub fn bpx_table_from_node(table: &mut Table, node: &Node) -> Option<repr_c::Box<TableImport>> {
+    unwrap_result(from_node(table, node)).map(|v| Box::new(v).into())
+}
+
+#[ffi_export]
+pub fn bpx_table_from_bpxsd(table: &mut Table, buffer: c_slice::Ref<'_, u8>, key: char_p::Ref<'_>, dictionary: Option<&Dictionary>) -> Option<repr_c::Box<TableImport>> {
+    unwrap_result(from_bpxsd(table, buffer.as_slice(), key.to_str(), dictionary)).map(|v| Box::new(v).into())
+}
+
+#[ffi_export]
+pub fn bpx_table_import_get_row_count(import: &TableImport) -> u64 {
+    import.rows
+}
+
+#[ffi_export]
+pub fn bpx_table_import_get_errors(import: &TableImport) -> c_slice::Ref<'_, RowError> {
+    import.errors.as_slice().into()
+}
+
+#[ffi_export]
+pub fn bpx_table_import_free(import: repr_c::Box<TableImport>) {
+    drop(import);
+}
@@ -26,40 +26,115 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::cell::RefCell;
 use std::ffi::CString;
-use bpx::core::Handle;
-use bpx::table::column::Type;
+use std::io::{Seek, SeekFrom};
+use std::rc::{Rc, Weak};
+use bp3d_util::simple_error;
+use bpx::core::{AutoSectionData, Handle};
+use bpx::table::column::Column;
 use bpx::table::core::RawTable;
 use safer_ffi::prelude::*;
 use crate::common::Container;
 use crate::error::{set_last_error, unwrap_result};
-use crate::table::row::Row;
+use crate::table::index::{self, Index};
+use crate::table::row::{column_type, element_count, value_type, Row};
+use crate::table::transaction::{self, SchemaChange, Transaction};
 use crate::tree::model::ValueType;
 
+simple_error! {
+    pub Error {
+        SectionNotFound(u32) => "section not found ({})",
+        NoColumns => "the table has no columns",
+        ColumnNotFound(isize) => "no column at index {}",
+        EmptyColumnName => "column names cannot be empty",
+        TooManyColumns => "the table cannot have more columns",
+        (impl From) Io(std::io::Error) => "io error: {}",
+        (impl From) Bpx(bpx::core::error::Error) => "BPX error: {}",
+        (impl From) Table(bpx::table::error::Error) => "table error: {}"
+    }
+}
+
+fn check_section(container: &Container, handle: Handle) -> Result<(), Error> {
+    let raw = handle.into_raw();
+    match container.sections.iter().any(|v| v.handle == raw) {
+        true => Ok(()),
+        false => Err(Error::SectionNotFound(raw))
+    }
+}
+
 #[derive_ReprC]
 #[repr(opaque)]
 pub struct Table {
     pub(super) inner: RawTable,
     name: char_p::Box,
-    pub(super) container: *const Container
+    pub(super) container: *const Container,
+    pub(super) strict: bool,
+    pub(super) schema: u64,
+    pub(super) indexes: RefCell<Vec<Index>>,
+    pub(super) transaction: RefCell<Option<Transaction>>,
+    alive: Rc<()>
 }
 
 #[ffi_export]
 pub fn bpx_table_create(container: &mut Container, strings: u32, name: char_p::Ref<'_>) -> Option<repr_c::Box<Table>> {
     let strings = unsafe { Handle::from_raw(strings) };
+    unwrap_result(check_section(container, strings))?;
     let inner = unwrap_result(RawTable::create(&mut container.underlying, name.to_str(), strings))?;
     container.refresh();
-    Some(Box::new(Table { inner, name: name.to_owned(), container }).into())
+    Some(Box::new(Table { inner, name: name.to_owned(), container, strict: true, schema: 0, indexes: RefCell::new(Vec::new()), transaction: RefCell::new(None), alive: Rc::new(()) }).into())
+}
+
+impl Table {
+    pub(super) fn open(container: &Container, handle: Handle, strings: Handle) -> Result<Self, Error> {
+        check_section(container, handle)?;
+        check_section(container, strings)?;
+        // The header is read from the current position of the section.
+        container.underlying.sections().load(handle)?.seek(SeekFrom::Start(0))?;
+        let inner = RawTable::open(&container.underlying, handle, strings)?;
+        let name = CString::new(inner.load_name(&container.underlying)?).unwrap();
+        Ok(Table { inner, name: name.into(), container, strict: true, schema: 0, indexes: RefCell::new(Vec::new()), transaction: RefCell::new(None), alive: Rc::new(()) })
+    }
+
+    pub(super) fn name(&self) -> &str {
+        self.name.to_str()
+    }
+
+    /// Returns a token which stays upgradable for as long as this table is not freed; cursors and
+    /// queries keep it to detect a table freed before them.
+    pub(super) fn alive(&self) -> Weak<()> {
+        Rc::downgrade(&self.alive)
+    }
+
+    /// Allocates a row buffer, which is impossible while the table has no columns.
+    pub(super) fn alloc_row(&self) -> Result<bpx::table::row::Row, Error> {
+        match self.inner.get_row_size() {
+            0 => Err(Error::NoColumns),
+            _ => Ok(self.inner.alloc_row())
+        }
+    }
+
+    pub(super) fn in_transaction(&self) -> bool {
+        self.transaction.borrow().is_some()
+    }
+
+    /// Calls `f` with the staged copy of the table section when a transaction is open, and with
+    /// the section itself otherwise.
+    pub(super) fn with_data<T, E: From<bpx::core::error::Error>>(&self, f: impl FnOnce(&mut AutoSectionData) -> Result<T, E>) -> Result<T, E> {
+        if let Some(transaction) = self.transaction.borrow_mut().as_mut() {
+            return f(&mut transaction.data);
+        }
+        let container = unsafe { &*self.container };
+        let mut data = container.underlying.sections().load(self.inner.handle())?;
+        f(&mut data)
+    }
 }
 
 #[ffi_export]
 pub fn bpx_table_open(container: &Container, handle: u32, strings: u32) -> Option<repr_c::Box<Table>> {
     let handle = unsafe { Handle::from_raw(handle) };
     let strings = unsafe { Handle::from_raw(strings) };
-    let inner = unwrap_result(RawTable::open(&container.underlying, handle, strings))?;
-    let name = unwrap_result(inner.load_name(&container.underlying))?;
-    let name = CString::new(name).unwrap();
-    Some(Box::new(Table { inner, name: name.into(), container }).into())
+    unwrap_result(Table::open(container, handle, strings)).map(|v| Box::new(v).into())
 }
 
 #[ffi_export]
@@ -67,31 +142,64 @@ pub fn bpx_table_get_name(table: &Table) -> char_p::Ref<'_> {
     table.name.as_ref()
 }
 
+#[ffi_export]
+pub fn bpx_table_set_strict(table: &mut Table, strict: bool) {
+    table.strict = strict;
+}
+
+#[ffi_export]
+pub fn bpx_table_is_strict(table: &Table) -> bool {
+    table.strict
+}
+
 #[ffi_export]
 pub fn bpx_table_save(table: &mut Table) -> bool {
+    if let Some(transaction) = table.transaction.get_mut() {
+        transaction.changes.push(SchemaChange::Save);
+        return true;
+    }
     let container = unsafe { &*table.container };
+    container.touch();
     unwrap_result(table.inner.save(&container.underlying))
         .map(|()| true).unwrap_or(false)
 }
 
+/// Checks that a column named `name` can be added to a table which has `columns` columns.
+fn check_create(name: &str, columns: usize) -> Result<(), Error> {
+    if name.is_empty() {
+        return Err(Error::EmptyColumnName);
+    }
+    if columns >= u16::MAX as usize {
+        return Err(Error::TooManyColumns);
+    }
+    Ok(())
+}
+
+/// Checks that `index` names one of the `columns` columns of a table.
+fn check_remove(index: isize, columns: usize) -> Result<usize, Error> {
+    match usize::try_from(index) {
+        Ok(i) if i < columns => Ok(i),
+        _ => Err(Error::ColumnNotFound(index))
+    }
+}
+
 #[ffi_export]
 pub fn bpx_table_column_create(table: &mut Table, name: char_p::Ref<'_>, ty: ValueType, len: u16) -> isize {
     let container = unsafe { &*table.container };
-    let ty = match ty {
-        ValueType::Null => Type::Null,
-        ValueType::Int8 => Type::Int8,
-        ValueType::UInt8 => Type::Uint8,
-        ValueType::Int16 => Type::Int16,
-        ValueType::UInt16 => Type::Uint16,
-        ValueType::Int32 => Type::Int32,
-        ValueType::UInt32 => Type::Uint32,
-        ValueType::Int64 => Type::Int64,
-        ValueType::UInt64 => Type::Uint64,
-        ValueType::Float => Type::Float,
-        ValueType::Double => Type::Double,
-        ValueType::Boolean => Type::Boolean,
-        ValueType::String => Type::Varchar
-    };
+    let ty = column_type(ty);
+    let columns = table.inner.columns(&container.underlying).len();
+    if let Some(transaction) = table.transaction.get_mut() {
+        let index = transaction.column_count(columns);
+        if unwrap_result(check_create(name.to_str(), index)).is_none() {
+            return -1;
+        }
+        transaction.changes.push(SchemaChange::Create(name.to_str().into(), ty, len));
+        return index as _;
+    }
+    if unwrap_result(check_create(name.to_str(), columns)).is_none() {
+        return -1;
+    }
+    table.schema += 1;
     match unwrap_result(table.inner.columns_mut(&container.underlying).create(name.to_str(), ty, len)) {
         Some(index) => index as isize,
         None => -1
@@ -99,9 +207,26 @@ pub fn bpx_table_column_create(table: &mut Table, name: char_p::Ref<'_>, ty: Val
 }
 
 #[ffi_export]
-pub fn bpx_table_column_remove_at(table: &mut Table, index: isize) {
+pub fn bpx_table_column_remove_at(table: &mut Table, index: isize) -> bool {
     let container = unsafe { &*table.container };
-    table.inner.columns_mut(&container.underlying).remove_at(index as usize);
+    let columns = table.inner.columns(&container.underlying).len();
+    if let Some(transaction) = table.transaction.get_mut() {
+        return match unwrap_result(check_remove(index, transaction.column_count(columns))) {
+            Some(index) => {
+                transaction.changes.push(SchemaChange::Remove(index));
+                true
+            },
+            None => false
+        };
+    }
+    match unwrap_result(check_remove(index, columns)) {
+        Some(index) => {
+            table.schema += 1;
+            table.inner.columns_mut(&container.underlying).remove_at(index);
+            true
+        },
+        None => false
+    }
 }
 
 #[ffi_export]
@@ -121,52 +246,66 @@ pub fn bpx_table_handle(table: &Table) -> u32 {
 
 #[ffi_export]
 pub fn bpx_table_read(table: &Table, row: &mut Row, index: isize) -> bool {
+    unwrap_result(table.with_data(|data| bpx::table::row::read(data, &mut row.inner, index as _).map_err(Error::from))).is_some()
+        && unwrap_result(row.sync_read(table)).is_some()
+}
+
+fn write_row(table: &Table, row: &bpx::table::row::Row, index: Option<usize>) -> Option<usize> {
+    if table.in_transaction() {
+        return unwrap_result(transaction::write(table, row, index));
+    }
     let container = unsafe { &*table.container };
-    let data = unwrap_result(container.underlying.sections().load(table.inner.handle()));
-    match data {
-        None => false,
-        Some(mut value) => {
-            let res = unwrap_result(bpx::table::row::read(&mut *value, &mut row.inner, index as _)).map(|()| true).unwrap_or(false);
-            row.sync_read(table);
-            res
-        }
+    let target = match index {
+        Some(index) => index,
+        None => bpx::table::row::count(&*unwrap_result(container.underlying.sections().load(table.inner.handle()))?, row)
+    };
+    let pending = unwrap_result(index::prepare(table, || Ok(vec![(target, index::row_values(table, row)?)])))?;
+    container.touch();
+    let mut data = unwrap_result(container.underlying.sections().load(table.inner.handle()))?;
+    let res = match index {
+        Some(index) => unwrap_result(bpx::table::row::write(&mut *data, row, index)).map(|()| index),
+        None => unwrap_result(bpx::table::row::append(&mut *data, row))
+    };
+    drop(data);
+    if res.is_some() {
+        index::commit(table, pending);
     }
+    res
 }
 
 #[ffi_export]
 pub fn bpx_table_write(table: &Table, row: &mut Row, index: isize) -> bool {
-    row.sync_write(table);
-    let container = unsafe { &*table.container };
-    let data = unwrap_result(container.underlying.sections().load(table.inner.handle()));
-    match data {
-        None => false,
-        Some(mut value) => {
-            unwrap_result(bpx::table::row::write(&mut *value, &row.inner, index as _)).map(|()| true).unwrap_or(false)
-        }
+    if unwrap_result(row.sync_write(table)).is_none() {
+        return false;
     }
+    write_row(table, &row.inner, Some(index as _)).is_some()
 }
 
 #[ffi_export]
 pub fn bpx_table_append(table: &Table, row: &mut Row) -> isize {
-    row.sync_write(table);
-    let container = unsafe { &*table.container };
-    let data = unwrap_result(container.underlying.sections().load(table.inner.handle()));
-    match data {
-        None => -1,
-        Some(mut value) => {
-            unwrap_result(bpx::table::row::append(&mut *value, &row.inner)).map(|v| v as _).unwrap_or(-1)
-        }
+    if unwrap_result(row.sync_write(table)).is_none() {
+        return -1;
     }
+    write_row(table, &row.inner, None).map(|v| v as _).unwrap_or(-1)
 }
 
 #[ffi_export]
-pub fn bpx_table_get_row_count(table: &Table, row: &Row) -> isize {
-    let container = unsafe { &*table.container };
-    let data = unwrap_result(container.underlying.sections().load(table.inner.handle()));
-    match data {
-        None => -1,
-        Some(value) => bpx::table::row::count(&*value, &row.inner) as _
+pub fn bpx_table_delete(table: &Table, index: isize) -> bool {
+    let mut row = match unwrap_result(table.alloc_row()) {
+        Some(row) => row,
+        None => return false
+    };
+    if unwrap_result(table.with_data(|data| bpx::table::row::read(data, &mut row, index as _).map_err(Error::from))).is_none() {
+        return false;
     }
+    row.set_free(true);
+    write_row(table, &row, Some(index as _)).is_some()
+}
+
+#[ffi_export]
+pub fn bpx_table_get_row_count(table: &Table, row: &Row) -> isize {
+    unwrap_result(table.with_data(|data| Ok::<_, Error>(bpx::table::row::count(data, &row.inner))))
+        .map(|v| v as _).unwrap_or(-1)
 }
 
 #[ffi_export]
@@ -199,6 +338,34 @@ pub fn bpx_table_get_columns(table: &Table) -> usize {
     table.inner.columns(&container.underlying).len()
 }
 
+/// Calls `f` with the column at `index`, or sets the last error when there is none.
+fn with_column<T>(table: &Table, index: usize, out: &mut T, f: impl FnOnce(&Column) -> T) -> bool {
+    let container = unsafe { &*table.container };
+    let columns = table.inner.columns(&container.underlying);
+    match unwrap_result(columns.get(index).ok_or(Error::ColumnNotFound(index as _))) {
+        Some(column) => {
+            *out = f(column);
+            true
+        },
+        None => false
+    }
+}
+
+#[ffi_export]
+pub fn bpx_table_column_get_type(table: &Table, index: usize, out: &mut ValueType) -> bool {
+    with_column(table, index, out, |v| value_type(v.ty))
+}
+
+#[ffi_export]
+pub fn bpx_table_column_get_len(table: &Table, index: usize, out: &mut u16) -> bool {
+    with_column(table, index, out, |v| v.len)
+}
+
+#[ffi_export]
+pub fn bpx_table_column_get_element_count(table: &Table, index: usize, out: &mut usize) -> bool {
+    with_column(table, index, out, element_count)
+}
+
 #[ffi_export]
 pub fn bpx_table_destroy(table: repr_c::Box<Table>) {
     drop(table);
@@ -0,0 +1,311 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! A row cursor which caches the table layout and a window of rows.
+//!
+//! The cursor reads rows from the table section in windows of `WINDOW_ROWS` rows, so moving
+//! through neighbouring rows does not touch the section. It keeps the schema revision of its table
+//! and the revision of the container: a schema change invalidates the cursor for good, while a
+//! container change only drops the cached window and row count, which are reloaded on the next
+//! move. A cursor becomes invalid as well when the table section is removed or when its table is
+//! freed. A cursor cannot be created while its table has a pending transaction, since it reads
+//! the table section rather than the staged rows.
+//!
+//! Every element of a cell is decoded. The value of a column is its first element, or null when
+//! the cell holds none, as with a 0-length column.
+
+use std::io::{Read, Seek, SeekFrom};
+use std::rc::Weak;
+use bp3d_util::simple_error;
+use bpx::core::SectionData;
+use bpx::table::column::{Type, SIZE_COLUMN_STRUCTURE};
+use bpx::table::header::SIZE_HEADER_STRUCTURE;
+use safer_ffi::prelude::*;
+use crate::common::Container;
+use crate::error::unwrap_result;
+use crate::table::core::Table;
+use crate::table::row::{element_count, first_element, read_elements, CellError};
+use crate::tree::model::Value;
+
+simple_error! {
+    pub Error {
+        Invalidated => "cursor was invalidated by a schema or container change",
+        TableFreed => "the table of the cursor was freed",
+        PendingTransaction => "table has a pending transaction",
+        RowIndexOutOfBounds(usize) => "row index out of bounds ({})",
+        InvalidCell(CellError) => "{}",
+        (impl From) Bpx(bpx::core::error::Error) => "BPX error: {}",
+        (impl From) Io(std::io::Error) => "io error: {}"
+    }
+}
+
+const WINDOW_ROWS: usize = 256;
+
+struct CellLayout {
+    ty: Type,
+    count: usize,
+    offset: usize,
+    size: usize
+}
+
+#[derive_ReprC]
+#[repr(opaque)]
+pub struct Cursor {
+    table: *const Table,
+    alive: Weak<()>,
+    schema: u64,
+    revision: u64,
+    valid: bool,
+    cells: Vec<CellLayout>,
+    header_size: usize,
+    row_size: usize,
+    actual_row_size: usize,
+    count: usize,
+    window: Vec<u8>,
+    window_start: usize,
+    position: isize,
+    elements: Box<[Box<[Value]>]>,
+    values: Box<[Value]>,
+    free: bool
+}
+
+impl Cursor {
+    pub(super) fn new(table: &Table) -> Result<Self, Error> {
+        if table.in_transaction() {
+            return Err(Error::PendingTransaction);
+        }
+        let container = unsafe { &*table.container };
+        let columns = table.inner.columns(&container.underlying);
+        let mut offset = 0;
+        let cells: Vec<CellLayout> = columns.iter().map(|v| {
+            let cell = CellLayout { ty: v.ty, count: element_count(v), offset, size: v.get_size() };
+            offset += cell.size;
+            cell
+        }).collect();
+        let mut cursor = Self {
+            table,
+            alive: table.alive(),
+            schema: table.schema,
+            revision: container.revision(),
+            valid: true,
+            header_size: SIZE_HEADER_STRUCTURE + cells.len() * SIZE_COLUMN_STRUCTURE,
+            row_size: table.inner.get_row_size(),
+            actual_row_size: table.inner.get_actual_row_size(),
+            elements: vec![Box::default(); cells.len()].into_boxed_slice(),
+            values: vec![Value::Null; cells.len()].into_boxed_slice(),
+            cells,
+            count: 0,
+            window: Vec::new(),
+            window_start: 0,
+            position: -1,
+            free: false
+        };
+        cursor.count = cursor.load_count()?;
+        Ok(cursor)
+    }
+
+    fn table(&self) -> &Table {
+        unsafe { &*self.table }
+    }
+
+    fn container(&self) -> &Container {
+        unsafe { &*self.table().container }
+    }
+
+    fn load_count(&self) -> Result<usize, Error> {
+        let data = self.container().underlying.sections().load(self.table().inner.handle())?;
+        Ok(data.size().saturating_sub(self.header_size) / self.actual_row_size)
+    }
+
+    fn check(&mut self) -> Result<(), Error> {
+        if self.alive.strong_count() == 0 {
+            self.valid = false;
+            return Err(Error::TableFreed);
+        }
+        if self.valid && self.schema != self.table().schema {
+            self.valid = false;
+        }
+        if self.valid && self.revision != self.container().revision() {
+            let handle = self.table().inner.handle().into_raw();
+            self.valid = self.container().sections.iter().any(|v| v.handle == handle);
+            if self.valid {
+                self.window.clear();
+                self.count = self.load_count()?;
+                self.revision = self.container().revision();
+            }
+        }
+        match self.valid {
+            true => Ok(()),
+            false => Err(Error::Invalidated)
+        }
+    }
+
+    fn load_window(&mut self, row: usize) -> Result<(), Error> {
+        let start = row - row % WINDOW_ROWS;
+        let len = WINDOW_ROWS.min(self.count - start) * self.actual_row_size;
+        let container = unsafe { &*self.table().container };
+        let mut data = container.underlying.sections().load(self.table().inner.handle())?;
+        self.window.resize(len, 0);
+        data.seek(SeekFrom::Start((self.header_size + start * self.actual_row_size) as _))?;
+        data.read_exact(&mut self.window)?;
+        self.window_start = start;
+        Ok(())
+    }
+
+    pub(super) fn read(&mut self, row: usize) -> Result<(), Error> {
+        self.check()?;
+        if row >= self.count {
+            return Err(Error::RowIndexOutOfBounds(row));
+        }
+        let cached = self.window.len() / self.actual_row_size;
+        if row < self.window_start || row >= self.window_start + cached {
+            self.load_window(row)?;
+        }
+        let offset = (row - self.window_start) * self.actual_row_size;
+        let data = &self.window[offset..offset + self.actual_row_size];
+        for (index, cell) in self.cells.iter().enumerate() {
+            self.elements[index] = read_elements(cell.ty, cell.count, &data[cell.offset..cell.offset + cell.size])
+                .map_err(|e| Error::InvalidCell(CellError::new(index, e)))?;
+            self.values[index] = first_element(&self.elements[index]);
+        }
+        self.free = data[self.row_size] == 1;
+        self.position = row as _;
+        Ok(())
+    }
+
+    pub(super) fn next(&mut self) -> Result<bool, Error> {
+        self.check()?;
+        let row = (self.position + 1) as usize;
+        if row >= self.count {
+            self.position = self.count as _;
+            return Ok(false);
+        }
+        self.read(row).map(|()| true)
+    }
+
+    fn prev(&mut self) -> Result<bool, Error> {
+        self.check()?;
+        let row = self.position.min(self.count as _) - 1;
+        if row < 0 {
+            self.position = -1;
+            return Ok(false);
+        }
+        self.read(row as _).map(|()| true)
+    }
+
+    fn has_row(&self) -> bool {
+        self.position >= 0 && (self.position as usize) < self.count
+    }
+
+    pub(super) fn position(&self) -> usize {
+        self.position as _
+    }
+
+    /// Returns the value of every column for the current row, that is the first element of
+    /// array columns and null for cells without elements.
+    pub(super) fn values(&self) -> &[Value] {
+        &self.values
+    }
+
+    pub(super) fn elements(&self) -> &[Box<[Value]>] {
+        &self.elements
+    }
+
+    pub(super) fn is_free(&self) -> bool {
+        self.free
+    }
+}
+
+#[ffi_export]
+pub fn bpx_table_cursor_create(table: &Table) -> Option<repr_c::Box<Cursor>> {
+    unwrap_result(Cursor::new(table)).map(|v| Box::new(v).into())
+}
+
+#[ffi_export]
+pub fn bpx_table_cursor_next(cursor: &mut Cursor) -> bool {
+    unwrap_result(cursor.next()).unwrap_or(false)
+}
+
+#[ffi_export]
+pub fn bpx_table_cursor_prev(cursor: &mut Cursor) -> bool {
+    unwrap_result(cursor.prev()).unwrap_or(false)
+}
+
+#[ffi_export]
+pub fn bpx_table_cursor_seek(cursor: &mut Cursor, row: usize) -> bool {
+    unwrap_result(cursor.read(row)).is_some()
+}
+
+#[ffi_export]
+pub fn bpx_table_cursor_get_position(cursor: &Cursor) -> isize {
+    cursor.position
+}
+
+#[ffi_export]
+pub fn bpx_table_cursor_get_row_count(cursor: &mut Cursor) -> isize {
+    unwrap_result(cursor.check()).map(|()| cursor.count as _).unwrap_or(-1)
+}
+
+#[ffi_export]
+pub fn bpx_table_cursor_is_valid(cursor: &mut Cursor) -> bool {
+    cursor.check().is_ok()
+}
+
+#[ffi_export]
+pub fn bpx_table_cursor_is_free(cursor: &Cursor) -> bool {
+    cursor.has_row() && cursor.free
+}
+
+#[ffi_export]
+pub fn bpx_table_cursor_get_value(cursor: &Cursor, index: usize) -> Option<&Value> {
+    match cursor.has_row() {
+        true => cursor.values.get(index),
+        false => None
+    }
+}
+
+#[ffi_export]
+pub fn bpx_table_cursor_get_element_count(cursor: &Cursor, index: usize) -> usize {
+    match cursor.has_row() {
+        true => cursor.elements.get(index).map(|v| v.len()).unwrap_or(0),
+        false => 0
+    }
+}
+
+#[ffi_export]
+pub fn bpx_table_cursor_get_element(cursor: &Cursor, index: usize, element: usize) -> Option<&Value> {
+    match cursor.has_row() {
+        true => cursor.elements.get(index).and_then(|v| v.get(element)),
+        false => None
+    }
+}
+
+#[ffi_export]
+pub fn bpx_table_cursor_free(cursor: repr_c::Box<Cursor>) {
+    drop(cursor);
+}
@@ -0,0 +1,508 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! In-memory secondary indexes over table columns.
+//!
+//! An index maps the values of one or more columns to the rows holding them; free rows are never
+//! indexed. Hash indexes only answer exact lookups, B-tree indexes order their keys with
+//! `Value::cmp_value`, like the query predicates, and answer range lookups on a key prefix as
+//! well.
+//!
+//! Indexes belong to a table handle and are built when created. Rows written, appended or deleted
+//! through that handle update them in place, while any other change to the container marks them
+//! out of date, in which case they are rebuilt on their next use. Columns are tracked by name so
+//! that an index survives schema changes which keep its columns.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Bound;
+use bp3d_util::simple_error;
+use bpx::table::row::Row as RawRow;
+use safer_ffi::prelude::*;
+use crate::error::unwrap_result;
+use crate::table::core::Table;
+use crate::table::cursor::{self, Cursor};
+use crate::table::query::{self, column_names, CompareOp, Predicate, ResultSet};
+use crate::table::row::{element_count, first_element, read_elements, value_type, CellError};
+use crate::tree::model::{Value, ValueType};
+
+simple_error! {
+    pub Error {
+        NoColumns => "an index needs at least one column",
+        ColumnOutOfRange(usize) => "column index out of range ({})",
+        ColumnNotFound(String) => "indexed column not found ({})",
+        IndexOutOfRange(usize) => "index out of range ({})",
+        KeyTooLong(usize) => "key has more values than the index has columns ({})",
+        KeyLength(usize) => "expected a key of {} values",
+        NotOrdered => "range lookups need a B-tree index",
+        Duplicate(String) => "duplicate key in unique index ({})",
+        InvalidCell(CellError) => "{}",
+        (impl From) Cursor(cursor::Error) => "cursor error: {}",
+        (impl From) Query(query::Error) => "query error: {}"
+    }
+}
+
+#[derive_ReprC]
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum IndexKind {
+    Hash = 0,
+    BTree
+}
+
+struct OrderedKey(Box<[Value]>);
+
+impl Ord for OrderedKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_prefix(&self.0, &other.0).then(self.0.len().cmp(&other.0.len()))
+    }
+}
+
+impl PartialOrd for OrderedKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for OrderedKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OrderedKey {}
+
+fn compare_prefix(a: &[Value], b: &[Value]) -> Ordering {
+    a.iter().zip(b.iter()).fold(Ordering::Equal, |order, (a, b)| order.then_with(|| a.cmp_value(b)))
+}
+
+/// Returns the key a hash map stores: zeros and NaNs are folded into a single value each, as
+/// `Value::cmp_value` makes `-0.0` equal to `0.0` and every NaN equal to any other NaN.
+fn hash_key(key: &[Value]) -> Box<[Value]> {
+    key.iter().map(|v| match v {
+        Value::Float(v) if v.is_nan() => Value::Float(f32::NAN),
+        Value::Float(v) if *v == 0.0 => Value::Float(0.0),
+        Value::Double(v) if v.is_nan() => Value::Double(f64::NAN),
+        Value::Double(v) if *v == 0.0 => Value::Double(0.0),
+        v => v.clone()
+    }).collect()
+}
+
+enum Map {
+    Hash(HashMap<Box<[Value]>, Vec<usize>>),
+    BTree(BTreeMap<OrderedKey, Vec<usize>>)
+}
+
+impl Map {
+    fn new(kind: IndexKind) -> Self {
+        match kind {
+            IndexKind::Hash => Map::Hash(HashMap::new()),
+            IndexKind::BTree => Map::BTree(BTreeMap::new())
+        }
+    }
+
+    fn get(&self, key: &[Value]) -> Option<&Vec<usize>> {
+        match self {
+            Map::Hash(map) => map.get(&hash_key(key)),
+            Map::BTree(map) => map.get(&OrderedKey(key.into()))
+        }
+    }
+
+    fn entry(&mut self, key: Box<[Value]>) -> &mut Vec<usize> {
+        match self {
+            Map::Hash(map) => map.entry(hash_key(&key)).or_default(),
+            Map::BTree(map) => map.entry(OrderedKey(key)).or_default()
+        }
+    }
+
+    fn remove(&mut self, key: &[Value], row: usize) {
+        let list = match self {
+            Map::Hash(map) => map.get_mut(&hash_key(key)),
+            Map::BTree(map) => map.get_mut(&OrderedKey(key.into()))
+        };
+        let empty = match list {
+            Some(list) => {
+                list.retain(|v| *v != row);
+                list.is_empty()
+            },
+            None => false
+        };
+        if empty {
+            match self {
+                Map::Hash(map) => map.remove(&hash_key(key)).map(|_| ()),
+                Map::BTree(map) => map.remove(&OrderedKey(key.into())).map(|_| ())
+            };
+        }
+    }
+}
+
+fn describe(key: &[Value]) -> String {
+    key.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+/// The row index and the values of a row being written, or `None` when the row is free.
+pub type Change = (usize, Option<Box<[Value]>>);
+
+pub struct Index {
+    names: Vec<String>,
+    columns: Vec<usize>,
+    types: Vec<ValueType>,
+    kind: IndexKind,
+    unique: bool,
+    schema: u64,
+    revision: u64,
+    map: Map,
+    keys: Vec<Option<Box<[Value]>>>
+}
+
+impl Index {
+    fn new(table: &Table, columns: &[usize], kind: IndexKind, unique: bool) -> Result<Self, Error> {
+        if columns.is_empty() {
+            return Err(Error::NoColumns);
+        }
+        let names = column_names(table)?;
+        let names = columns.iter()
+            .map(|v| names.get(*v).cloned().ok_or(Error::ColumnOutOfRange(*v)))
+            .collect::<Result<_, _>>()?;
+        let mut index = Index {
+            names,
+            columns: Vec::new(),
+            types: Vec::new(),
+            kind,
+            unique,
+            schema: 0,
+            revision: 0,
+            map: Map::new(kind),
+            keys: Vec::new()
+        };
+        index.build(table)?;
+        Ok(index)
+    }
+
+    fn is_fresh(&self, table: &Table) -> bool {
+        let container = unsafe { &*table.container };
+        self.schema == table.schema && self.revision == container.revision()
+    }
+
+    fn build(&mut self, table: &Table) -> Result<(), Error> {
+        let container = unsafe { &*table.container };
+        let names = column_names(table)?;
+        self.columns = self.names.iter()
+            .map(|name| names.iter().position(|v| v == name).ok_or_else(|| Error::ColumnNotFound(name.clone())))
+            .collect::<Result<_, _>>()?;
+        let columns = table.inner.columns(&container.underlying);
+        self.types = self.columns.iter().map(|v| value_type(columns[*v].ty)).collect();
+        self.map = Map::new(self.kind);
+        self.keys.clear();
+        let mut cursor = Cursor::new(table)?;
+        while cursor.next()? {
+            if cursor.is_free() {
+                continue;
+            }
+            let key = self.key(cursor.values());
+            if self.insert(cursor.position(), key.clone()) && self.unique {
+                return Err(Error::Duplicate(describe(&key)));
+            }
+        }
+        self.schema = table.schema;
+        self.revision = container.revision();
+        Ok(())
+    }
+
+    fn refresh(&mut self, table: &Table) -> Result<(), Error> {
+        match self.is_fresh(table) {
+            true => Ok(()),
+            false => self.build(table)
+        }
+    }
+
+    fn key(&self, values: &[Value]) -> Box<[Value]> {
+        self.columns.iter().map(|v| values[*v].clone()).collect()
+    }
+
+    /// Adds a row under the given key and returns true when another row already used the key.
+    fn insert(&mut self, row: usize, key: Box<[Value]>) -> bool {
+        if self.keys.len() <= row {
+            self.keys.resize(row + 1, None);
+        }
+        self.keys[row] = Some(key.clone());
+        let list = self.map.entry(key);
+        let taken = !list.is_empty();
+        if let Err(pos) = list.binary_search(&row) {
+            list.insert(pos, row);
+        }
+        taken
+    }
+
+    fn remove(&mut self, row: usize) {
+        if let Some(key) = self.keys.get_mut(row).and_then(|v| v.take()) {
+            self.map.remove(&key, row);
+        }
+    }
+
+    fn check(&self, changes: &[Change]) -> Result<(), Error> {
+        if !self.unique {
+            return Ok(());
+        }
+        let rows: HashSet<usize> = changes.iter().map(|v| v.0).collect();
+        let mut seen = Map::new(self.kind);
+        for values in changes.iter().filter_map(|v| v.1.as_ref()) {
+            let key = self.key(values);
+            let taken = self.map.get(&key).is_some_and(|list| list.iter().any(|v| !rows.contains(v)));
+            let list = seen.entry(key.clone());
+            if taken || !list.is_empty() {
+                return Err(Error::Duplicate(describe(&key)));
+            }
+            list.push(0);
+        }
+        Ok(())
+    }
+
+    fn apply(&mut self, changes: &[Change]) {
+        for (row, values) in changes {
+            self.remove(*row);
+            if let Some(values) = values {
+                let key = self.key(values);
+                self.insert(*row, key);
+            }
+        }
+    }
+
+    fn lookup(&self, key: &[Value]) -> Result<Vec<usize>, Error> {
+        if key.len() != self.columns.len() {
+            return Err(Error::KeyLength(self.columns.len()));
+        }
+        let key: Box<[Value]> = match self.kind {
+            // Hash keys only match values of the column type, which the key is converted to; a
+            // value which does not convert exactly cannot be equal to any cell.
+            IndexKind::Hash => match key.iter().zip(self.types.iter()).map(|(v, ty)| v.convert(*ty)).collect() {
+                Ok(key) => key,
+                Err(_) => return Ok(Vec::new())
+            },
+            IndexKind::BTree => key.into()
+        };
+        Ok(self.map.get(&key).cloned().unwrap_or_default())
+    }
+
+    fn lookup_range(&self, low: &[Value], high: &[Value]) -> Result<Vec<usize>, Error> {
+        let len = low.len().max(high.len());
+        if len > self.columns.len() {
+            return Err(Error::KeyTooLong(len));
+        }
+        let map = match &self.map {
+            Map::BTree(map) => map,
+            Map::Hash(_) => return Err(Error::NotOrdered)
+        };
+        let start = match low.is_empty() {
+            true => Bound::Unbounded,
+            false => Bound::Included(OrderedKey(low.into()))
+        };
+        let mut rows: Vec<usize> = map.range((start, Bound::Unbounded))
+            .take_while(|(key, _)| high.is_empty() || compare_prefix(&key.0, high) != Ordering::Greater)
+            .flat_map(|(_, list)| list.iter().copied())
+            .collect();
+        rows.sort_unstable();
+        Ok(rows)
+    }
+
+    /// Returns the rows which may match the filter, when this index can narrow it down.
+    fn candidates(&self, filter: &[Predicate]) -> Option<Vec<usize>> {
+        let equal: Vec<Value> = self.columns.iter()
+            .map_while(|column| filter.iter().find_map(|v| match v {
+                Predicate::Compare(c, CompareOp::Eq, value) if c == column => Some(value.clone()),
+                _ => None
+            }))
+            .collect();
+        match self.kind {
+            IndexKind::Hash if equal.len() == self.columns.len() => self.lookup(&equal).ok(),
+            IndexKind::Hash => None,
+            IndexKind::BTree if !equal.is_empty() => self.lookup_range(&equal, &equal).ok(),
+            IndexKind::BTree => {
+                let column = self.columns[0];
+                let (mut low, mut high) = (Vec::new(), Vec::new());
+                for predicate in filter {
+                    match predicate {
+                        Predicate::Compare(c, CompareOp::Gt | CompareOp::Ge, v) if *c == column => low = vec![v.clone()],
+                        Predicate::Compare(c, CompareOp::Lt | CompareOp::Le, v) if *c == column => high = vec![v.clone()],
+                        Predicate::Range(c, a, b) if *c == column => {
+                            low = vec![a.clone()];
+                            high = vec![b.clone()];
+                        },
+                        _ => ()
+                    }
+                }
+                match low.is_empty() && high.is_empty() {
+                    true => None,
+                    false => self.lookup_range(&low, &high).ok()
+                }
+            }
+        }
+    }
+}
+
+/// Returns the values of a row about to be written, or `None` when the row is free.
+pub fn row_values(table: &Table, row: &RawRow) -> Result<Option<Box<[Value]>>, Error> {
+    if row.is_free() {
+        return Ok(None);
+    }
+    let container = unsafe { &*table.container };
+    let columns = table.inner.columns(&container.underlying);
+    let mut values = Vec::with_capacity(columns.len());
+    for (index, column) in columns.iter().enumerate() {
+        let pos = table.inner.get_column_pos_at(index).unwrap();
+        let elements = read_elements(column.ty, element_count(column), row.cell(pos).as_bytes())
+            .map_err(|e| Error::InvalidCell(CellError::new(index, e)))?;
+        values.push(first_element(&elements));
+    }
+    Ok(Some(values.into()))
+}
+
+/// Decodes a row laid out as in the table section, or returns `None` when the row is free.
+pub fn decode_row(table: &Table, data: &[u8]) -> Result<Option<Box<[Value]>>, Error> {
+    if data[table.inner.get_row_size()] == 1 {
+        return Ok(None);
+    }
+    let container = unsafe { &*table.container };
+    let mut offset = 0;
+    let mut values = Vec::new();
+    for (index, column) in table.inner.columns(&container.underlying).iter().enumerate() {
+        let size = column.get_size();
+        let elements = read_elements(column.ty, element_count(column), &data[offset..offset + size])
+            .map_err(|e| Error::InvalidCell(CellError::new(index, e)))?;
+        values.push(first_element(&elements));
+        offset += size;
+    }
+    Ok(Some(values.into()))
+}
+
+pub struct Pending {
+    revision: u64,
+    changes: Vec<Change>
+}
+
+/// Brings every index of the table up to date and checks the changes against the unique ones.
+/// Returns `None` when the table has no index, in which case `changes` is never called.
+pub fn prepare(table: &Table, changes: impl FnOnce() -> Result<Vec<Change>, Error>) -> Result<Option<Pending>, Error> {
+    let mut indexes = table.indexes.borrow_mut();
+    if indexes.is_empty() {
+        return Ok(None);
+    }
+    let changes = changes()?;
+    for index in indexes.iter_mut() {
+        index.refresh(table)?;
+        index.check(&changes)?;
+    }
+    let container = unsafe { &*table.container };
+    Ok(Some(Pending { revision: container.revision(), changes }))
+}
+
+/// Applies prepared changes once they were written, right after the container was touched.
+pub fn commit(table: &Table, pending: Option<Pending>) {
+    let pending = match pending {
+        Some(v) => v,
+        None => return
+    };
+    let container = unsafe { &*table.container };
+    for index in table.indexes.borrow_mut().iter_mut() {
+        if index.revision == pending.revision && index.schema == table.schema {
+            index.apply(&pending.changes);
+            index.revision = container.revision();
+        }
+    }
+}
+
+/// Returns the rows which may match the filter according to the first index able to narrow it
+/// down, in ascending order.
+pub fn candidates(table: &Table, filter: &[Predicate]) -> Option<Vec<usize>> {
+    table.indexes.borrow_mut().iter_mut()
+        .filter_map(|index| index.refresh(table).ok().and_then(|()| index.candidates(filter)))
+        .next()
+}
+
+fn fetch(table: &Table, rows: Vec<usize>) -> Result<ResultSet, Error> {
+    let names = column_names(table)?;
+    let mut cursor = Cursor::new(table)?;
+    let mut values = Vec::with_capacity(rows.len());
+    for row in &rows {
+        cursor.read(*row)?;
+        values.push(cursor.values().into());
+    }
+    Ok(ResultSet {
+        names: names.into_iter().map(|v| std::ffi::CString::new(v).unwrap().into()).collect(),
+        indices: rows.into_iter().map(|v| v as u64).collect(),
+        rows: values
+    })
+}
+
+fn with_index<T>(table: &Table, index: usize, f: impl FnOnce(&Index) -> Result<T, Error>) -> Result<T, Error> {
+    let mut indexes = table.indexes.borrow_mut();
+    let index = indexes.get_mut(index).ok_or(Error::IndexOutOfRange(index))?;
+    index.refresh(table)?;
+    f(index)
+}
+
+#[ffi_export]
+pub fn bpx_table_index_create(table: &mut Table, columns: c_slice::Ref<'_, usize>, kind: IndexKind, unique: bool) -> isize {
+    match unwrap_result(Index::new(table, columns.as_slice(), kind, unique)) {
+        Some(index) => {
+            let indexes = table.indexes.get_mut();
+            indexes.push(index);
+            (indexes.len() - 1) as _
+        },
+        None => -1
+    }
+}
+
+#[ffi_export]
+pub fn bpx_table_index_remove_at(table: &mut Table, index: usize) {
+    let indexes = table.indexes.get_mut();
+    if index < indexes.len() {
+        indexes.remove(index);
+    }
+}
+
+#[ffi_export]
+pub fn bpx_table_get_indexes(table: &Table) -> usize {
+    table.indexes.borrow().len()
+}
+
+#[ffi_export]
+pub fn bpx_table_index_lookup(table: &Table, index: usize, key: c_slice::Ref<'_, &Value>) -> Option<repr_c::Box<ResultSet>> {
+    let key: Vec<Value> = key.as_slice().iter().map(|v| (*v).clone()).collect();
+    let rows = unwrap_result(with_index(table, index, |v| v.lookup(&key)))?;
+    unwrap_result(fetch(table, rows)).map(|v| Box::new(v).into())
+}
+
+#[ffi_export]
+pub fn bpx_table_index_lookup_range(table: &Table, index: usize, low: c_slice::Ref<'_, &Value>,
+                                    high: c_slice::Ref<'_, &Value>) -> Option<repr_c::Box<ResultSet>> {
+    let low: Vec<Value> = low.as_slice().iter().map(|v| (*v).clone()).collect();
+    let high: Vec<Value> = high.as_slice().iter().map(|v| (*v).clone()).collect();
+    let rows = unwrap_result(with_index(table, index, |v| v.lookup_range(&low, &high)))?;
+    unwrap_result(fetch(table, rows)).map(|v| Box::new(v).into())
+}
@@ -0,0 +1,77 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::io::{Seek, SeekFrom};
+use bpx::core::Handle;
+use bpx::core::header::Struct;
+use bpx::table::column::{Column, SIZE_COLUMN_STRUCTURE};
+use bpx::table::core::RawTable;
+use bpx::table::header::{Header, SIZE_HEADER_STRUCTURE};
+use crate::common::Container;
+
+pub struct Layout {
+    pub header: Header,
+    pub columns: Vec<Column>
+}
+
+impl Layout {
+    pub fn read(mut data: &[u8]) -> Result<Self, bpx::table::error::Error> {
+        let header = Header::read(&mut data)?;
+        let mut columns = Vec::with_capacity(header.columns as _);
+        for _ in 0..header.columns {
+            columns.push(Column::read(&mut data)?);
+        }
+        Ok(Self { header, columns })
+    }
+
+    pub fn size(&self) -> usize {
+        SIZE_HEADER_STRUCTURE + self.columns.len() * SIZE_COLUMN_STRUCTURE
+    }
+
+    pub fn is_compatible(&self, other: &Layout) -> bool {
+        self.columns.len() == other.columns.len()
+            && self.columns.iter().zip(other.columns.iter()).all(|(a, b)| a.ty == b.ty && a.len == b.len)
+    }
+
+    pub fn write(&self, out: &mut [u8]) {
+        out[..SIZE_HEADER_STRUCTURE].copy_from_slice(&self.header.to_bytes());
+        for (index, column) in self.columns.iter().enumerate() {
+            let offset = SIZE_HEADER_STRUCTURE + index * SIZE_COLUMN_STRUCTURE;
+            out[offset..offset + SIZE_COLUMN_STRUCTURE].copy_from_slice(&column.to_bytes());
+        }
+    }
+}
+
+/// Opens the table stored in the given section so that row sizes are computed by bpx itself. The
+/// strings section is never read to compute sizes, so the table is opened without one.
+pub fn open_raw(container: &Container, handle: Handle) -> Result<RawTable, bpx::table::error::Error> {
+    container.underlying.sections().load(handle)?.seek(SeekFrom::Start(0))?;
+    let table = RawTable::open(&container.underlying, handle, handle)?;
+    container.underlying.sections().load(handle)?.seek(SeekFrom::Start(0))?;
+    Ok(table)
+}
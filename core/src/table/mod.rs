@@ -27,4 +27,16 @@
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 mod core;
-mod row;
+pub mod row;
+pub mod layout;
+pub mod batch;
+pub mod cursor;
+pub mod query;
+pub mod index;
+pub mod profile;
+pub mod convert;
+pub mod validate;
+pub mod transaction;
+pub mod sql;
+
+pub use self::core::Error;
@@ -0,0 +1,311 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Per-column statistics over the rows of a table.
+//!
+//! Free rows are counted but otherwise skipped. Every element of an array column counts as one
+//! value of the column. Distinct values are counted exactly, along with the occurrences of every
+//! value, until a column holds more than `exact_limit` distinct values. Past that point the
+//! distinct count is estimated from the smallest hashes seen (a k minimum values sketch) and the
+//! most common values are tracked by a space-saving summary, which reports for each value the
+//! number of occurrences it is guaranteed to have; `exact` is false for such columns.
+//!
+//! The minimum and maximum of a column, and the order of most common values with the same count,
+//! follow `Value::cmp_value`. The mean is only computed for numeric columns.
+
+use std::collections::{BTreeSet, HashMap};
+use std::ffi::CString;
+use bp3d_util::simple_error;
+use safer_ffi::prelude::*;
+use crate::error::unwrap_result;
+use crate::table::core::Table;
+use crate::table::cursor::{self, Cursor};
+use crate::table::query::{self, column_names};
+use crate::table::row::value_type;
+use crate::tree::cmp::stable_hash;
+use crate::tree::model::{Value, ValueType};
+
+simple_error! {
+    pub Error {
+        (impl From) Cursor(cursor::Error) => "cursor error: {}",
+        (impl From) Query(query::Error) => "query error: {}"
+    }
+}
+
+const SKETCH_SIZE: usize = 1024;
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct ProfileOptions {
+    pub top_values: u32,
+    pub exact_limit: u32
+}
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct ValueCount {
+    pub value: repr_c::Box<Value>,
+    pub count: u64
+}
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct ColumnProfile {
+    pub name: char_p::Box,
+    pub ty: ValueType,
+    pub len: u16,
+    pub count: u64,
+    pub min: Option<repr_c::Box<Value>>,
+    pub max: Option<repr_c::Box<Value>>,
+    pub mean: Option<repr_c::Box<Value>>,
+    pub distinct: u64,
+    pub exact: bool,
+    pub empty_strings: u64,
+    pub top: c_slice::Box<ValueCount>
+}
+
+#[derive_ReprC]
+#[repr(opaque)]
+pub struct Profile {
+    rows: u64,
+    free_rows: u64,
+    columns: Vec<ColumnProfile>
+}
+
+fn mix(mut v: u64) -> u64 {
+    // The splitmix64 finalizer, which spreads the FNV hash over all bits for the sketch.
+    v = (v ^ (v >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    v = (v ^ (v >> 27)).wrapping_mul(0x94D049BB133111EB);
+    v ^ (v >> 31)
+}
+
+enum Counter {
+    Exact(HashMap<Value, u64>),
+    Estimate {
+        sketch: BTreeSet<u64>,
+        summary: HashMap<Value, (u64, u64)>
+    }
+}
+
+struct Stats {
+    count: u64,
+    min: Option<Value>,
+    max: Option<Value>,
+    sum: f64,
+    numeric: bool,
+    empty_strings: u64,
+    counter: Counter
+}
+
+impl Stats {
+    fn new(ty: ValueType) -> Self {
+        Self {
+            count: 0,
+            min: None,
+            max: None,
+            sum: 0.0,
+            numeric: !matches!(ty, ValueType::Null | ValueType::Boolean | ValueType::String),
+            empty_strings: 0,
+            counter: Counter::Exact(HashMap::new())
+        }
+    }
+
+    fn sketch(sketch: &mut BTreeSet<u64>, value: &Value) {
+        let hash = mix(stable_hash(value));
+        if sketch.len() < SKETCH_SIZE || sketch.last().is_some_and(|v| hash < *v) {
+            sketch.insert(hash);
+            if sketch.len() > SKETCH_SIZE {
+                sketch.pop_last();
+            }
+        }
+    }
+
+    fn add(&mut self, value: &Value, options: &ProfileOptions) {
+        self.count += 1;
+        if self.min.as_ref().is_none_or(|v| value.cmp_value(v).is_lt()) {
+            self.min = Some(value.clone());
+        }
+        if self.max.as_ref().is_none_or(|v| value.cmp_value(v).is_gt()) {
+            self.max = Some(value.clone());
+        }
+        if self.numeric {
+            self.sum += value.as_f64();
+        }
+        if matches!(value, Value::String(v) if v.to_str().is_empty()) {
+            self.empty_strings += 1;
+        }
+        match &mut self.counter {
+            Counter::Exact(counts) => {
+                *counts.entry(value.clone()).or_default() += 1;
+                if counts.len() > options.exact_limit as usize {
+                    self.estimate(options);
+                }
+            },
+            Counter::Estimate { sketch, summary } => {
+                Self::sketch(sketch, value);
+                let capacity = summary_capacity(options);
+                if let Some((count, _)) = summary.get_mut(value) {
+                    *count += 1;
+                } else if summary.len() < capacity {
+                    summary.insert(value.clone(), (1, 0));
+                } else {
+                    // Space-saving: the new value replaces the least counted one and inherits its
+                    // count, which is recorded as the error of the new entry.
+                    let (least, count) = summary.iter().min_by_key(|v| v.1.0).map(|(k, v)| (k.clone(), v.0)).unwrap();
+                    summary.remove(&least);
+                    summary.insert(value.clone(), (count + 1, count));
+                }
+            }
+        }
+    }
+
+    fn estimate(&mut self, options: &ProfileOptions) {
+        let counts = match &mut self.counter {
+            Counter::Exact(counts) => std::mem::take(counts),
+            Counter::Estimate { .. } => return
+        };
+        let mut sketch = BTreeSet::new();
+        for value in counts.keys() {
+            Self::sketch(&mut sketch, value);
+        }
+        let mut summary: Vec<(Value, u64)> = counts.into_iter().collect();
+        summary.sort_by_key(|v| std::cmp::Reverse(v.1));
+        summary.truncate(summary_capacity(options));
+        self.counter = Counter::Estimate { sketch, summary: summary.into_iter().map(|(k, v)| (k, (v, 0))).collect() };
+    }
+
+    fn into_profile(self, name: String, ty: ValueType, len: u16, options: &ProfileOptions) -> ColumnProfile {
+        let (distinct, exact, counts): (u64, bool, HashMap<Value, u64>) = match self.counter {
+            Counter::Exact(counts) => (counts.len() as u64, true, counts),
+            Counter::Estimate { sketch, summary } => {
+                let distinct = match sketch.len() < SKETCH_SIZE {
+                    true => sketch.len() as u64,
+                    false => {
+                        let kth = *sketch.last().unwrap() as f64 / u64::MAX as f64;
+                        ((SKETCH_SIZE - 1) as f64 / kth) as u64
+                    }
+                };
+                let counts = summary.into_iter()
+                    .map(|(k, (count, error))| (k, count - error))
+                    .filter(|v| v.1 > 0)
+                    .collect();
+                (distinct, false, counts)
+            }
+        };
+        let mut top: Vec<(Value, u64)> = counts.into_iter().collect();
+        top.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp_value(&b.0)));
+        top.truncate(options.top_values as usize);
+        let mean = match self.numeric && self.count > 0 {
+            true => Some(Box::new(Value::Double(self.sum / self.count as f64)).into()),
+            false => None
+        };
+        ColumnProfile {
+            name: CString::new(name).unwrap().into(),
+            ty,
+            len,
+            count: self.count,
+            min: self.min.map(|v| Box::new(v).into()),
+            max: self.max.map(|v| Box::new(v).into()),
+            mean,
+            distinct,
+            exact,
+            empty_strings: self.empty_strings,
+            top: top.into_iter()
+                .map(|(value, count)| ValueCount { value: Box::new(value).into(), count })
+                .collect::<Vec<_>>()
+                .into_boxed_slice()
+                .into()
+        }
+    }
+}
+
+fn summary_capacity(options: &ProfileOptions) -> usize {
+    (options.top_values as usize * 4).max(16)
+}
+
+pub fn profile(table: &Table, options: &ProfileOptions) -> Result<Profile, Error> {
+    let container = unsafe { &*table.container };
+    let names = column_names(table)?;
+    let columns = table.inner.columns(&container.underlying);
+    let types: Vec<(ValueType, u16)> = columns.iter().map(|v| (value_type(v.ty), v.len)).collect();
+    let mut stats: Vec<Stats> = types.iter().map(|v| Stats::new(v.0)).collect();
+    let mut cursor = Cursor::new(table)?;
+    let (mut rows, mut free_rows) = (0, 0);
+    while cursor.next()? {
+        rows += 1;
+        if cursor.is_free() {
+            free_rows += 1;
+            continue;
+        }
+        for (stats, elements) in stats.iter_mut().zip(cursor.elements()) {
+            for value in elements.iter() {
+                stats.add(value, options);
+            }
+        }
+    }
+    Ok(Profile {
+        rows,
+        free_rows,
+        columns: stats.into_iter().zip(names).zip(types)
+            .map(|((stats, name), (ty, len))| stats.into_profile(name, ty, len, options))
+            .collect()
+    })
+}
+
+#[ffi_export]
+pub unsafe fn bpx_profile_options_default(options: *mut ProfileOptions) {
+    *options = ProfileOptions {
+        top_values: 5,
+        exact_limit: 65536
+    }
+}
+
+#[ffi_export]
+pub fn bpx_table_profile(table: &Table, options: &ProfileOptions) -> Option<repr_c::Box<Profile>> {
+    unwrap_result(profile(table, options)).map(|v| Box::new(v).into())
+}
+
+#[ffi_export]
+pub fn bpx_profile_get_row_count(profile: &Profile) -> u64 {
+    profile.rows
+}
+
+#[ffi_export]
+pub fn bpx_profile_get_free_row_count(profile: &Profile) -> u64 {
+    profile.free_rows
+}
+
+#[ffi_export]
+pub fn bpx_profile_get_columns(profile: &Profile) -> c_slice::Ref<'_, ColumnProfile> {
+    profile.columns.as_slice().into()
+}
+
+#[ffi_export]
+pub fn bpx_profile_free(profile: repr_c::Box<Profile>) {
+    drop(profile);
+}
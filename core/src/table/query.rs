@@ -0,0 +1,356 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Filter, sort and projection queries over tables.
+//!
+//! Predicates compare cells by value with `Value::cmp_value`, so a column of any numeric type can
+//! be compared against a number of any other type. Free rows are never returned. Results keep the
+//! index of every source row along with the projected values.
+//!
+//! An array column takes part in predicates and sorting through its first element. Table cells
+//! have no null state: only the cells of a column of type `Null`, and those of a 0-length column
+//! which hold no element, read as null.
+
+use std::cmp::Ordering;
+use std::ffi::CString;
+use std::rc::Weak;
+use bp3d_util::simple_error;
+use safer_ffi::prelude::*;
+use crate::error::unwrap_result;
+use crate::table::core::Table;
+use crate::table::cursor::{self, Cursor};
+use crate::table::index;
+use crate::tree::model::Value;
+
+simple_error! {
+    pub Error {
+        ColumnOutOfRange(usize) => "column index out of range ({})",
+        TableFreed => "the table of the query was freed",
+        (impl From) Cursor(cursor::Error) => "cursor error: {}",
+        (impl From) Table(bpx::table::error::Error) => "table error: {}"
+    }
+}
+
+#[derive_ReprC]
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum CompareOp {
+    Eq = 0,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge
+}
+
+impl CompareOp {
+    pub fn test(self, order: Ordering) -> bool {
+        match self {
+            CompareOp::Eq => order == Ordering::Equal,
+            CompareOp::Ne => order != Ordering::Equal,
+            CompareOp::Lt => order == Ordering::Less,
+            CompareOp::Le => order != Ordering::Greater,
+            CompareOp::Gt => order == Ordering::Greater,
+            CompareOp::Ge => order != Ordering::Less
+        }
+    }
+}
+
+pub enum Predicate {
+    Compare(usize, CompareOp, Value),
+    Range(usize, Value, Value),
+    Prefix(usize, String),
+    Contains(usize, String),
+    /// Tests whether the cell is null, which only holds for columns of type `Null` and 0-length
+    /// columns.
+    Null(usize, bool)
+}
+
+impl Predicate {
+    fn column(&self) -> usize {
+        match self {
+            Predicate::Compare(column, _, _) | Predicate::Range(column, _, _) | Predicate::Prefix(column, _)
+            | Predicate::Contains(column, _) | Predicate::Null(column, _) => *column
+        }
+    }
+
+    pub fn eval(&self, row: &[Value]) -> bool {
+        match self {
+            Predicate::Compare(column, op, value) => op.test(row[*column].cmp_value(value)),
+            Predicate::Range(column, low, high) => row[*column].cmp_value(low) != Ordering::Less
+                && row[*column].cmp_value(high) != Ordering::Greater,
+            Predicate::Prefix(column, s) => matches!(&row[*column], Value::String(v) if v.to_str().starts_with(s.as_str())),
+            Predicate::Contains(column, s) => matches!(&row[*column], Value::String(v) if v.to_str().contains(s.as_str())),
+            Predicate::Null(column, null) => matches!(row[*column], Value::Null) == *null
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct SortKey {
+    pub column: usize,
+    pub descending: bool
+}
+
+/// Sorts rows on the given keys, in order, keeping the original order of equal rows.
+pub fn sort_rows<T>(rows: &mut [T], keys: &[SortKey], values: impl Fn(&T) -> &[Value]) {
+    rows.sort_by(|a, b| {
+        let (a, b) = (values(a), values(b));
+        keys.iter().fold(Ordering::Equal, |order, key| order.then_with(|| {
+            let order = a[key.column].cmp_value(&b[key.column]);
+            match key.descending {
+                true => order.reverse(),
+                false => order
+            }
+        }))
+    });
+}
+
+pub type ScanRow = (usize, Box<[Value]>);
+
+#[derive(Default)]
+pub struct Query {
+    pub filter: Vec<Predicate>,
+    pub order: Vec<SortKey>,
+    pub columns: Vec<usize>,
+    pub offset: usize,
+    pub limit: Option<usize>
+}
+
+#[derive_ReprC]
+#[repr(opaque)]
+pub struct ResultSet {
+    pub(super) names: Vec<char_p::Box>,
+    pub(super) indices: Vec<u64>,
+    pub(super) rows: Vec<Box<[Value]>>
+}
+
+pub fn column_names(table: &Table) -> Result<Vec<String>, Error> {
+    let container = unsafe { &*table.container };
+    let columns = table.inner.columns(&container.underlying);
+    let mut names = Vec::with_capacity(columns.len());
+    for column in columns.iter() {
+        names.push(columns.load_name(column)?.into());
+    }
+    Ok(names)
+}
+
+/// Returns the index and values of every row which is not free and matches the filter, going
+/// through an index of the table when one can narrow the filter down.
+pub fn scan(table: &Table, filter: &[Predicate]) -> Result<Vec<ScanRow>, Error> {
+    let mut cursor = Cursor::new(table)?;
+    let mut rows = Vec::new();
+    if let Some(candidates) = index::candidates(table, filter) {
+        for row in candidates {
+            cursor.read(row)?;
+            if !cursor.is_free() && filter.iter().all(|v| v.eval(cursor.values())) {
+                rows.push((row, cursor.values().into()));
+            }
+        }
+        return Ok(rows);
+    }
+    while cursor.next()? {
+        if !cursor.is_free() && filter.iter().all(|v| v.eval(cursor.values())) {
+            rows.push((cursor.position(), cursor.values().into()));
+        }
+    }
+    Ok(rows)
+}
+
+impl Query {
+    fn check(&self, count: usize) -> Result<(), Error> {
+        let mut columns = self.columns.clone();
+        columns.extend(self.filter.iter().map(|v| v.column()));
+        columns.extend(self.order.iter().map(|v| v.column));
+        match columns.into_iter().find(|v| *v >= count) {
+            Some(column) => Err(Error::ColumnOutOfRange(column)),
+            None => Ok(())
+        }
+    }
+
+    pub fn run(&self, table: &Table) -> Result<ResultSet, Error> {
+        let names = column_names(table)?;
+        self.check(names.len())?;
+        let mut rows = scan(table, &self.filter)?;
+        sort_rows(&mut rows, &self.order, |v| &v.1);
+        let limit = self.limit.unwrap_or(usize::MAX);
+        let rows: Vec<ScanRow> = rows.into_iter().skip(self.offset).take(limit).collect();
+        let columns: Vec<usize> = match self.columns.is_empty() {
+            true => (0..names.len()).collect(),
+            false => self.columns.clone()
+        };
+        Ok(ResultSet {
+            names: columns.iter().map(|v| CString::new(names[*v].as_str()).unwrap().into()).collect(),
+            indices: rows.iter().map(|v| v.0 as u64).collect(),
+            rows: rows.into_iter().map(|(_, row)| columns.iter().map(|v| row[*v].clone()).collect()).collect()
+        })
+    }
+}
+
+#[derive_ReprC]
+#[repr(opaque)]
+pub struct TableQuery {
+    table: *const Table,
+    alive: Weak<()>,
+    query: Query,
+    columns: usize
+}
+
+impl TableQuery {
+    fn table(&self) -> Result<&Table, Error> {
+        match self.alive.strong_count() > 0 {
+            true => Ok(unsafe { &*self.table }),
+            false => Err(Error::TableFreed)
+        }
+    }
+
+    fn column(&self, column: usize) -> Result<usize, Error> {
+        match column < self.columns {
+            true => Ok(column),
+            false => Err(Error::ColumnOutOfRange(column))
+        }
+    }
+
+    fn filter(&mut self, column: usize, f: impl FnOnce(usize) -> Predicate) -> bool {
+        match unwrap_result(self.column(column)) {
+            Some(column) => {
+                self.query.filter.push(f(column));
+                true
+            },
+            None => false
+        }
+    }
+}
+
+#[ffi_export]
+pub fn bpx_table_query_create(table: &Table) -> repr_c::Box<TableQuery> {
+    let container = unsafe { &*table.container };
+    Box::new(TableQuery {
+        table,
+        alive: table.alive(),
+        query: Query::default(),
+        columns: table.inner.columns(&container.underlying).len()
+    }).into()
+}
+
+#[ffi_export]
+pub fn bpx_table_query_where(query: &mut TableQuery, column: usize, op: CompareOp, value: &Value) -> bool {
+    query.filter(column, |v| Predicate::Compare(v, op, value.clone()))
+}
+
+#[ffi_export]
+pub fn bpx_table_query_where_range(query: &mut TableQuery, column: usize, low: &Value, high: &Value) -> bool {
+    query.filter(column, |v| Predicate::Range(v, low.clone(), high.clone()))
+}
+
+#[ffi_export]
+pub fn bpx_table_query_where_prefix(query: &mut TableQuery, column: usize, prefix: char_p::Ref<'_>) -> bool {
+    query.filter(column, |v| Predicate::Prefix(v, prefix.to_str().into()))
+}
+
+#[ffi_export]
+pub fn bpx_table_query_where_contains(query: &mut TableQuery, column: usize, text: char_p::Ref<'_>) -> bool {
+    query.filter(column, |v| Predicate::Contains(v, text.to_str().into()))
+}
+
+#[ffi_export]
+pub fn bpx_table_query_where_null(query: &mut TableQuery, column: usize, null: bool) -> bool {
+    query.filter(column, |v| Predicate::Null(v, null))
+}
+
+#[ffi_export]
+pub fn bpx_table_query_order_by(query: &mut TableQuery, column: usize, descending: bool) -> bool {
+    match unwrap_result(query.column(column)) {
+        Some(column) => {
+            query.query.order.push(SortKey { column, descending });
+            true
+        },
+        None => false
+    }
+}
+
+#[ffi_export]
+pub fn bpx_table_query_select(query: &mut TableQuery, column: usize) -> bool {
+    match unwrap_result(query.column(column)) {
+        Some(column) => {
+            query.query.columns.push(column);
+            true
+        },
+        None => false
+    }
+}
+
+#[ffi_export]
+pub fn bpx_table_query_set_offset(query: &mut TableQuery, offset: usize) {
+    query.query.offset = offset;
+}
+
+#[ffi_export]
+pub fn bpx_table_query_set_limit(query: &mut TableQuery, limit: usize) {
+    query.query.limit = Some(limit);
+}
+
+#[ffi_export]
+pub fn bpx_table_query_run(query: &TableQuery) -> Option<repr_c::Box<ResultSet>> {
+    unwrap_result(query.table().and_then(|table| query.query.run(table))).map(|v| Box::new(v).into())
+}
+
+#[ffi_export]
+pub fn bpx_table_query_free(query: repr_c::Box<TableQuery>) {
+    drop(query);
+}
+
+#[ffi_export]
+pub fn bpx_result_set_get_row_count(result: &ResultSet) -> usize {
+    result.rows.len()
+}
+
+#[ffi_export]
+pub fn bpx_result_set_get_column_count(result: &ResultSet) -> usize {
+    result.names.len()
+}
+
+#[ffi_export]
+pub fn bpx_result_set_get_column_name(result: &ResultSet, column: usize) -> Option<char_p::Ref<'_>> {
+    result.names.get(column).map(|v| v.as_ref())
+}
+
+#[ffi_export]
+pub fn bpx_result_set_get_indices(result: &ResultSet) -> c_slice::Ref<'_, u64> {
+    result.indices.as_slice().into()
+}
+
+#[ffi_export]
+pub fn bpx_result_set_get_value(result: &ResultSet, row: usize, column: usize) -> Option<&Value> {
+    result.rows.get(row).and_then(|v| v.get(column))
+}
+
+#[ffi_export]
+pub fn bpx_result_set_free(result: repr_c::Box<ResultSet>) {
+    drop(result);
+}
@@ -27,97 +27,215 @@
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::ffi::CString;
-use bpx::table::column::Type;
+use std::fmt::{Display, Formatter};
+use bp3d_util::simple_error;
+use bpx::table::column::{Column, Type};
+use bpx::table::error::ValueError;
+use bpx::table::row::Value as CellValue;
 use safer_ffi::prelude::*;
 use crate::table::core::Table;
-use crate::tree::model::Value;
+use crate::tree::convert;
+use crate::tree::model::{Value, ValueType};
+
+simple_error! {
+    pub Error {
+        (impl From) Value(ValueError) => "invalid value: {}",
+        NulByte => "varchar contains a nul byte",
+        InvalidCell(CellError) => "{}"
+    }
+}
+
+/// A cell of a row which could not be decoded, along with the reason.
+#[derive(Debug)]
+pub struct CellError {
+    pub column: usize,
+    pub cause: Box<Error>
+}
+
+impl CellError {
+    pub fn new(column: usize, cause: Error) -> Self {
+        Self { column, cause: Box::new(cause) }
+    }
+}
+
+impl Display for CellError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "row contains an invalid value in column {}: {}", self.column, self.cause)
+    }
+}
+
+pub fn value_type(ty: Type) -> ValueType {
+    match ty {
+        Type::Null => ValueType::Null,
+        Type::Boolean => ValueType::Boolean,
+        Type::Uint8 => ValueType::UInt8,
+        Type::Uint16 => ValueType::UInt16,
+        Type::Uint32 => ValueType::UInt32,
+        Type::Uint64 => ValueType::UInt64,
+        Type::Int8 => ValueType::Int8,
+        Type::Int16 => ValueType::Int16,
+        Type::Int32 => ValueType::Int32,
+        Type::Int64 => ValueType::Int64,
+        Type::Float => ValueType::Float,
+        Type::Double => ValueType::Double,
+        Type::Varchar => ValueType::String
+    }
+}
+
+pub fn column_type(ty: ValueType) -> Type {
+    match ty {
+        ValueType::Null => Type::Null,
+        ValueType::Int8 => Type::Int8,
+        ValueType::UInt8 => Type::Uint8,
+        ValueType::Int16 => Type::Int16,
+        ValueType::UInt16 => Type::Uint16,
+        ValueType::Int32 => Type::Int32,
+        ValueType::UInt32 => Type::Uint32,
+        ValueType::Int64 => Type::Int64,
+        ValueType::UInt64 => Type::Uint64,
+        ValueType::Float => Type::Float,
+        ValueType::Double => Type::Double,
+        ValueType::Boolean => Type::Boolean,
+        ValueType::String => Type::Varchar
+    }
+}
+
+pub fn read_cell(ty: Type, cell: &[u8]) -> Result<Value, Error> {
+    Ok(match ty {
+        Type::Null => Value::Null,
+        Type::Boolean => Value::Boolean(bool::read(cell, ty)?),
+        Type::Uint8 => Value::UInt8(u8::read(cell, ty)?),
+        Type::Uint16 => Value::UInt16(u16::read(cell, ty)?),
+        Type::Uint32 => Value::UInt32(u32::read(cell, ty)?),
+        Type::Uint64 => Value::UInt64(u64::read(cell, ty)?),
+        Type::Int8 => Value::Int8(i8::read(cell, ty)?),
+        Type::Int16 => Value::Int16(i16::read(cell, ty)?),
+        Type::Int32 => Value::Int32(i32::read(cell, ty)?),
+        Type::Int64 => Value::Int64(i64::read(cell, ty)?),
+        Type::Float => Value::Float(f64::read(cell, ty)? as _),
+        Type::Double => Value::Double(f64::read(cell, ty)?),
+        Type::Varchar => Value::String(CString::new(<&str>::read(cell, ty)?).map_err(|_| Error::NulByte)?.into()),
+    })
+}
+
+/// Returns the number of elements held by a cell of the given column; a varchar column holds a
+/// single string of up to `len` bytes.
+pub fn element_count(column: &Column) -> usize {
+    match column.ty {
+        Type::Null | Type::Varchar => 1,
+        _ => column.len as usize
+    }
+}
+
+/// Decodes every element of a cell holding `count` elements of the given type. A cell of a
+/// 0-length column holds no element.
+pub fn read_elements(ty: Type, count: usize, cell: &[u8]) -> Result<Box<[Value]>, Error> {
+    let size = cell.len() / count.max(1);
+    (0..count).map(|i| read_cell(ty, &cell[i * size..(i + 1) * size])).collect()
+}
+
+/// Returns the value standing for a whole cell where a single value is expected: its first
+/// element, or null when the cell holds no element.
+pub fn first_element(elements: &[Value]) -> Value {
+    elements.first().cloned().unwrap_or(Value::Null)
+}
+
+fn default_value(ty: Type) -> Value {
+    match ty {
+        Type::Null => Value::Null,
+        Type::Boolean => Value::Boolean(false),
+        Type::Uint8 => Value::UInt8(0),
+        Type::Uint16 => Value::UInt16(0),
+        Type::Uint32 => Value::UInt32(0),
+        Type::Uint64 => Value::UInt64(0),
+        Type::Int8 => Value::Int8(0),
+        Type::Int16 => Value::Int16(0),
+        Type::Int32 => Value::Int32(0),
+        Type::Int64 => Value::Int64(0),
+        Type::Float => Value::Float(0.0),
+        Type::Double => Value::Double(0.0),
+        Type::Varchar => Value::String(CString::new("").unwrap().into()),
+    }
+}
 
 #[derive_ReprC]
 #[repr(opaque)]
 pub struct Row {
     pub(super) inner: bpx::table::row::Row,
-    value: Box<[Value]>
+    value: Box<[Box<[Value]>]>,
+    // Handed out as the value of a cell which holds no element; it is never written to the row.
+    placeholder: Value
 }
 
 impl Row {
-    fn new(inner: bpx::table::row::Row, table: &Table) -> Self {
+    pub(super) fn new(inner: bpx::table::row::Row, table: &Table) -> Self {
         let container = unsafe { &*table.container };
-        let mut val = Self {
-            inner,
-            value: vec![Value::Null; table.inner.columns(&container.underlying).len()].into_boxed_slice()
-        };
-        val.init(table);
-        val
+        let value = table.inner.columns(&container.underlying).iter()
+            .map(|v| vec![default_value(v.ty); element_count(v)].into_boxed_slice())
+            .collect();
+        Self { inner, value, placeholder: Value::Null }
     }
 
-    fn init(&mut self, table: &Table) {
-        let container = unsafe { &*table.container };
-        let columns = table.inner.columns(&container.underlying);
-        for (index, cell) in self.value.iter_mut().enumerate() {
-            match columns[index].ty {
-                Type::Null => *cell = Value::Null,
-                Type::Boolean => *cell = Value::Boolean(false),
-                Type::Uint8 => *cell = Value::UInt8(0),
-                Type::Uint16 => *cell = Value::UInt16(0),
-                Type::Uint32 => *cell = Value::UInt32(0),
-                Type::Uint64 => *cell = Value::UInt64(0),
-                Type::Int8 => *cell = Value::Int8(0),
-                Type::Int16 => *cell = Value::Int16(0),
-                Type::Int32 => *cell = Value::Int32(0),
-                Type::Int64 => *cell = Value::Int64(0),
-                Type::Float => *cell = Value::Float(0.0),
-                Type::Double => *cell = Value::Double(0.0),
-                Type::Varchar => *cell = Value::String(CString::new("").unwrap().into()),
-            }
-        }
+    pub(super) fn cells(&self) -> &[Box<[Value]>] {
+        &self.value
+    }
+
+    pub(super) fn cells_mut(&mut self) -> &mut [Box<[Value]>] {
+        &mut self.value
     }
 
-    pub(super) fn sync_read(&mut self, table: &Table) {
+    pub(super) fn sync_read(&mut self, table: &Table) -> Result<(), Error> {
         let container = unsafe { &*table.container };
         let columns = table.inner.columns(&container.underlying);
         for (index, cell) in self.value.iter_mut().enumerate() {
             let pos = table.inner.get_column_pos_at(index).unwrap();
-            let r = self.inner.cell(pos);
-            match columns[index].ty {
-                Type::Null => (),
-                Type::Boolean => *cell = Value::Boolean(r.get().unwrap()),
-                Type::Uint8 => *cell = Value::UInt8(r.get().unwrap()),
-                Type::Uint16 => *cell = Value::UInt16(r.get().unwrap()),
-                Type::Uint32 => *cell = Value::UInt32(r.get().unwrap()),
-                Type::Uint64 => *cell = Value::UInt64(r.get().unwrap()),
-                Type::Int8 => *cell = Value::Int8(r.get().unwrap()),
-                Type::Int16 => *cell = Value::Int16(r.get().unwrap()),
-                Type::Int32 => *cell = Value::Int32(r.get().unwrap()),
-                Type::Int64 => *cell = Value::Int64(r.get().unwrap()),
-                Type::Float => *cell = Value::Float(r.get::<f64>().unwrap() as _),
-                Type::Double => *cell = Value::Double(r.get().unwrap()),
-                Type::Varchar => *cell = Value::String(CString::new(r.get::<&str>().unwrap()).unwrap().into()),
-            }
+            let data = self.inner.cell(pos);
+            *cell = read_elements(columns[index].ty, element_count(&columns[index]), data.as_bytes())
+                .map_err(|e| Error::InvalidCell(CellError::new(index, e)))?;
         }
+        Ok(())
     }
 
-    pub(super) fn sync_write(&mut self, table: &Table) {
+    pub(super) fn sync_write(&mut self, table: &Table) -> Result<(), convert::Error> {
         let container = unsafe { &*table.container };
         let columns = table.inner.columns(&container.underlying);
         for (index, cell) in self.value.iter().enumerate() {
+            let column = &columns[index];
+            let ty = value_type(column.ty);
             let pos = table.inner.get_column_pos_at(index).unwrap();
             let mut r = self.inner.cell_mut(pos);
-            match columns[index].ty {
-                Type::Null => (),
-                Type::Boolean => r.set(cell.as_bool()).unwrap(),
-                Type::Uint8 => r.set(cell.as_u64()).unwrap(),
-                Type::Uint16 => r.set(cell.as_u64()).unwrap(),
-                Type::Uint32 => r.set(cell.as_u64()).unwrap(),
-                Type::Uint64 => r.set(cell.as_u64()).unwrap(),
-                Type::Int8 => r.set(cell.as_i64()).unwrap(),
-                Type::Int16 => r.set(cell.as_i64()).unwrap(),
-                Type::Int32 => r.set(cell.as_i64()).unwrap(),
-                Type::Int64 => r.set(cell.as_i64()).unwrap(),
-                Type::Float => r.set(cell.as_f64()).unwrap(),
-                Type::Double => r.set(cell.as_f64()).unwrap(),
-                Type::Varchar => r.set(cell.as_str()).unwrap(),
+            let data = r.as_bytes_mut();
+            let size = data.len() / element_count(column).max(1);
+            for (i, element) in cell.iter().enumerate().take(element_count(column)) {
+                let value = match table.strict {
+                    true => element.convert(ty)?,
+                    false => element.coerce(ty)
+                };
+                if table.strict && column.ty == Type::Varchar && value.as_str().len() > column.len as usize {
+                    return Err(convert::Error::Overflow(ty.name()));
+                }
+                let data = &mut data[i * size..(i + 1) * size];
+                match value {
+                    Value::Null => (),
+                    Value::Int8(v) => v.write(data, column.ty).unwrap(),
+                    Value::UInt8(v) => v.write(data, column.ty).unwrap(),
+                    Value::Int16(v) => v.write(data, column.ty).unwrap(),
+                    Value::UInt16(v) => v.write(data, column.ty).unwrap(),
+                    Value::Int32(v) => v.write(data, column.ty).unwrap(),
+                    Value::UInt32(v) => v.write(data, column.ty).unwrap(),
+                    Value::Int64(v) => v.write(data, column.ty).unwrap(),
+                    Value::UInt64(v) => v.write(data, column.ty).unwrap(),
+                    Value::Float(v) => (v as f64).write(data, column.ty).unwrap(),
+                    Value::Double(v) => v.write(data, column.ty).unwrap(),
+                    Value::Boolean(v) => v.write(data, column.ty).unwrap(),
+                    Value::String(v) => {
+                        data.fill(0);
+                        v.to_str().write(data, column.ty).unwrap()
+                    }
+                }
             }
         }
+        Ok(())
     }
 }
 
@@ -138,12 +256,33 @@ pub fn bpx_table_row_is_free(row: &Row) -> bool {
 
 #[ffi_export]
 pub fn bpx_table_row_get_value_const(row: &Row, index: usize) -> &Value {
-    &row.value[index]
+    row.value[index].first().unwrap_or(&row.placeholder)
 }
 
 #[ffi_export]
 pub fn bpx_table_row_get_value(row: &mut Row, index: usize) -> &mut Value {
-    &mut row.value[index]
+    match row.value[index].first_mut() {
+        Some(value) => value,
+        None => {
+            row.placeholder = Value::Null;
+            &mut row.placeholder
+        }
+    }
+}
+
+#[ffi_export]
+pub fn bpx_table_row_get_element_count(row: &Row, index: usize) -> usize {
+    row.value[index].len()
+}
+
+#[ffi_export]
+pub fn bpx_table_row_get_element_const(row: &Row, index: usize, element: usize) -> Option<&Value> {
+    row.value[index].get(element)
+}
+
+#[ffi_export]
+pub fn bpx_table_row_get_element(row: &mut Row, index: usize, element: usize) -> Option<&mut Value> {
+    row.value[index].get_mut(element)
 }
 
 #[ffi_export]
@@ -0,0 +1,1002 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! A small SQL subset over the tables of a container.
+//!
+//! The supported statement is:
+//!
+//! ```text
+//! SELECT * | expr [AS alias], ...
+//! FROM table [alias]
+//! [[INNER | LEFT] JOIN table [alias] ON expr] ...
+//! [WHERE expr]
+//! [GROUP BY expr, ...]
+//! [ORDER BY expr [ASC | DESC], ...]
+//! [LIMIT count [OFFSET offset]]
+//! ```
+//!
+//! Expressions are column references (`name` or `table.name`, quoted with `"` or `` ` `` when
+//! needed), literals (numbers, `'strings'`, `TRUE`, `FALSE` and `NULL`), comparisons (`=`, `!=`,
+//! `<>`, `<`, `<=`, `>`, `>=`), `IS [NOT] NULL`, `[NOT] BETWEEN`, `[NOT] LIKE` with `%` and `_`,
+//! `AND`, `OR`, `NOT` and the aggregates `COUNT(*)`, `COUNT`, `SUM`, `MIN`, `MAX` and `AVG`.
+//! Keywords are case-insensitive while table and column names are matched exactly. Comparisons
+//! involving `NULL` are unknown, as in standard SQL, and rows for which the `WHERE` or `ON`
+//! condition is unknown are dropped.
+//!
+//! `ORDER BY` accepts the alias or name of an output column, its 1-based position or any other
+//! expression; a number which is not the position of an output column is an error. The index
+//! returned for every output row is the one of the row of the `FROM` table it came from, or of the
+//! first row of its group.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::ffi::CString;
+use bp3d_util::simple_error;
+use bpx::core::header::SECTION_TYPE_TABLE;
+use safer_ffi::prelude::*;
+use crate::common::{find_sections, Container};
+use crate::error::unwrap_result;
+use crate::strings::resolve_strings;
+use crate::table::core::Table;
+use crate::table::query::{self, column_names, scan, CompareOp, ResultSet};
+use crate::tree::model::Value;
+
+simple_error! {
+    pub ParseError {
+        Syntax(String) => "syntax error: {}"
+    }
+}
+
+simple_error! {
+    pub Error {
+        NoStrings => "no string section found",
+        TableNotFound(String) => "table not found ({})",
+        ColumnNotFound(String) => "column not found ({})",
+        AmbiguousColumn(String) => "ambiguous column name ({})",
+        NotGrouped(String) => "{} must appear in GROUP BY or be used in an aggregate",
+        MisplacedAggregate(String) => "aggregates are not allowed in {}",
+        NotNumeric(String) => "{} needs numeric values",
+        InvalidPosition(String) => "ORDER BY position must be an output column number ({})",
+        (impl From) Table(bpx::table::error::Error) => "table error: {}",
+        (impl From) Query(query::Error) => "query error: {}"
+    }
+}
+
+#[derive(Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Number(String),
+    String(String),
+    Symbol(&'static str)
+}
+
+const SYMBOLS: [&str; 13] = ["<=", ">=", "!=", "<>", "=", "<", ">", ",", ".", "(", ")", "*", "-"];
+
+fn lex(text: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some(&(pos, c)) = chars.peek() {
+        if c.is_whitespace() || c == ';' {
+            chars.next();
+        } else if c.is_alphabetic() || c == '_' {
+            let mut word = String::new();
+            while let Some(&(_, c)) = chars.peek().filter(|(_, c)| c.is_alphanumeric() || *c == '_') {
+                word.push(c);
+                chars.next();
+            }
+            tokens.push((Token::Word(word), pos));
+        } else if c.is_ascii_digit() {
+            let mut number = String::new();
+            while let Some(&(_, c)) = chars.peek().filter(|(_, c)| c.is_ascii_alphanumeric() || *c == '.' || *c == '_') {
+                number.push(c);
+                chars.next();
+                // The exponent of a decimal number may be signed.
+                if matches!(c, 'e' | 'E') && !number.starts_with("0x") {
+                    if let Some(&(_, sign)) = chars.peek().filter(|(_, c)| matches!(c, '+' | '-')) {
+                        number.push(sign);
+                        chars.next();
+                    }
+                }
+            }
+            tokens.push((Token::Number(number), pos));
+        } else if matches!(c, '\'' | '"' | '`') {
+            chars.next();
+            let mut out = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, v)) if v == c => match chars.peek() {
+                        Some(&(_, v)) if v == c => {
+                            out.push(c);
+                            chars.next();
+                        },
+                        _ => break
+                    },
+                    Some((_, v)) => out.push(v),
+                    None => return Err(ParseError::Syntax(format!("unterminated quote at offset {}", pos)))
+                }
+            }
+            tokens.push((if c == '\'' { Token::String(out) } else { Token::Quoted(out) }, pos));
+        } else {
+            let rest = &text[pos..];
+            let symbol = SYMBOLS.iter().find(|v| rest.starts_with(**v))
+                .ok_or_else(|| ParseError::Syntax(format!("unexpected character '{}' at offset {}", c, pos)))?;
+            tokens.push((Token::Symbol(symbol), pos));
+            for _ in 0..symbol.len() {
+                chars.next();
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum Aggregate {
+    Count,
+    Sum,
+    Min,
+    Max,
+    Avg
+}
+
+#[derive(Clone, PartialEq)]
+enum Expr {
+    Column(Option<String>, String),
+    Index(usize),
+    Literal(Value),
+    Aggregate(Aggregate, Option<Box<Expr>>),
+    Compare(Box<Expr>, CompareOp, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    IsNull(Box<Expr>),
+    Between(Box<Expr>, Box<Expr>, Box<Expr>),
+    Like(Box<Expr>, String)
+}
+
+struct SelectItem {
+    expr: Expr,
+    name: String
+}
+
+struct TableRef {
+    name: String,
+    alias: String
+}
+
+struct Join {
+    table: TableRef,
+    on: Expr,
+    left: bool
+}
+
+struct OrderItem {
+    expr: Expr,
+    descending: bool
+}
+
+pub struct Select {
+    items: Option<Vec<SelectItem>>,
+    from: TableRef,
+    joins: Vec<Join>,
+    filter: Option<Expr>,
+    group: Vec<Expr>,
+    order: Vec<OrderItem>,
+    limit: Option<usize>,
+    offset: usize
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    tokens: Vec<(Token, usize)>,
+    pos: usize
+}
+
+impl Parser<'_> {
+    fn offset(&self) -> usize {
+        self.tokens.get(self.pos).map(|v| v.1).unwrap_or(self.text.len())
+    }
+
+    fn error<T>(&self, msg: &str) -> Result<T, ParseError> {
+        match self.tokens.get(self.pos) {
+            Some((_, pos)) => Err(ParseError::Syntax(format!("{} at offset {}", msg, pos))),
+            None => Err(ParseError::Syntax(format!("{} at end of query", msg)))
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|v| &v.0)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(v)) if v.eq_ignore_ascii_case(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), ParseError> {
+        match self.eat_keyword(keyword) {
+            true => Ok(()),
+            false => self.error(&format!("expected {}", keyword))
+        }
+    }
+
+    fn eat_symbol(&mut self, symbol: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Symbol(v)) if *v == symbol);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<(), ParseError> {
+        match self.eat_symbol(symbol) {
+            true => Ok(()),
+            false => self.error(&format!("expected '{}'", symbol))
+        }
+    }
+
+    fn name(&mut self) -> Result<String, ParseError> {
+        match self.peek() {
+            Some(Token::Word(v)) if !is_reserved(v) => {
+                let name = v.clone();
+                self.pos += 1;
+                Ok(name)
+            },
+            Some(Token::Quoted(v)) => {
+                let name = v.clone();
+                self.pos += 1;
+                Ok(name)
+            },
+            _ => self.error("expected a name")
+        }
+    }
+
+    fn usize(&mut self) -> Result<usize, ParseError> {
+        match self.peek() {
+            Some(Token::Number(v)) => match v.parse() {
+                Ok(v) => {
+                    self.pos += 1;
+                    Ok(v)
+                },
+                Err(_) => self.error("expected a count")
+            },
+            _ => self.error("expected a count")
+        }
+    }
+
+    fn number(&mut self, negative: bool) -> Result<Value, ParseError> {
+        let text = match self.peek() {
+            Some(Token::Number(v)) => v.replace('_', ""),
+            _ => return self.error("expected a number")
+        };
+        let sign = if negative { "-" } else { "" };
+        let value = match text.contains(['.', 'e', 'E']) && !text.starts_with("0x") {
+            true => format!("{}{}", sign, text).parse().ok().map(Value::Double),
+            false => format!("{}{}", sign, text).parse().ok().map(Value::Int64)
+                .or_else(|| text.parse().ok().filter(|_| !negative).map(Value::UInt64))
+        };
+        match value {
+            Some(v) => {
+                self.pos += 1;
+                Ok(v)
+            },
+            None => self.error("invalid number")
+        }
+    }
+
+    fn table(&mut self) -> Result<TableRef, ParseError> {
+        let name = self.name()?;
+        self.eat_keyword("AS");
+        let alias = match self.peek() {
+            Some(Token::Word(v)) if !is_reserved(v) => self.name()?,
+            Some(Token::Quoted(_)) => self.name()?,
+            _ => name.clone()
+        };
+        Ok(TableRef { name, alias })
+    }
+
+    fn operand(&mut self) -> Result<Expr, ParseError> {
+        if self.eat_symbol("(") {
+            let expr = self.expr()?;
+            self.expect_symbol(")")?;
+            return Ok(expr);
+        }
+        if self.eat_symbol("-") {
+            return self.number(true).map(Expr::Literal);
+        }
+        let token = match self.peek() {
+            Some(v) => v.clone(),
+            None => return self.error("expected an expression")
+        };
+        match token {
+            Token::Number(_) => self.number(false).map(Expr::Literal),
+            Token::String(v) => {
+                self.pos += 1;
+                Ok(Expr::Literal(Value::String(CString::new(v.replace('\0', "")).unwrap().into())))
+            },
+            Token::Word(v) if v.eq_ignore_ascii_case("NULL") => {
+                self.pos += 1;
+                Ok(Expr::Literal(Value::Null))
+            },
+            Token::Word(v) if v.eq_ignore_ascii_case("TRUE") || v.eq_ignore_ascii_case("FALSE") => {
+                self.pos += 1;
+                Ok(Expr::Literal(Value::Boolean(v.eq_ignore_ascii_case("TRUE"))))
+            },
+            Token::Word(v) if matches!(self.tokens.get(self.pos + 1), Some((Token::Symbol("("), _))) => {
+                let aggregate = match v.to_ascii_uppercase().as_str() {
+                    "COUNT" => Aggregate::Count,
+                    "SUM" => Aggregate::Sum,
+                    "MIN" => Aggregate::Min,
+                    "MAX" => Aggregate::Max,
+                    "AVG" => Aggregate::Avg,
+                    _ => return self.error("unknown function")
+                };
+                self.pos += 2;
+                let arg = match aggregate == Aggregate::Count && self.eat_symbol("*") {
+                    true => None,
+                    false => Some(Box::new(self.expr()?))
+                };
+                if arg.as_ref().is_some_and(|v| v.has_aggregate()) {
+                    return self.error("aggregates cannot be nested");
+                }
+                self.expect_symbol(")")?;
+                Ok(Expr::Aggregate(aggregate, arg))
+            },
+            _ => {
+                let name = self.name()?;
+                match self.eat_symbol(".") {
+                    true => Ok(Expr::Column(Some(name), self.name()?)),
+                    false => Ok(Expr::Column(None, name))
+                }
+            }
+        }
+    }
+
+    fn predicate(&mut self) -> Result<Expr, ParseError> {
+        let left = self.operand()?;
+        for (symbol, op) in [("=", CompareOp::Eq), ("!=", CompareOp::Ne), ("<>", CompareOp::Ne), ("<=", CompareOp::Le),
+                             (">=", CompareOp::Ge), ("<", CompareOp::Lt), (">", CompareOp::Gt)] {
+            if self.eat_symbol(symbol) {
+                return Ok(Expr::Compare(Box::new(left), op, Box::new(self.operand()?)));
+            }
+        }
+        if self.eat_keyword("IS") {
+            let not = self.eat_keyword("NOT");
+            self.expect_keyword("NULL")?;
+            let expr = Expr::IsNull(Box::new(left));
+            return Ok(if not { Expr::Not(Box::new(expr)) } else { expr });
+        }
+        let not = self.eat_keyword("NOT");
+        let expr = if self.eat_keyword("BETWEEN") {
+            let low = self.operand()?;
+            self.expect_keyword("AND")?;
+            Expr::Between(Box::new(left), Box::new(low), Box::new(self.operand()?))
+        } else if self.eat_keyword("LIKE") {
+            match self.peek() {
+                Some(Token::String(v)) => {
+                    let pattern = v.clone();
+                    self.pos += 1;
+                    Expr::Like(Box::new(left), pattern)
+                },
+                _ => return self.error("expected a pattern")
+            }
+        } else if not {
+            return self.error("expected BETWEEN or LIKE");
+        } else {
+            return Ok(left);
+        };
+        Ok(if not { Expr::Not(Box::new(expr)) } else { expr })
+    }
+
+    fn not(&mut self) -> Result<Expr, ParseError> {
+        match self.eat_keyword("NOT") {
+            true => Ok(Expr::Not(Box::new(self.not()?))),
+            false => self.predicate()
+        }
+    }
+
+    fn and(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.not()?;
+        while self.eat_keyword("AND") {
+            expr = Expr::And(Box::new(expr), Box::new(self.not()?));
+        }
+        Ok(expr)
+    }
+
+    fn expr(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.and()?;
+        while self.eat_keyword("OR") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn list<T>(&mut self, mut f: impl FnMut(&mut Self) -> Result<T, ParseError>) -> Result<Vec<T>, ParseError> {
+        let mut list = vec![f(self)?];
+        while self.eat_symbol(",") {
+            list.push(f(self)?);
+        }
+        Ok(list)
+    }
+
+    fn select_item(&mut self) -> Result<SelectItem, ParseError> {
+        let start = self.offset();
+        let expr = self.expr()?;
+        let end = self.tokens.get(self.pos).map(|v| v.1).unwrap_or(self.text.len());
+        let name = match self.eat_keyword("AS") {
+            true => self.name()?,
+            false => match &expr {
+                Expr::Column(_, name) => name.clone(),
+                _ => self.text[start..end].trim().into()
+            }
+        };
+        Ok(SelectItem { expr, name })
+    }
+
+    fn select(&mut self) -> Result<Select, ParseError> {
+        self.expect_keyword("SELECT")?;
+        let items = match self.eat_symbol("*") {
+            true => None,
+            false => Some(self.list(Self::select_item)?)
+        };
+        self.expect_keyword("FROM")?;
+        let from = self.table()?;
+        let mut joins = Vec::new();
+        loop {
+            let left = self.eat_keyword("LEFT");
+            if !left {
+                self.eat_keyword("INNER");
+            }
+            if !self.eat_keyword("JOIN") {
+                if left || self.is_keyword("ON") {
+                    return self.error("expected JOIN");
+                }
+                break;
+            }
+            let table = self.table()?;
+            self.expect_keyword("ON")?;
+            joins.push(Join { table, on: self.expr()?, left });
+        }
+        let filter = match self.eat_keyword("WHERE") {
+            true => Some(self.expr()?),
+            false => None
+        };
+        let mut group = Vec::new();
+        if self.eat_keyword("GROUP") {
+            self.expect_keyword("BY")?;
+            group = self.list(Self::expr)?;
+        }
+        let mut order = Vec::new();
+        if self.eat_keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            order = self.list(|p| {
+                let expr = p.expr()?;
+                let descending = p.eat_keyword("DESC");
+                if !descending {
+                    p.eat_keyword("ASC");
+                }
+                Ok(OrderItem { expr, descending })
+            })?;
+        }
+        let mut limit = None;
+        let mut offset = 0;
+        if self.eat_keyword("LIMIT") {
+            limit = Some(self.usize()?);
+            if self.eat_keyword("OFFSET") {
+                offset = self.usize()?;
+            }
+        }
+        if self.pos < self.tokens.len() {
+            return self.error("unexpected token");
+        }
+        Ok(Select { items, from, joins, filter, group, order, limit, offset })
+    }
+}
+
+const RESERVED: [&str; 24] = [
+    "SELECT", "FROM", "WHERE", "GROUP", "ORDER", "BY", "LIMIT", "OFFSET", "JOIN", "INNER", "LEFT",
+    "ON", "AS", "AND", "OR", "NOT", "IS", "NULL", "BETWEEN", "LIKE", "ASC", "DESC", "TRUE", "FALSE"
+];
+
+fn is_reserved(word: &str) -> bool {
+    RESERVED.iter().any(|v| v.eq_ignore_ascii_case(word))
+}
+
+pub fn parse(text: &str) -> Result<Select, ParseError> {
+    Parser { text, tokens: lex(text)?, pos: 0 }.select()
+}
+
+fn like(text: &[char], pattern: &[char]) -> bool {
+    // Classic wildcard matching: `star` remembers the last `%` to backtrack to.
+    let (mut t, mut p) = (0, 0);
+    let mut star = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('%') => {
+                star = Some((p, t));
+                p += 1;
+            },
+            Some(c) if *c == '_' || *c == text[t] => {
+                t += 1;
+                p += 1;
+            },
+            _ => match star {
+                Some((sp, st)) => {
+                    p = sp + 1;
+                    t = st + 1;
+                    star = Some((sp, st + 1));
+                },
+                None => return false
+            }
+        }
+    }
+    pattern[p..].iter().all(|v| *v == '%')
+}
+
+#[derive(Hash, Eq, PartialEq)]
+enum Key {
+    Null,
+    Bool(bool),
+    Integer(i128),
+    Float(u64),
+    String(String)
+}
+
+fn key(value: &Value) -> Key {
+    match value {
+        Value::Null => Key::Null,
+        Value::Boolean(v) => Key::Bool(*v),
+        Value::String(v) => Key::String(v.to_str().into()),
+        Value::Float(_) | Value::Double(_) => {
+            let v = value.as_f64();
+            match v.fract() == 0.0 && v.abs() < 1e38 {
+                true => Key::Integer(v as i128),
+                false if v.is_nan() => Key::Float(f64::NAN.to_bits()),
+                false => Key::Float(v.to_bits())
+            }
+        },
+        Value::UInt64(v) => Key::Integer(*v as _),
+        _ => Key::Integer(value.as_i64() as _)
+    }
+}
+
+fn truth(value: &Value) -> Option<bool> {
+    match value {
+        Value::Null => None,
+        Value::Boolean(v) => Some(*v),
+        v => Some(v.as_bool())
+    }
+}
+
+fn boolean(value: Option<bool>) -> Value {
+    value.map(Value::Boolean).unwrap_or(Value::Null)
+}
+
+enum Number {
+    Integer(i128),
+    Float(f64)
+}
+
+fn number(value: &Value) -> Option<Number> {
+    match value {
+        Value::Null | Value::Boolean(_) | Value::String(_) => None,
+        Value::Float(_) | Value::Double(_) => Some(Number::Float(value.as_f64())),
+        Value::UInt64(v) => Some(Number::Integer(*v as _)),
+        v => Some(Number::Integer(v.as_i64() as _))
+    }
+}
+
+fn aggregate(aggregate: Aggregate, values: Vec<Value>) -> Result<Value, Error> {
+    let values: Vec<Value> = values.into_iter().filter(|v| !matches!(v, Value::Null)).collect();
+    Ok(match aggregate {
+        Aggregate::Count => Value::UInt64(values.len() as _),
+        Aggregate::Min => values.into_iter().min_by(|a, b| a.cmp_value(b)).unwrap_or(Value::Null),
+        Aggregate::Max => values.into_iter().max_by(|a, b| a.cmp_value(b)).unwrap_or(Value::Null),
+        Aggregate::Sum | Aggregate::Avg => {
+            if values.is_empty() {
+                return Ok(Value::Null);
+            }
+            let name = if aggregate == Aggregate::Sum { "SUM" } else { "AVG" };
+            let mut integer: Option<i128> = Some(0);
+            let mut float = 0.0;
+            for v in &values {
+                match number(v).ok_or_else(|| Error::NotNumeric(name.into()))? {
+                    Number::Integer(v) => {
+                        integer = integer.and_then(|i| i.checked_add(v));
+                        float += v as f64;
+                    },
+                    Number::Float(v) => {
+                        integer = None;
+                        float += v;
+                    }
+                }
+            }
+            match (aggregate, integer.and_then(|v| i64::try_from(v).ok())) {
+                (Aggregate::Sum, Some(v)) => Value::Int64(v),
+                (Aggregate::Sum, None) => Value::Double(float),
+                _ => Value::Double(float / values.len() as f64)
+            }
+        }
+    })
+}
+
+impl Expr {
+    fn has_aggregate(&self) -> bool {
+        matches!(self, Expr::Aggregate(_, _)) || self.children().into_iter().any(Expr::has_aggregate)
+    }
+
+    fn children(&self) -> Vec<&Expr> {
+        match self {
+            Expr::Column(_, _) | Expr::Index(_) | Expr::Literal(_) | Expr::Aggregate(_, None) => Vec::new(),
+            Expr::Aggregate(_, Some(v)) | Expr::Not(v) | Expr::IsNull(v) | Expr::Like(v, _) => vec![&**v],
+            Expr::Compare(a, _, b) | Expr::And(a, b) | Expr::Or(a, b) => vec![&**a, &**b],
+            Expr::Between(a, b, c) => vec![&**a, &**b, &**c]
+        }
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Expr> {
+        match self {
+            Expr::Column(_, _) | Expr::Index(_) | Expr::Literal(_) | Expr::Aggregate(_, None) => Vec::new(),
+            Expr::Aggregate(_, Some(v)) | Expr::Not(v) | Expr::IsNull(v) | Expr::Like(v, _) => vec![&mut **v],
+            Expr::Compare(a, _, b) | Expr::And(a, b) | Expr::Or(a, b) => vec![&mut **a, &mut **b],
+            Expr::Between(a, b, c) => vec![&mut **a, &mut **b, &mut **c]
+        }
+    }
+
+    fn resolve(&mut self, scope: &Scope) -> Result<(), Error> {
+        if let Expr::Column(table, name) = self {
+            *self = Expr::Index(scope.find(table.as_deref(), name)?);
+            return Ok(());
+        }
+        self.children_mut().into_iter().try_for_each(|v| v.resolve(scope))
+    }
+
+    /// Checks that every column used outside of an aggregate is one of the group keys.
+    fn check_grouped(&self, keys: &[Expr], scope: &Scope) -> Result<(), Error> {
+        if keys.contains(self) {
+            return Ok(());
+        }
+        match self {
+            Expr::Aggregate(_, _) | Expr::Literal(_) => Ok(()),
+            Expr::Index(v) => Err(Error::NotGrouped(scope.columns[*v].1.clone())),
+            _ => self.children().into_iter().try_for_each(|v| v.check_grouped(keys, scope))
+        }
+    }
+
+    fn eval(&self, row: &[Value], group: &[&Row]) -> Result<Value, Error> {
+        let eval = |v: &Expr| v.eval(row, group);
+        Ok(match self {
+            Expr::Column(_, name) => return Err(Error::ColumnNotFound(name.clone())),
+            Expr::Index(v) => row[*v].clone(),
+            Expr::Literal(v) => v.clone(),
+            Expr::Aggregate(_, None) => Value::UInt64(group.len() as _),
+            Expr::Aggregate(f, Some(arg)) => {
+                let mut values = Vec::with_capacity(group.len());
+                for v in group {
+                    values.push(arg.eval(&v.values, &[])?);
+                }
+                aggregate(*f, values)?
+            },
+            Expr::Compare(a, op, b) => {
+                let (a, b) = (eval(a)?, eval(b)?);
+                match matches!(a, Value::Null) || matches!(b, Value::Null) {
+                    true => Value::Null,
+                    false => Value::Boolean(op.test(a.cmp_value(&b)))
+                }
+            },
+            Expr::And(a, b) => match (truth(&eval(a)?), truth(&eval(b)?)) {
+                (Some(false), _) | (_, Some(false)) => Value::Boolean(false),
+                (Some(true), Some(true)) => Value::Boolean(true),
+                _ => Value::Null
+            },
+            Expr::Or(a, b) => match (truth(&eval(a)?), truth(&eval(b)?)) {
+                (Some(true), _) | (_, Some(true)) => Value::Boolean(true),
+                (Some(false), Some(false)) => Value::Boolean(false),
+                _ => Value::Null
+            },
+            Expr::Not(v) => boolean(truth(&eval(v)?).map(|v| !v)),
+            Expr::IsNull(v) => Value::Boolean(matches!(eval(v)?, Value::Null)),
+            Expr::Between(v, low, high) => {
+                let (v, low, high) = (eval(v)?, eval(low)?, eval(high)?);
+                match [&v, &low, &high].iter().any(|v| matches!(v, Value::Null)) {
+                    true => Value::Null,
+                    false => Value::Boolean(v.cmp_value(&low) != Ordering::Less && v.cmp_value(&high) != Ordering::Greater)
+                }
+            },
+            Expr::Like(v, pattern) => match eval(v)? {
+                Value::Null => Value::Null,
+                Value::String(v) => {
+                    let text: Vec<char> = v.to_str().chars().collect();
+                    let pattern: Vec<char> = pattern.chars().collect();
+                    Value::Boolean(like(&text, &pattern))
+                },
+                _ => Value::Boolean(false)
+            }
+        })
+    }
+
+    fn test(&self, row: &[Value]) -> Result<bool, Error> {
+        Ok(truth(&self.eval(row, &[])?) == Some(true))
+    }
+}
+
+struct Scope {
+    /// Table alias and name of every column of the joined rows.
+    columns: Vec<(String, String)>
+}
+
+impl Scope {
+    fn find(&self, table: Option<&str>, name: &str) -> Result<usize, Error> {
+        let mut found = self.columns.iter().enumerate()
+            .filter(|(_, (t, n))| n == name && table.map(|v| v == t).unwrap_or(true))
+            .map(|(i, _)| i);
+        let display = match table {
+            Some(table) => format!("{}.{}", table, name),
+            None => name.into()
+        };
+        match (found.next(), found.next()) {
+            (Some(index), None) => Ok(index),
+            (Some(_), Some(_)) => Err(Error::AmbiguousColumn(display)),
+            (None, _) => Err(Error::ColumnNotFound(display))
+        }
+    }
+}
+
+struct Row {
+    index: usize,
+    values: Vec<Value>
+}
+
+fn open_table(container: &Container, strings: u32, name: &str) -> Result<Table, Error> {
+    let strings = resolve_strings(container, strings).ok_or(Error::NoStrings)?;
+    for handle in find_sections(container, SECTION_TYPE_TABLE) {
+        if let Ok(table) = Table::open(container, handle, strings) {
+            if table.name() == name {
+                return Ok(table);
+            }
+        }
+    }
+    Err(Error::TableNotFound(name.into()))
+}
+
+fn join(rows: Vec<Row>, right: Vec<Box<[Value]>>, on: &Expr, left: bool, width: usize) -> Result<Vec<Row>, Error> {
+    let right_width = right.first().map(|v| v.len()).unwrap_or(0);
+    let combine = |row: &Row, other: Option<&[Value]>| Row {
+        index: row.index,
+        values: row.values.iter().cloned()
+            .chain(match other {
+                Some(v) => v.to_vec(),
+                None => vec![Value::Null; right_width]
+            })
+            .collect()
+    };
+    // Equality between a column of each side is joined through a hash table, anything else
+    // falls back to testing every pair of rows.
+    let keys = match on {
+        Expr::Compare(a, CompareOp::Eq, b) => match (&**a, &**b) {
+            (Expr::Index(a), Expr::Index(b)) if *a < width && *b >= width => Some((*a, *b - width)),
+            (Expr::Index(a), Expr::Index(b)) if *b < width && *a >= width => Some((*b, *a - width)),
+            _ => None
+        },
+        _ => None
+    };
+    let mut out = Vec::new();
+    match keys {
+        Some((left_key, right_key)) => {
+            let mut table: HashMap<Key, Vec<usize>> = HashMap::new();
+            for (index, row) in right.iter().enumerate() {
+                if !matches!(row[right_key], Value::Null) {
+                    table.entry(key(&row[right_key])).or_default().push(index);
+                }
+            }
+            for row in &rows {
+                let matches = match row.values[left_key] {
+                    Value::Null => None,
+                    ref v => table.get(&key(v))
+                };
+                match matches {
+                    Some(list) => out.extend(list.iter().map(|v| combine(row, Some(&right[*v])))),
+                    None if left => out.push(combine(row, None)),
+                    None => ()
+                }
+            }
+        },
+        None => for row in &rows {
+            let len = out.len();
+            for other in &right {
+                let combined = combine(row, Some(other));
+                if on.test(&combined.values)? {
+                    out.push(combined);
+                }
+            }
+            if left && out.len() == len {
+                out.push(combine(row, None));
+            }
+        }
+    }
+    Ok(out)
+}
+
+struct Output {
+    index: usize,
+    values: Vec<Value>,
+    keys: Vec<Value>
+}
+
+pub fn execute(container: &Container, strings: u32, select: &Select) -> Result<ResultSet, Error> {
+    let mut scope = Scope { columns: Vec::new() };
+    let from = open_table(container, strings, &select.from.name)?;
+    scope.columns.extend(column_names(&from)?.into_iter().map(|v| (select.from.alias.clone(), v)));
+    let mut joins = Vec::with_capacity(select.joins.len());
+    for item in &select.joins {
+        let table = open_table(container, strings, &item.table.name)?;
+        let start = scope.columns.len();
+        scope.columns.extend(column_names(&table)?.into_iter().map(|v| (item.table.alias.clone(), v)));
+        let mut on = item.on.clone();
+        on.resolve(&scope)?;
+        if on.has_aggregate() {
+            return Err(Error::MisplacedAggregate("ON".into()));
+        }
+        joins.push((table, on, item.left, start));
+    }
+    let mut filter = select.filter.clone();
+    if let Some(filter) = &mut filter {
+        filter.resolve(&scope)?;
+        if filter.has_aggregate() {
+            return Err(Error::MisplacedAggregate("WHERE".into()));
+        }
+    }
+    let mut rows: Vec<Row> = scan(&from, &[])?.into_iter()
+        .map(|(index, values)| Row { index, values: values.into_vec() })
+        .collect();
+    for (table, on, left, start) in &joins {
+        let right = scan(table, &[])?.into_iter().map(|v| v.1).collect();
+        rows = join(rows, right, on, *left, *start)?;
+    }
+    if let Some(filter) = &filter {
+        let mut kept = Vec::with_capacity(rows.len());
+        for row in rows {
+            if filter.test(&row.values)? {
+                kept.push(row);
+            }
+        }
+        rows = kept;
+    }
+    let mut items: Vec<(Expr, String)> = match &select.items {
+        Some(items) => items.iter().map(|v| (v.expr.clone(), v.name.clone())).collect(),
+        None => (0..scope.columns.len()).map(|v| (Expr::Index(v), scope.columns[v].1.clone())).collect()
+    };
+    for (expr, _) in &mut items {
+        expr.resolve(&scope)?;
+    }
+    let mut group = select.group.clone();
+    for expr in &mut group {
+        expr.resolve(&scope)?;
+        if expr.has_aggregate() {
+            return Err(Error::MisplacedAggregate("GROUP BY".into()));
+        }
+    }
+    // Order items naming an output column, by alias or by position, sort on that column.
+    let mut order = Vec::with_capacity(select.order.len());
+    for item in &select.order {
+        let output = match &item.expr {
+            Expr::Literal(v) if !matches!(v, Value::Null | Value::String(_) | Value::Boolean(_)) => {
+                let position = match v {
+                    Value::Float(_) | Value::Double(_) => None,
+                    v => Some((v.as_i64() as usize).wrapping_sub(1)).filter(|v| *v < items.len())
+                };
+                match position {
+                    Some(v) => Some(v),
+                    None => return Err(Error::InvalidPosition(format!("{}", v)))
+                }
+            },
+            Expr::Column(None, name) => items.iter().position(|(_, v)| v == name),
+            _ => None
+        };
+        let expr = match output {
+            Some(index) => items[index].0.clone(),
+            None => {
+                let mut expr = item.expr.clone();
+                expr.resolve(&scope)?;
+                expr
+            }
+        };
+        order.push((expr, item.descending));
+    }
+    let grouped = !group.is_empty() || items.iter().any(|(v, _)| v.has_aggregate())
+        || order.iter().any(|(v, _)| v.has_aggregate());
+    let mut output = Vec::new();
+    if grouped {
+        for expr in items.iter().map(|v| &v.0).chain(order.iter().map(|v| &v.0)) {
+            expr.check_grouped(&group, &scope)?;
+        }
+        let mut groups: Vec<Vec<&Row>> = Vec::new();
+        let mut lookup: HashMap<Vec<Key>, usize> = HashMap::new();
+        for row in &rows {
+            let mut k = Vec::with_capacity(group.len());
+            for expr in &group {
+                k.push(key(&expr.eval(&row.values, &[])?));
+            }
+            let index = *lookup.entry(k).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[index].push(row);
+        }
+        if groups.is_empty() && group.is_empty() {
+            groups.push(Vec::new());
+        }
+        for list in groups {
+            let values = list.first().map(|v| v.values.clone()).unwrap_or_else(|| vec![Value::Null; scope.columns.len()]);
+            let mut out = Output { index: list.first().map(|v| v.index).unwrap_or(0), values: Vec::new(), keys: Vec::new() };
+            for (expr, _) in &items {
+                out.values.push(expr.eval(&values, &list)?);
+            }
+            for (expr, _) in &order {
+                out.keys.push(expr.eval(&values, &list)?);
+            }
+            output.push(out);
+        }
+    } else {
+        for row in &rows {
+            let mut out = Output { index: row.index, values: Vec::new(), keys: Vec::new() };
+            for (expr, _) in &items {
+                out.values.push(expr.eval(&row.values, &[])?);
+            }
+            for (expr, _) in &order {
+                out.keys.push(expr.eval(&row.values, &[])?);
+            }
+            output.push(out);
+        }
+    }
+    output.sort_by(|a, b| {
+        order.iter().enumerate().fold(Ordering::Equal, |acc, (i, (_, descending))| acc.then_with(|| {
+            let order = a.keys[i].cmp_value(&b.keys[i]);
+            if *descending { order.reverse() } else { order }
+        }))
+    });
+    let output: Vec<Output> = output.into_iter().skip(select.offset).take(select.limit.unwrap_or(usize::MAX)).collect();
+    Ok(ResultSet {
+        names: items.iter().map(|(_, v)| CString::new(v.replace('\0', "")).unwrap().into()).collect(),
+        indices: output.iter().map(|v| v.index as u64).collect(),
+        rows: output.into_iter().map(|v| v.values.into_boxed_slice()).collect()
+    })
+}
+
+#[ffi_export]
+pub fn bpx_sql_execute(container: &Container, strings: u32, sql: char_p::Ref<'_>) -> Option<repr_c::Box<ResultSet>> {
+    let select = unwrap_result(parse(sql.to_str()))?;
+    unwrap_result(execute(container, strings, &select)).map(|v| Box::new(v).into())
+}
@@ -0,0 +1,312 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+//! Transactions which stage the edits of a table in memory.
+//!
+//! Beginning a transaction copies the table section to memory. Until the transaction ends, row
+//! writes, appends, deletes and batch writes go to that copy, and reading or counting rows through
+//! the table or the batch functions sees it as well. Cursors, and with them queries, index
+//! creation and lookups and profiles, as well as conversions and validation, read the table
+//! section; they fail until the transaction ends rather than miss the staged rows. Column changes
+//! and saves are recorded instead of being applied, so the columns of the table stay those it had
+//! when the transaction began.
+//!
+//! Column changes are checked when they are recorded: a removal must name a column the table will
+//! have at that point and a creation must have a name. Committing checks the staged rows against
+//! the unique indexes of the table, writes the names of the created columns to the strings section
+//! and builds the headers of the recorded saves into the copy, so that writing the copy back to the
+//! table section is the last step which can fail; the column changes then take effect as if they
+//! were made right after the rows. A commit which fails leaves the transaction open and the columns
+//! of the table unchanged, although the names it already wrote stay in the strings section. Rolling
+//! back drops the copy and the recorded changes; neither touches any section.
+
+use std::collections::BTreeSet;
+use std::io::{Read, Seek, SeekFrom, Write};
+use bp3d_util::simple_error;
+use bpx::core::{AutoSectionData, SectionData};
+use bpx::core::header::Struct;
+use bpx::table::column::{Column, Type, SIZE_COLUMN_STRUCTURE};
+use bpx::table::core::RawTable;
+use bpx::table::header::{Header, SIZE_HEADER_STRUCTURE};
+use bpx::table::row::Row as RawRow;
+use safer_ffi::prelude::*;
+use crate::common::{read_all, Container};
+use crate::error::unwrap_result;
+use crate::table::core::Table;
+use crate::table::index;
+
+simple_error! {
+    pub Error {
+        AlreadyActive => "a transaction is already open on this table",
+        NotActive => "no transaction is open on this table",
+        (impl From) Index(index::Error) => "index error: {}",
+        (impl From) Bpx(bpx::core::error::Error) => "BPX error: {}",
+        (impl From) Table(bpx::table::error::Error) => "table error: {}",
+        (impl From) Io(std::io::Error) => "io error: {}"
+    }
+}
+
+#[derive(Clone)]
+pub enum SchemaChange {
+    Create(String, Type, u16),
+    Remove(usize),
+    Save
+}
+
+pub struct Transaction {
+    pub(super) data: AutoSectionData,
+    pub(super) changes: Vec<SchemaChange>,
+    rows: BTreeSet<usize>,
+    // The address of the table name, when the section had a header as the transaction began.
+    name: Option<u32>
+}
+
+impl Transaction {
+    /// Returns the number of columns the table will have once the recorded changes are applied.
+    pub(super) fn column_count(&self, columns: usize) -> usize {
+        self.changes.iter().fold(columns, |count, change| match change {
+            SchemaChange::Create(..) => count + 1,
+            SchemaChange::Remove(_) => count.saturating_sub(1),
+            SchemaChange::Save => count
+        })
+    }
+}
+
+pub fn begin(table: &Table) -> Result<(), Error> {
+    if table.in_transaction() {
+        return Err(Error::AlreadyActive);
+    }
+    let container = unsafe { &*table.container };
+    let bytes = read_all(&mut *container.underlying.sections().load(table.inner.handle())?)?;
+    let name = match bytes.get(..SIZE_HEADER_STRUCTURE) {
+        Some(header) => Some(Header::from_bytes(header.try_into().unwrap())?.name),
+        None => None
+    };
+    let mut data = AutoSectionData::default();
+    data.write_all(&bytes)?;
+    data.seek(SeekFrom::Start(0))?;
+    *table.transaction.borrow_mut() = Some(Transaction { data, changes: Vec::new(), rows: BTreeSet::new(), name });
+    Ok(())
+}
+
+/// Writes a row to the staged copy, or appends it when `index` is None, and returns its index.
+pub fn write(table: &Table, row: &RawRow, index: Option<usize>) -> Result<usize, Error> {
+    let mut transaction = table.transaction.borrow_mut();
+    let transaction = transaction.as_mut().ok_or(Error::NotActive)?;
+    let index = match index {
+        Some(index) => bpx::table::row::write(&mut transaction.data, row, index).map(|()| index)?,
+        None => bpx::table::row::append(&mut transaction.data, row)?
+    };
+    transaction.rows.insert(index);
+    Ok(index)
+}
+
+fn layout(table: &Table, container: &Container) -> (usize, usize) {
+    let columns = table.inner.columns(&container.underlying).len();
+    (SIZE_HEADER_STRUCTURE + columns * SIZE_COLUMN_STRUCTURE, table.inner.get_actual_row_size())
+}
+
+/// Writes consecutive rows laid out as in the table section to the staged copy, starting at row
+/// `start` which may be at most the row count.
+pub fn write_rows(table: &Table, start: usize, rows: &[u8]) -> Result<(), Error> {
+    let container = unsafe { &*table.container };
+    let (header_size, actual_row_size) = layout(table, container);
+    let mut transaction = table.transaction.borrow_mut();
+    let transaction = transaction.as_mut().ok_or(Error::NotActive)?;
+    transaction.data.seek(SeekFrom::Start((header_size + start * actual_row_size) as _))?;
+    transaction.data.write_all(rows)?;
+    transaction.rows.extend(start..start + rows.len() / actual_row_size);
+    Ok(())
+}
+
+fn staged_rows(table: &Table, transaction: &mut Transaction) -> Result<Vec<(usize, Vec<u8>)>, Error> {
+    let container = unsafe { &*table.container };
+    let (header_size, actual_row_size) = layout(table, container);
+    let mut rows = Vec::with_capacity(transaction.rows.len());
+    for index in &transaction.rows {
+        let mut row = vec![0; actual_row_size];
+        transaction.data.seek(SeekFrom::Start((header_size + index * actual_row_size) as _))?;
+        transaction.data.read_exact(&mut row)?;
+        rows.push((*index, row));
+    }
+    Ok(rows)
+}
+
+/// Removes the columns past the first `columns` ones.
+fn truncate_columns(table: &mut RawTable, container: &Container, columns: usize) {
+    for index in (columns..table.columns(&container.underlying).len()).rev() {
+        table.columns_mut(&container.underlying).remove_at(index);
+    }
+}
+
+/// Appends the recorded creations to the columns of the table, which writes their names to the
+/// strings section. The columns are left as they were when a creation fails.
+fn create_columns(table: &mut RawTable, container: &Container, changes: &[SchemaChange]) -> Result<(), Error> {
+    let columns = table.columns(&container.underlying).len();
+    for change in changes {
+        if let SchemaChange::Create(name, ty, len) = change {
+            if let Err(e) = table.columns_mut(&container.underlying).create(name, *ty, *len) {
+                truncate_columns(table, container, columns);
+                return Err(e.into());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Replays the recorded changes over the indices of the columns once every creation is appended
+/// after the first `columns` ones. Returns the indices which remain and, for each save, those
+/// present when it was recorded.
+fn replay(changes: &[SchemaChange], columns: usize) -> (Vec<usize>, Vec<Vec<usize>>) {
+    let mut current: Vec<usize> = (0..columns).collect();
+    let mut created = columns;
+    let mut saves = Vec::new();
+    for change in changes {
+        match change {
+            SchemaChange::Create(..) => {
+                current.push(created);
+                created += 1;
+            },
+            SchemaChange::Remove(index) => {
+                current.remove(*index);
+            },
+            SchemaChange::Save => saves.push(current.clone())
+        }
+    }
+    (current, saves)
+}
+
+/// Returns the address of the name of a table whose section has no header yet. Only bpx knows it,
+/// so the table is saved and the header read back; the commit replaces the section afterwards.
+fn load_name(table: &mut RawTable, container: &Container) -> Result<u32, Error> {
+    table.save(&container.underlying)?;
+    let mut data = container.underlying.sections().load(table.handle())?;
+    data.seek(SeekFrom::Start(0))?;
+    let header = Header::read(&mut *data)?;
+    data.seek(SeekFrom::Start(0))?;
+    Ok(header.name)
+}
+
+/// Writes the header a save of `columns` writes to the start of the section to the start of `bytes`.
+fn write_header(bytes: &mut Vec<u8>, name: u32, columns: &[Column]) {
+    let mut header = Header::new();
+    header.name = name;
+    header.columns = columns.len() as _;
+    let mut buffer = header.to_bytes().to_vec();
+    for column in columns {
+        buffer.extend_from_slice(&column.to_bytes());
+    }
+    if bytes.len() < buffer.len() {
+        bytes.resize(buffer.len(), 0);
+    }
+    bytes[..buffer.len()].copy_from_slice(&buffer);
+}
+
+/// Builds the headers of the recorded saves into `bytes` and writes them to the table section.
+fn write_section(table: &mut RawTable, container: &Container, mut bytes: Vec<u8>, name: Option<u32>, saves: &[Vec<usize>]) -> Result<(), Error> {
+    if !saves.is_empty() {
+        let name = match name {
+            Some(v) => v,
+            None => load_name(table, container)?
+        };
+        let columns: Vec<Column> = table.columns(&container.underlying).iter().copied().collect();
+        for save in saves {
+            let columns: Vec<Column> = save.iter().map(|index| columns[*index]).collect();
+            write_header(&mut bytes, name, &columns);
+        }
+    }
+    container.touch();
+    let mut data = container.underlying.sections().load(table.handle())?;
+    let size = data.size();
+    data.truncate(size)?;
+    data.seek(SeekFrom::Start(0))?;
+    data.write_all(&bytes)?;
+    Ok(())
+}
+
+/// Ends the transaction, putting it back when the commit fails.
+pub fn commit(table: &mut Table) -> Result<(), Error> {
+    // The indexes are brought up to date from the table section, which cursors refuse to read
+    // while a transaction is open.
+    let mut transaction = table.transaction.take().ok_or(Error::NotActive)?;
+    let res = apply(table, &mut transaction);
+    if res.is_err() {
+        *table.transaction.get_mut() = Some(transaction);
+    }
+    res
+}
+
+fn apply(table: &mut Table, transaction: &mut Transaction) -> Result<(), Error> {
+    let container = unsafe { &*table.container };
+    let rows = match table.indexes.borrow().is_empty() {
+        true => Vec::new(),
+        false => staged_rows(table, transaction)?
+    };
+    let pending = index::prepare(table, || rows.iter()
+        .map(|(index, row)| index::decode_row(table, row).map(|v| (*index, v)))
+        .collect())?;
+    let (bytes, changes, name) = (read_all(&mut transaction.data)?, &transaction.changes, transaction.name);
+    let columns = table.inner.columns(&container.underlying).len();
+    create_columns(&mut table.inner, container, changes)?;
+    let (remaining, saves) = replay(changes, columns);
+    if let Err(e) = write_section(&mut table.inner, container, bytes, name, &saves) {
+        truncate_columns(&mut table.inner, container, columns);
+        return Err(e);
+    }
+    index::commit(table, pending);
+    for index in (0..table.inner.columns(&container.underlying).len()).rev() {
+        if remaining.binary_search(&index).is_err() {
+            table.inner.columns_mut(&container.underlying).remove_at(index);
+        }
+    }
+    table.schema += changes.iter().filter(|v| !matches!(v, SchemaChange::Save)).count() as u64;
+    Ok(())
+}
+
+pub fn rollback(table: &Table) -> Result<(), Error> {
+    table.transaction.take().map(drop).ok_or(Error::NotActive)
+}
+
+#[ffi_export]
+pub fn bpx_table_begin(table: &Table) -> bool {
+    unwrap_result(begin(table)).is_some()
+}
+
+#[ffi_export]
+pub fn bpx_table_commit(table: &mut Table) -> bool {
+    unwrap_result(commit(table)).is_some()
+}
+
+#[ffi_export]
+pub fn bpx_table_rollback(table: &Table) -> bool {
+    unwrap_result(rollback(table)).is_some()
+}
+
+#[ffi_export]
+pub fn bpx_table_in_transaction(table: &Table) -> bool {
+    table.in_transaction()
+}
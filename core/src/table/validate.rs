@@ -0,0 +1,151 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+//! Consistency checks of a table against its strings section.
+//!
+//! The name of a table and the names of its columns are offsets into the strings section given
+//! when the table was opened or created, while varchar cells hold their bytes inline. A wrong
+//! strings handle or a damaged strings section shows as names which fail to load, and damaged
+//! rows as cells which do not decode, such as varchar cells holding invalid UTF-8 or a nul byte
+//! followed by other characters. Every row is checked, including free rows since the row reader
+//! reads them as well. A table with a pending transaction cannot be validated.
+
+use std::ffi::CString;
+use bp3d_util::simple_error;
+use safer_ffi::prelude::*;
+use crate::error::unwrap_result;
+use crate::table::core::Table;
+use crate::table::row::{element_count, read_cell};
+
+simple_error! {
+    pub Error {
+        PendingTransaction => "table has a pending transaction",
+        (impl From) Bpx(bpx::core::error::Error) => "BPX error: {}",
+        (impl From) Table(bpx::table::error::Error) => "table error: {}"
+    }
+}
+
+#[derive_ReprC]
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum IssueKind {
+    TableName = 0,
+    ColumnName,
+    Cell
+}
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct Issue {
+    pub kind: IssueKind,
+    pub row: u64,
+    pub column: u64,
+    pub element: u64,
+    pub message: char_p::Box
+}
+
+impl Issue {
+    fn new(kind: IssueKind, row: usize, column: usize, element: usize, message: String) -> Self {
+        Self {
+            kind,
+            row: row as _,
+            column: column as _,
+            element: element as _,
+            message: CString::new(message.replace('\0', "")).unwrap().into()
+        }
+    }
+}
+
+#[derive_ReprC]
+#[repr(opaque)]
+pub struct TableValidation {
+    rows: u64,
+    issues: Vec<Issue>
+}
+
+pub fn validate(table: &Table) -> Result<TableValidation, Error> {
+    let container = unsafe { &*table.container };
+    if table.in_transaction() {
+        return Err(Error::PendingTransaction);
+    }
+    let mut issues = Vec::new();
+    if let Err(e) = table.inner.load_name(&container.underlying) {
+        issues.push(Issue::new(IssueKind::TableName, 0, 0, 0, e.to_string()));
+    }
+    let columns = table.inner.columns(&container.underlying);
+    for (index, column) in columns.iter().enumerate() {
+        if let Err(e) = columns.load_name(column) {
+            issues.push(Issue::new(IssueKind::ColumnName, 0, index, 0, e.to_string()));
+        }
+    }
+    if columns.is_empty() {
+        return Ok(TableValidation { rows: 0, issues });
+    }
+    let mut row = table.inner.alloc_row();
+    let mut data = container.underlying.sections().load(table.inner.handle())?;
+    let rows = bpx::table::row::count(&*data, &row);
+    for index in 0..rows {
+        bpx::table::row::read(&mut *data, &mut row, index)?;
+        for (i, column) in columns.iter().enumerate() {
+            let cell = row.cell(table.inner.get_column_pos_at(i).unwrap());
+            let bytes = cell.as_bytes();
+            let count = element_count(column);
+            let size = bytes.len() / count.max(1);
+            for element in 0..count {
+                if let Err(e) = read_cell(column.ty, &bytes[element * size..(element + 1) * size]) {
+                    issues.push(Issue::new(IssueKind::Cell, index, i, element, e.to_string()));
+                }
+            }
+        }
+    }
+    Ok(TableValidation { rows: rows as _, issues })
+}
+
+#[ffi_export]
+pub fn bpx_table_validate(table: &Table) -> Option<repr_c::Box<TableValidation>> {
+    unwrap_result(validate(table)).map(|v| Box::new(v).into())
+}
+
+#[ffi_export]
+pub fn bpx_table_validation_is_valid(validation: &TableValidation) -> bool {
+    validation.issues.is_empty()
+}
+
+#[ffi_export]
+pub fn bpx_table_validation_get_row_count(validation: &TableValidation) -> u64 {
+    validation.rows
+}
+
+#[ffi_export]
+pub fn bpx_table_validation_get_issues(validation: &TableValidation) -> c_slice::Ref<'_, Issue> {
+    validation.issues.as_slice().into()
+}
+
+#[ffi_export]
+pub fn bpx_table_validation_free(validation: repr_c::Box<TableValidation>) {
+    drop(validation);
+}
@@ -0,0 +1,224 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Equality, ordering and hashing for values and nodes.
+//!
+//! Values of different classes order as null < boolean < number < string. Numbers are compared by
+//! their mathematical value whatever their type, NaN sorting after every other number. Two numbers
+//! with the same value but different types are not equal: ties are broken by type, then by the
+//! IEEE total order so that `-0.0 < 0.0`. Equality is therefore exact, like the one used by tree
+//! diffs, and consistent with the ordering and the hash.
+//!
+//! Node equality and hashing cover the name, the value and the children in order. Details are
+//! ignored as they describe where a node came from rather than what it contains.
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use crate::tree::model::{Node, Value};
+
+#[derive(Copy, Clone)]
+enum Number {
+    Integer(i128),
+    Float(f64)
+}
+
+fn number(value: &Value) -> Option<Number> {
+    Some(match value {
+        Value::Int8(v) => Number::Integer(*v as _),
+        Value::UInt8(v) => Number::Integer(*v as _),
+        Value::Int16(v) => Number::Integer(*v as _),
+        Value::UInt16(v) => Number::Integer(*v as _),
+        Value::Int32(v) => Number::Integer(*v as _),
+        Value::UInt32(v) => Number::Integer(*v as _),
+        Value::Int64(v) => Number::Integer(*v as _),
+        Value::UInt64(v) => Number::Integer(*v as _),
+        Value::Float(v) => Number::Float(*v as _),
+        Value::Double(v) => Number::Float(*v),
+        _ => return None
+    })
+}
+
+fn class(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::Boolean(_) => 1,
+        Value::String(_) => 3,
+        _ => 2
+    }
+}
+
+fn compare_mixed(i: i128, f: f64) -> Ordering {
+    if f.is_nan() {
+        return Ordering::Less;
+    }
+    // Every float outside of this range is beyond any 64-bit integer.
+    let t = f.trunc();
+    if t <= -1e38 {
+        return Ordering::Greater;
+    }
+    if t >= 1e38 {
+        return Ordering::Less;
+    }
+    i.cmp(&(t as i128)).then_with(|| 0.0.partial_cmp(&(f - t)).unwrap())
+}
+
+fn compare_numbers(a: Number, b: Number) -> Ordering {
+    match (a, b) {
+        (Number::Integer(a), Number::Integer(b)) => a.cmp(&b),
+        (Number::Integer(a), Number::Float(b)) => compare_mixed(a, b),
+        (Number::Float(a), Number::Integer(b)) => compare_mixed(b, a).reverse(),
+        (Number::Float(a), Number::Float(b)) => match (a.is_nan(), b.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => a.partial_cmp(&b).unwrap()
+        }
+    }
+}
+
+fn tie_break(a: &Value, b: &Value) -> Ordering {
+    (a.get_type() as i32).cmp(&(b.get_type() as i32)).then_with(|| match (a, b) {
+        (Value::Float(a), Value::Float(b)) => a.total_cmp(b),
+        (Value::Double(a), Value::Double(b)) => a.total_cmp(b),
+        _ => Ordering::Equal
+    })
+}
+
+impl Value {
+    /// Compares two values by class and value only, so that numbers of different types holding
+    /// the same value compare equal.
+    pub fn cmp_value(&self, other: &Self) -> Ordering {
+        class(self).cmp(&class(other)).then_with(|| match (self, other) {
+            (Value::Boolean(a), Value::Boolean(b)) => a.cmp(b),
+            (Value::String(a), Value::String(b)) => a.to_str().cmp(b.to_str()),
+            _ => match (number(self), number(other)) {
+                (Some(a), Some(b)) => compare_numbers(a, b),
+                _ => Ordering::Equal
+            }
+        })
+    }
+}
+
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_value(other).then_with(|| tie_break(self, other))
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Value {}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u8(self.get_type() as u8);
+        match self {
+            Value::Null => (),
+            Value::Int8(v) => state.write_u64(*v as u64),
+            Value::UInt8(v) => state.write_u64(*v as u64),
+            Value::Int16(v) => state.write_u64(*v as u64),
+            Value::UInt16(v) => state.write_u64(*v as u64),
+            Value::Int32(v) => state.write_u64(*v as u64),
+            Value::UInt32(v) => state.write_u64(*v as u64),
+            Value::Int64(v) => state.write_u64(*v as u64),
+            Value::UInt64(v) => state.write_u64(*v),
+            Value::Float(v) => state.write_u64(v.to_bits() as u64),
+            Value::Double(v) => state.write_u64(v.to_bits()),
+            Value::Boolean(v) => state.write_u8(*v as u8),
+            Value::String(v) => {
+                state.write(v.to_str().as_bytes());
+                state.write_u8(0xFF);
+            }
+        }
+    }
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.name.to_str() == other.name.to_str() && self.value == other.value && self.children == other.children
+    }
+}
+
+impl Eq for Node {}
+
+impl Hash for Node {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write(self.name.to_str().as_bytes());
+        state.write_u8(0xFF);
+        self.value.hash(state);
+        state.write_u64(self.children.len() as u64);
+        for child in &self.children {
+            child.hash(state);
+        }
+    }
+}
+
+/// A 64-bit FNV-1a hasher whose output does not depend on the platform or the process.
+pub struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(0xCBF29CE484222325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 = (self.0 ^ *b as u64).wrapping_mul(0x100000001B3);
+        }
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+}
+
+pub fn stable_hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = StableHasher::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
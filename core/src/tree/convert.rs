@@ -0,0 +1,160 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Checked conversions between values.
+//!
+//! Integers convert to other integer types when the value fits, and to floating point types when
+//! the value is represented exactly. Floating point values convert to integer types when they are
+//! integral and in range, and double values convert to float when no precision is lost. Booleans,
+//! strings and null only convert to their own type.
+
+use std::ffi::CString;
+use bp3d_util::simple_error;
+use crate::tree::model::{Value, ValueType};
+
+simple_error! {
+    pub Error {
+        Overflow(&'static str) => "value does not fit in {}",
+        PrecisionLoss(&'static str) => "value cannot be represented exactly as {}",
+        TypeMismatch(String) => "type mismatch: {}"
+    }
+}
+
+fn mismatch(from: ValueType, to: ValueType) -> Error {
+    Error::TypeMismatch(format!("cannot convert {} to {}", from.name(), to.name()))
+}
+
+enum Number {
+    Signed(i64),
+    Unsigned(u64),
+    Float(f64)
+}
+
+fn number(value: &Value) -> Option<Number> {
+    Some(match value {
+        Value::Int8(v) => Number::Signed(*v as _),
+        Value::Int16(v) => Number::Signed(*v as _),
+        Value::Int32(v) => Number::Signed(*v as _),
+        Value::Int64(v) => Number::Signed(*v),
+        Value::UInt8(v) => Number::Unsigned(*v as _),
+        Value::UInt16(v) => Number::Unsigned(*v as _),
+        Value::UInt32(v) => Number::Unsigned(*v as _),
+        Value::UInt64(v) => Number::Unsigned(*v),
+        Value::Float(v) => Number::Float(*v as _),
+        Value::Double(v) => Number::Float(*v),
+        _ => return None
+    })
+}
+
+fn is_integer(ty: ValueType) -> bool {
+    matches!(ty, ValueType::Int8 | ValueType::UInt8 | ValueType::Int16 | ValueType::UInt16
+        | ValueType::Int32 | ValueType::UInt32 | ValueType::Int64 | ValueType::UInt64)
+}
+
+fn to_i128(n: &Number, ty: ValueType) -> Result<i128, Error> {
+    match *n {
+        Number::Signed(v) => Ok(v as _),
+        Number::Unsigned(v) => Ok(v as _),
+        Number::Float(v) if v.is_finite() && v.fract() == 0.0 => match v >= i128::MIN as f64 && v < i128::MAX as f64 {
+            true => Ok(v as _),
+            false => Err(Error::Overflow(ty.name()))
+        },
+        Number::Float(_) => Err(Error::PrecisionLoss(ty.name()))
+    }
+}
+
+fn to_f64(n: &Number, ty: ValueType) -> Result<f64, Error> {
+    match *n {
+        Number::Signed(v) if (v as f64) as i64 == v && v != i64::MAX => Ok(v as _),
+        Number::Unsigned(v) if (v as f64) as u64 == v && v != u64::MAX => Ok(v as _),
+        Number::Float(v) => Ok(v),
+        _ => Err(Error::PrecisionLoss(ty.name()))
+    }
+}
+
+macro_rules! int {
+    ($value: expr, $ty: expr, $t: ty, $variant: ident) => {
+        <$t>::try_from($value).map(Value::$variant).map_err(|_| Error::Overflow($ty.name()))
+    };
+}
+
+impl Value {
+    /// Converts this value to the given type, failing instead of losing information.
+    pub fn convert(&self, ty: ValueType) -> Result<Value, Error> {
+        let mismatch = || mismatch(self.get_type(), ty);
+        if self.get_type() == ty {
+            return Ok(self.clone());
+        }
+        let n = number(self).ok_or_else(mismatch)?;
+        if is_integer(ty) {
+            let v = to_i128(&n, ty)?;
+            return match ty {
+                ValueType::Int8 => int!(v, ty, i8, Int8),
+                ValueType::UInt8 => int!(v, ty, u8, UInt8),
+                ValueType::Int16 => int!(v, ty, i16, Int16),
+                ValueType::UInt16 => int!(v, ty, u16, UInt16),
+                ValueType::Int32 => int!(v, ty, i32, Int32),
+                ValueType::UInt32 => int!(v, ty, u32, UInt32),
+                ValueType::Int64 => int!(v, ty, i64, Int64),
+                _ => int!(v, ty, u64, UInt64)
+            };
+        }
+        match ty {
+            ValueType::Double => to_f64(&n, ty).map(Value::Double),
+            ValueType::Float => {
+                let v = to_f64(&n, ty)?;
+                let f = v as f32;
+                match f as f64 == v || v.is_nan() {
+                    true => Ok(Value::Float(f)),
+                    false if v.is_finite() && f.is_infinite() => Err(Error::Overflow(ty.name())),
+                    false => Err(Error::PrecisionLoss(ty.name()))
+                }
+            },
+            _ => Err(mismatch())
+        }
+    }
+
+    /// Converts this value to the given type the way the unchecked accessors do.
+    pub fn coerce(&self, ty: ValueType) -> Value {
+        match ty {
+            ValueType::Null => Value::Null,
+            ValueType::Int8 => Value::Int8(self.as_i64() as _),
+            ValueType::UInt8 => Value::UInt8(self.as_u64() as _),
+            ValueType::Int16 => Value::Int16(self.as_i64() as _),
+            ValueType::UInt16 => Value::UInt16(self.as_u64() as _),
+            ValueType::Int32 => Value::Int32(self.as_i64() as _),
+            ValueType::UInt32 => Value::UInt32(self.as_u64() as _),
+            ValueType::Int64 => Value::Int64(self.as_i64()),
+            ValueType::UInt64 => Value::UInt64(self.as_u64()),
+            ValueType::Float => Value::Float(self.as_f64() as _),
+            ValueType::Double => Value::Double(self.as_f64()),
+            ValueType::Boolean => Value::Boolean(self.as_bool()),
+            ValueType::String => Value::String(CString::new(self.as_str()).unwrap().into())
+        }
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Structural diff between Node trees.
+//!
+//! Children of objects are matched by name and children of arrays, which are all unnamed, by the
+//! longest common subsequence of equal elements. Unmatched children with the same content are
+//! reported as moves: a renamed object entry or a reordered array element.
+//!
+//! Paths use the syntax of the query module. Removed entries and the source of moves are given
+//! with their path in the old tree, every other change with its path in the new tree.
+
+use safer_ffi::prelude::*;
+use crate::tree::model::{Node, Value};
+use crate::tree::query::push_segment;
+
+#[derive_ReprC]
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ChangeKind {
+    Added = 0,
+    Removed,
+    Changed,
+    Moved
+}
+
+pub struct NodeChange {
+    pub kind: ChangeKind,
+    pub path: String,
+    pub from: Option<String>,
+    pub old: Option<Value>,
+    pub new: Option<Value>
+}
+
+/// How a child of the old node relates to a child of the new node, by index.
+#[derive(Copy, Clone)]
+pub enum Pair {
+    Same(usize, usize),
+    Changed(usize, usize),
+    Moved(usize, usize),
+    Removed(usize),
+    Added(usize)
+}
+
+// Above this many cells the LCS table is not built and array elements are matched by index.
+const MAX_LCS_CELLS: usize = 1 << 22;
+
+/// Returns true when both nodes hold the same value and children, whatever their names.
+pub fn same_content(a: &Node, b: &Node) -> bool {
+    a.value == b.value && a.children == b.children
+}
+
+pub fn is_array(node: &Node) -> bool {
+    node.children.iter().all(|v| v.name.to_str().is_empty())
+}
+
+fn child_path(parent: &str, index: usize, child: &Node) -> String {
+    let mut path = String::from(parent);
+    push_segment(&mut path, index, child);
+    path
+}
+
+fn find_match(nodes: &[Node], index: usize, child: &Node) -> Option<usize> {
+    match child.name.to_str() {
+        "" => nodes.get(index).filter(|v| v.name.to_str().is_empty()).map(|_| index),
+        name => nodes.iter().position(|v| v.name.to_str() == name)
+    }
+}
+
+fn match_by_name(a: &[Node], b: &[Node]) -> Vec<Pair> {
+    let mut pairs = Vec::new();
+    let mut matched = vec![false; b.len()];
+    for (i, child) in a.iter().enumerate() {
+        match find_match(b, i, child) {
+            Some(j) => {
+                matched[j] = true;
+                pairs.push(match child == &b[j] {
+                    true => Pair::Same(i, j),
+                    false => Pair::Changed(i, j)
+                });
+            },
+            None => pairs.push(Pair::Removed(i))
+        }
+    }
+    pairs.extend((0..b.len()).filter(|j| !matched[*j]).map(Pair::Added));
+    pairs
+}
+
+fn lcs(a: &[Node], b: &[Node]) -> Vec<(usize, usize)> {
+    let width = b.len() + 1;
+    let mut table = vec![0u32; (a.len() + 1) * width];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            table[i * width + j] = match a[i] == b[j] {
+                true => table[(i + 1) * width + j + 1] + 1,
+                false => table[(i + 1) * width + j].max(table[i * width + j + 1])
+            };
+        }
+    }
+    let mut anchors = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            anchors.push((i, j));
+            i += 1;
+            j += 1;
+        } else if table[(i + 1) * width + j] >= table[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    anchors
+}
+
+fn match_array(a: &[Node], b: &[Node]) -> Vec<Pair> {
+    let anchors = lcs(a, b);
+    let mut pairs: Vec<Pair> = anchors.iter().map(|(i, j)| Pair::Same(*i, *j)).collect();
+    let mut removed: Vec<usize> = (0..a.len()).filter(|i| !anchors.iter().any(|v| v.0 == *i)).collect();
+    let mut added: Vec<usize> = (0..b.len()).filter(|j| !anchors.iter().any(|v| v.1 == *j)).collect();
+    removed.retain(|i| match added.iter().position(|j| same_content(&a[*i], &b[*j])) {
+        Some(k) => {
+            pairs.push(Pair::Moved(*i, added.remove(k)));
+            false
+        },
+        None => true
+    });
+    // Remaining elements which sit between the same two anchors are changed in place.
+    let gap = |index: usize, side: fn(&(usize, usize)) -> usize| anchors.iter().filter(|v| side(v) < index).count();
+    for i in removed {
+        let k = added.iter().position(|j| gap(*j, |v| v.1) == gap(i, |v| v.0));
+        match k {
+            Some(k) => pairs.push(Pair::Changed(i, added.remove(k))),
+            None => pairs.push(Pair::Removed(i))
+        }
+    }
+    pairs.extend(added.into_iter().map(Pair::Added));
+    pairs
+}
+
+/// Matches the children of `a` against the children of `b`.
+pub fn match_children(a: &Node, b: &Node) -> Vec<Pair> {
+    let (ca, cb) = (&a.children, &b.children);
+    let mut pairs = match is_array(a) && is_array(b) && ca.len() * cb.len() <= MAX_LCS_CELLS {
+        true => match_array(ca, cb),
+        false => {
+            let mut pairs = match_by_name(ca, cb);
+            let mut added: Vec<usize> = pairs.iter().filter_map(|v| match v {
+                Pair::Added(j) => Some(*j),
+                _ => None
+            }).collect();
+            for pair in &mut pairs {
+                if let Pair::Removed(i) = *pair {
+                    if let Some(k) = added.iter().position(|j| same_content(&ca[i], &cb[*j])) {
+                        *pair = Pair::Moved(i, added.remove(k));
+                    }
+                }
+            }
+            pairs.retain(|v| !matches!(v, Pair::Added(j) if !added.contains(j)));
+            pairs
+        }
+    };
+    pairs.sort_by_key(|v| match *v {
+        Pair::Removed(i) => (0, i),
+        Pair::Same(_, j) | Pair::Changed(_, j) | Pair::Moved(_, j) | Pair::Added(j) => (1, j)
+    });
+    pairs
+}
+
+fn diff_node(path: &str, a: &Node, b: &Node, out: &mut Vec<NodeChange>) {
+    if a.value != b.value {
+        out.push(NodeChange {
+            kind: ChangeKind::Changed,
+            path: path.into(),
+            from: None,
+            old: Some(a.value.clone()),
+            new: Some(b.value.clone())
+        });
+    }
+    for pair in match_children(a, b) {
+        match pair {
+            Pair::Same(_, _) => (),
+            Pair::Changed(i, j) => diff_node(&child_path(path, j, &b.children[j]), &a.children[i], &b.children[j], out),
+            Pair::Moved(i, j) => out.push(NodeChange {
+                kind: ChangeKind::Moved,
+                path: child_path(path, j, &b.children[j]),
+                from: Some(child_path(path, i, &a.children[i])),
+                old: Some(a.children[i].value.clone()),
+                new: Some(b.children[j].value.clone())
+            }),
+            Pair::Removed(i) => out.push(NodeChange {
+                kind: ChangeKind::Removed,
+                path: child_path(path, i, &a.children[i]),
+                from: None,
+                old: Some(a.children[i].value.clone()),
+                new: None
+            }),
+            Pair::Added(j) => out.push(NodeChange {
+                kind: ChangeKind::Added,
+                path: child_path(path, j, &b.children[j]),
+                from: None,
+                old: None,
+                new: Some(b.children[j].value.clone())
+            })
+        }
+    }
+}
+
+pub fn diff(a: &Node, b: &Node) -> Vec<NodeChange> {
+    let mut out = Vec::new();
+    diff_node("$", a, b, &mut out);
+    out
+}
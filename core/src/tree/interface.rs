@@ -27,7 +27,11 @@
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::tree::model::{Node, Value, ValueType};
+use crate::tree::text::{self, FormatOptions};
+use crate::tree::cmp::stable_hash;
+use std::ffi::CString;
 use safer_ffi::prelude::*;
+use crate::error::unwrap_result;
 
 #[ffi_export]
 pub fn bpx_node_free(node: repr_c::Box<Node>) {
@@ -54,6 +58,16 @@ pub fn bpx_node_get_children(node: &Node) -> c_slice::Ref<'_, Node> {
     node.children.as_slice().into()
 }
 
+#[ffi_export]
+pub fn bpx_node_equals(a: &Node, b: &Node) -> bool {
+    a == b
+}
+
+#[ffi_export]
+pub fn bpx_node_hash(node: &Node) -> u64 {
+    stable_hash(node)
+}
+
 #[ffi_export]
 pub fn bpx_value_get_type(value: &Value) -> ValueType {
     value.get_type()
@@ -64,6 +78,21 @@ pub fn bpx_value_is_null(value: &Value) -> bool {
     value.get_type() == ValueType::Null
 }
 
+#[ffi_export]
+pub fn bpx_value_compare(a: &Value, b: &Value) -> i32 {
+    a.cmp(b) as i32
+}
+
+#[ffi_export]
+pub fn bpx_value_equals(a: &Value, b: &Value) -> bool {
+    a == b
+}
+
+#[ffi_export]
+pub fn bpx_value_hash(value: &Value) -> u64 {
+    stable_hash(value)
+}
+
 #[ffi_export]
 pub fn bpx_value_set_null(value: &mut Value) {
     *value = Value::Null;
@@ -137,7 +166,79 @@ pub fn bpx_value_get_string(value: &Value) -> Option<char_p::Ref<'_>> {
     }
 }
 
+#[ffi_export]
+pub unsafe fn bpx_value_format_options_default(options: *mut FormatOptions) {
+    *options = FormatOptions::default();
+}
+
+#[ffi_export]
+pub fn bpx_value_format(value: &Value, options: Option<&FormatOptions>) -> char_p::Box {
+    let text = match options {
+        Some(options) => text::format(value, options),
+        None => text::format(value, &FormatOptions::default())
+    };
+    CString::new(text).unwrap().into()
+}
+
+#[ffi_export]
+pub fn bpx_value_format_free(text: char_p::Box) {
+    drop(text);
+}
+
+#[ffi_export]
+pub fn bpx_value_parse(value: &mut Value, text: char_p::Ref<'_>, ty: ValueType) -> bool {
+    match unwrap_result(text::parse(text.to_str(), ty)) {
+        Some(v) => {
+            *value = v;
+            true
+        },
+        None => false
+    }
+}
+
 #[ffi_export]
 pub fn bpx_value_set_string(value: &mut Value, v: char_p::Ref<'_>) {
     *value = Value::String(v.to_owned())
 }
+
+macro_rules! fn_try_get_num {
+    ($($name: ident => $vname: ident: $t: ty),*) => {
+        $(
+            #[ffi_export]
+            pub fn $name(value: &Value, out: &mut $t) -> bool {
+                match unwrap_result(value.convert(ValueType::$vname)) {
+                    Some(Value::$vname(v)) => {
+                        *out = v;
+                        true
+                    },
+                    _ => false
+                }
+            }
+        )*
+    };
+}
+
+fn_try_get_num! {
+    bpx_value_try_get_int8 => Int8: i8,
+    bpx_value_try_get_uint8 => UInt8: u8,
+    bpx_value_try_get_int16 => Int16: i16,
+    bpx_value_try_get_uint16 => UInt16: u16,
+    bpx_value_try_get_int32 => Int32: i32,
+    bpx_value_try_get_uint32 => UInt32: u32,
+    bpx_value_try_get_int64 => Int64: i64,
+    bpx_value_try_get_uint64 => UInt64: u64,
+    bpx_value_try_get_float => Float: f32,
+    bpx_value_try_get_double => Double: f64,
+    bpx_value_try_get_boolean => Boolean: bool
+}
+
+#[ffi_export]
+pub fn bpx_value_convert(value: &mut Value, ty: ValueType) -> bool {
+    match unwrap_result(value.convert(ty)) {
+        Some(v) => {
+            *value = v;
+            true
+        },
+        None => false
+    }
+}
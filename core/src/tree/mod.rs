@@ -28,4 +28,11 @@
 
 pub mod model;
 pub mod util;
+pub mod diff;
+pub mod query;
+pub mod schema;
+pub mod convert;
+pub mod text;
+pub mod cmp;
+pub mod patch;
 mod interface;
@@ -26,6 +26,7 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::fmt::{Display, Formatter};
 use safer_ffi::prelude::*;
 
 #[derive_ReprC]
@@ -66,6 +67,26 @@ pub enum ValueType {
     String
 }
 
+impl ValueType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ValueType::Null => "null",
+            ValueType::Int8 => "int8",
+            ValueType::UInt8 => "uint8",
+            ValueType::Int16 => "int16",
+            ValueType::UInt16 => "uint16",
+            ValueType::Int32 => "int32",
+            ValueType::UInt32 => "uint32",
+            ValueType::Int64 => "int64",
+            ValueType::UInt64 => "uint64",
+            ValueType::Float => "float",
+            ValueType::Double => "double",
+            ValueType::Boolean => "bool",
+            ValueType::String => "string"
+        }
+    }
+}
+
 impl Value {
     pub fn get_type(&self) -> ValueType {
         match self {
@@ -97,7 +118,7 @@ impl Value {
             Value::UInt64(v) => *v as _,
             Value::Float(v) => *v as _,
             Value::Double(v) => *v as _,
-            Value::Boolean(v) => if *v { 1 } else { 0 }
+            Value::Boolean(v) => *v as _,
             _ => 0
         }
     }
@@ -114,7 +135,7 @@ impl Value {
             Value::UInt64(v) => *v,
             Value::Float(v) => *v as _,
             Value::Double(v) => *v as _,
-            Value::Boolean(v) => if *v { 1 } else { 0 }
+            Value::Boolean(v) => *v as _,
             _ => 0
         }
     }
@@ -131,7 +152,7 @@ impl Value {
             Value::UInt64(v) => *v as _,
             Value::Float(v) => *v as _,
             Value::Double(v) => *v,
-            Value::Boolean(v) => if *v { 1.0 } else { 0.0 }
+            Value::Boolean(v) => *v as u8 as _,
             _ => 0.0
         }
     }
@@ -161,6 +182,26 @@ impl Value {
     }
 }
 
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Null => f.write_str("null"),
+            Value::Int8(v) => write!(f, "{}", v),
+            Value::UInt8(v) => write!(f, "{}", v),
+            Value::Int16(v) => write!(f, "{}", v),
+            Value::UInt16(v) => write!(f, "{}", v),
+            Value::Int32(v) => write!(f, "{}", v),
+            Value::UInt32(v) => write!(f, "{}", v),
+            Value::Int64(v) => write!(f, "{}", v),
+            Value::UInt64(v) => write!(f, "{}", v),
+            Value::Float(v) => write!(f, "{}", v),
+            Value::Double(v) => write!(f, "{}", v),
+            Value::Boolean(v) => write!(f, "{}", v),
+            Value::String(v) => f.write_str(v.to_str())
+        }
+    }
+}
+
 #[derive_ReprC]
 #[repr(opaque)]
 pub struct Node {
@@ -169,3 +210,14 @@ pub struct Node {
     pub details: Option<repr_c::Box<Node>>,
     pub children: Vec<Node>
 }
+
+impl Clone for Node {
+    fn clone(&self) -> Self {
+        Node {
+            name: self.name.clone(),
+            value: self.value.clone(),
+            details: self.details.as_deref().map(|v| Box::new(v.clone()).into()),
+            children: self.children.clone()
+        }
+    }
+}
@@ -0,0 +1,356 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Patches over Node trees, modelled after JSON Patch.
+//!
+//! A patch is a JSON array of operations applied in order, each an object with an `op` member:
+//!
+//! * `{"op": "add", "path": P, "value": V}` inserts V at P. When P ends with an index the value
+//!   is inserted before the node at that index, or appended when the index is the number of
+//!   children. When P ends with a name an existing child of that name is replaced.
+//! * `{"op": "remove", "path": P}` removes the node at P.
+//! * `{"op": "replace", "path": P, "value": V}` replaces the value and children of the node at P.
+//! * `{"op": "move", "from": F, "path": P}` removes the node at F and adds it at P.
+//! * `{"op": "copy", "from": F, "path": P}` adds a copy of the node at F at P.
+//! * `{"op": "test", "path": P, "value": V}` fails the patch unless the node at P equals V.
+//!
+//! Paths use the syntax of the query module and must designate a single node. Values use the
+//! JSON convention of the BPXSD JSON module. A patch applies entirely or not at all.
+
+use std::ffi::CString;
+use std::io::{Seek, SeekFrom, Write};
+use bp3d_util::simple_error;
+use bpx::core::{AutoSectionData, SectionData};
+use safer_ffi::prelude::*;
+use crate::bpxsd::dictionary::Dictionary;
+use crate::bpxsd::json;
+use crate::bpxsd::reader::{self, Reader, ReaderOptions};
+use crate::bpxsd::writer;
+use crate::common::{read_all, try_with_section_mut, Container};
+use crate::error::unwrap_result;
+use crate::tree::diff::{is_array, match_children, same_content, Pair};
+use crate::tree::model::Node;
+use crate::tree::query::{self, get, get_mut, locate_mut, push_name, Location};
+
+simple_error! {
+    pub Error {
+        InvalidOperation(String) => "invalid patch operation: {}",
+        IndexOutOfRange(usize) => "index {} is out of range",
+        TestFailed(String) => "test failed at '{}'",
+        (impl From) Query(query::Error) => "{}",
+        (impl From) Json(json::Error) => "{}",
+        (impl From) Reader(reader::Error) => "{}",
+        (impl From) Writer(writer::Error) => "{}",
+        (impl From) Io(std::io::Error) => "io error: {}"
+    }
+}
+
+pub enum Operation {
+    Add { path: String, value: Node },
+    Remove { path: String },
+    Replace { path: String, value: Node },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: Node }
+}
+
+#[derive_ReprC]
+#[repr(opaque)]
+#[derive(Default)]
+pub struct Patch {
+    operations: Vec<Operation>
+}
+
+fn take(root: &mut Node, path: &str) -> Result<Node, Error> {
+    let (parent, location) = locate_mut(root, path)?;
+    let index = match location {
+        Location::Name(name) => parent.children.iter().position(|v| v.name.to_str() == name),
+        Location::Index(index) => Some(index).filter(|v| *v < parent.children.len())
+    };
+    match index {
+        Some(index) => Ok(parent.children.remove(index)),
+        None => Err(query::Error::NotFound(path.into()).into())
+    }
+}
+
+fn insert(root: &mut Node, path: &str, mut node: Node) -> Result<(), Error> {
+    let (parent, location) = locate_mut(root, path)?;
+    match location {
+        Location::Name(name) => {
+            node.name = CString::new(name.replace('\0', "")).unwrap().into();
+            match parent.children.iter_mut().find(|v| v.name.to_str() == name) {
+                Some(v) => *v = node,
+                None => parent.children.push(node)
+            }
+        },
+        Location::Index(index) => {
+            if index > parent.children.len() {
+                return Err(Error::IndexOutOfRange(index));
+            }
+            node.name = CString::default().into();
+            parent.children.insert(index, node);
+        }
+    }
+    Ok(())
+}
+
+impl Operation {
+    fn apply(&self, root: &mut Node) -> Result<(), Error> {
+        match self {
+            Operation::Add { path, value } => insert(root, path, value.clone()),
+            Operation::Remove { path } => take(root, path).map(|_| ()),
+            Operation::Replace { path, value } => {
+                let target = get_mut(root, path)?;
+                target.value = value.value.clone();
+                target.children = value.children.clone();
+                target.details = value.details.clone();
+                Ok(())
+            },
+            Operation::Move { from, path } => {
+                let node = take(root, from)?;
+                insert(root, path, node)
+            },
+            Operation::Copy { from, path } => {
+                let node = get(root, from)?.clone();
+                insert(root, path, node)
+            },
+            Operation::Test { path, value } => match same_content(get(root, path)?, value) {
+                true => Ok(()),
+                false => Err(Error::TestFailed(path.clone()))
+            }
+        }
+    }
+
+    fn from_json(index: usize, value: &serde_json::Value) -> Result<Operation, Error> {
+        let member = |name: &str| value.get(name)
+            .ok_or_else(|| Error::InvalidOperation(format!("operation {} has no '{}' member", index, name)));
+        let string = |name: &str| member(name)?.as_str().map(String::from)
+            .ok_or_else(|| Error::InvalidOperation(format!("'{}' of operation {} is not a string", name, index)));
+        let node = || Ok::<_, Error>(json::to_node("", member("value")?)?);
+        Ok(match &*string("op")? {
+            "add" => Operation::Add { path: string("path")?, value: node()? },
+            "remove" => Operation::Remove { path: string("path")? },
+            "replace" => Operation::Replace { path: string("path")?, value: node()? },
+            "move" => Operation::Move { from: string("from")?, path: string("path")? },
+            "copy" => Operation::Copy { from: string("from")?, path: string("path")? },
+            "test" => Operation::Test { path: string("path")?, value: node()? },
+            op => return Err(Error::InvalidOperation(format!("unknown operation '{}'", op)))
+        })
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let (op, path, from, value) = match self {
+            Operation::Add { path, value } => ("add", path, None, Some(value)),
+            Operation::Remove { path } => ("remove", path, None, None),
+            Operation::Replace { path, value } => ("replace", path, None, Some(value)),
+            Operation::Move { from, path } => ("move", path, Some(from), None),
+            Operation::Copy { from, path } => ("copy", path, Some(from), None),
+            Operation::Test { path, value } => ("test", path, None, Some(value))
+        };
+        let mut object = serde_json::Map::new();
+        object.insert("op".into(), op.into());
+        if let Some(from) = from {
+            object.insert("from".into(), from.as_str().into());
+        }
+        object.insert("path".into(), path.as_str().into());
+        if let Some(value) = value {
+            object.insert("value".into(), json::from_node(value));
+        }
+        serde_json::Value::Object(object)
+    }
+}
+
+fn name_path(parent: &str, child: &Node) -> String {
+    let mut path = String::from(parent);
+    push_name(&mut path, child.name.to_str());
+    path
+}
+
+impl Patch {
+    fn replace(&mut self, path: &str, value: &Node) {
+        self.operations.push(Operation::Replace { path: path.into(), value: value.clone() });
+    }
+
+    fn push_array(&mut self, path: &str, a: &Node, b: &Node, pairs: &[Pair]) {
+        // Replay the operations on the list of old indices so that every path is valid at the
+        // time its operation runs. `None` stands for an element added by the patch.
+        let mut current: Vec<Option<usize>> = (0..a.children.len()).map(Some).collect();
+        for pair in pairs.iter().rev() {
+            if let Pair::Removed(i) = *pair {
+                let pos = current.iter().position(|v| *v == Some(i)).unwrap();
+                self.operations.push(Operation::Remove { path: format!("{}[{}]", path, pos) });
+                current.remove(pos);
+            }
+        }
+        for (j, child) in b.children.iter().enumerate() {
+            let target = format!("{}[{}]", path, j);
+            let source = pairs.iter().find_map(|v| match *v {
+                Pair::Same(i, k) | Pair::Changed(i, k) | Pair::Moved(i, k) if k == j => Some(i),
+                _ => None
+            });
+            let Some(i) = source else {
+                self.operations.push(Operation::Add { path: target, value: child.clone() });
+                current.insert(j, None);
+                continue;
+            };
+            let pos = current.iter().position(|v| *v == Some(i)).unwrap();
+            if pos != j {
+                self.operations.push(Operation::Move { from: format!("{}[{}]", path, pos), path: target.clone() });
+                current.remove(pos);
+                current.insert(j, Some(i));
+            }
+            if &a.children[i] != child {
+                self.push_node(&target, &a.children[i], child);
+            }
+        }
+    }
+
+    fn push_object(&mut self, path: &str, a: &Node, b: &Node, pairs: &[Pair]) {
+        for pair in pairs {
+            match *pair {
+                Pair::Same(_, _) => (),
+                Pair::Changed(i, j) => self.push_node(&name_path(path, &b.children[j]), &a.children[i], &b.children[j]),
+                Pair::Moved(i, j) => self.operations.push(Operation::Move {
+                    from: name_path(path, &a.children[i]),
+                    path: name_path(path, &b.children[j])
+                }),
+                Pair::Removed(i) => self.operations.push(Operation::Remove { path: name_path(path, &a.children[i]) }),
+                Pair::Added(j) => self.operations.push(Operation::Add {
+                    path: name_path(path, &b.children[j]),
+                    value: b.children[j].clone()
+                })
+            }
+        }
+    }
+
+    fn push_node(&mut self, path: &str, a: &Node, b: &Node) {
+        let (ta, tb) = (writer::type_code(a), writer::type_code(b));
+        if ta != tb || a.value != b.value || a.children.is_empty() || b.children.is_empty() {
+            return self.replace(path, b);
+        }
+        let pairs = match_children(a, b);
+        let named = |v: &Node| v.children.iter().all(|v| !v.name.to_str().is_empty());
+        if is_array(a) && is_array(b) {
+            self.push_array(path, a, b, &pairs);
+        } else if named(a) && named(b) {
+            self.push_object(path, a, b, &pairs);
+        } else {
+            self.replace(path, b);
+        }
+    }
+
+    /// Builds the patch which turns `a` into `b`.
+    pub fn create(a: &Node, b: &Node) -> Patch {
+        let mut patch = Patch::default();
+        if a != b {
+            patch.push_node("$", a, b);
+        }
+        patch
+    }
+
+    pub fn from_json(value: &serde_json::Value) -> Result<Patch, Error> {
+        let operations = value.as_array()
+            .ok_or_else(|| Error::InvalidOperation("patch is not an array".into()))?
+            .iter().enumerate()
+            .map(|(index, v)| Operation::from_json(index, v))
+            .collect::<Result<_, _>>()?;
+        Ok(Patch { operations })
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::Value::Array(self.operations.iter().map(Operation::to_json).collect())
+    }
+
+    pub fn apply(&self, root: &mut Node) -> Result<(), Error> {
+        let mut copy = root.clone();
+        for operation in &self.operations {
+            operation.apply(&mut copy)?;
+        }
+        *root = copy;
+        Ok(())
+    }
+
+    fn apply_to_section(&self, data: &mut AutoSectionData, options: &ReaderOptions, dictionary: Option<&Dictionary>) -> Result<(), Error> {
+        let bytes = read_all(data)?;
+        let mut root = Reader::new(&bytes, options, dictionary).read_root()?;
+        self.apply(&mut root)?;
+        let bytes = writer::write(&root)?;
+        let size = data.size();
+        data.truncate(size)?;
+        data.seek(SeekFrom::Start(0))?;
+        data.write_all(&bytes)?;
+        data.seek(SeekFrom::Start(0))?;
+        Ok(())
+    }
+}
+
+#[ffi_export]
+pub fn bpx_patch_create(a: &Node, b: &Node) -> repr_c::Box<Patch> {
+    Box::new(Patch::create(a, b)).into()
+}
+
+#[ffi_export]
+pub fn bpx_patch_from_json(json: char_p::Ref<'_>) -> Option<repr_c::Box<Patch>> {
+    let value = unwrap_result(serde_json::from_str(json.to_str()).map_err(json::Error::from))?;
+    unwrap_result(Patch::from_json(&value)).map(|v| Box::new(v).into())
+}
+
+#[ffi_export]
+pub fn bpx_patch_to_json(patch: &Patch, pretty: bool) -> char_p::Box {
+    let value = patch.to_json();
+    let text = match pretty {
+        true => serde_json::to_string_pretty(&value),
+        false => serde_json::to_string(&value)
+    };
+    CString::new(text.unwrap()).unwrap().into()
+}
+
+#[ffi_export]
+pub fn bpx_patch_json_free(json: char_p::Box) {
+    drop(json);
+}
+
+#[ffi_export]
+pub fn bpx_patch_get_count(patch: &Patch) -> usize {
+    patch.operations.len()
+}
+
+#[ffi_export]
+pub fn bpx_patch_apply(patch: &Patch, node: &mut Node) -> bool {
+    unwrap_result(patch.apply(node)).is_some()
+}
+
+#[ffi_export]
+pub fn bpx_patch_apply_to_section(patch: &Patch, container: &Container, handle: u32, options: &ReaderOptions, dictionary: Option<&Dictionary>) -> bool {
+    try_with_section_mut(container, handle, |data| patch.apply_to_section(data, options, dictionary)).is_some()
+}
+
+#[ffi_export]
+pub fn bpx_patch_free(patch: repr_c::Box<Patch>) {
+    drop(patch);
+}
@@ -0,0 +1,496 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! A small path and query language over Node trees.
+//!
+//! A query is a sequence of segments applied from the node it is run on, optionally written `$`:
+//!
+//! * `.name` or `['name']` selects the child with the given name (the leading `.` may be omitted
+//!   for the first segment).
+//! * `[2]` selects the child at the given index.
+//! * `.*` or `[*]` selects all children.
+//! * `..name` or `..*` selects matching nodes at any depth below the current nodes.
+//! * `[?@.hp > 10]` selects the children for which a filter holds. The operand is `@` followed
+//!   by an optional relative path made of names and indices; the operators are `==`, `!=`, `<`,
+//!   `<=`, `>` and `>=`, and the literal is a number, a quoted string, `true`, `false` or `null`.
+//!   A filter without an operator checks that the operand exists.
+//!
+//! Numbers compare by value regardless of their exact type. Paths returned by queries use the
+//! same syntax so they can be fed back into a query.
+
+use bp3d_util::simple_error;
+use safer_ffi::prelude::*;
+use crate::error::unwrap_result;
+use crate::tree::model::{Node, Value};
+
+simple_error! {
+    pub Error {
+        Syntax(String) => "syntax error: {}",
+        NotFound(String) => "no node matches '{}'",
+        Ambiguous(usize) => "path matches {} nodes"
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge
+}
+
+enum Literal {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String)
+}
+
+enum Step {
+    Name(String),
+    Index(usize)
+}
+
+enum Segment {
+    Child(String),
+    Index(usize),
+    Wildcard,
+    Descend(Option<String>),
+    Filter(Vec<Step>, Option<(Op, Literal)>)
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize
+}
+
+impl<'a> Parser<'a> {
+    fn error<T>(&self, msg: &str) -> Result<T, Error> {
+        Err(Error::Syntax(format!("{} at offset {}", msg, self.pos)))
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn skip_spaces(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        if self.rest().starts_with(s) {
+            self.pos += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn name(&mut self) -> Result<String, Error> {
+        let rest = self.rest();
+        let len = rest.find(['.', '[', ']', ' ', '=', '!', '<', '>']).unwrap_or(rest.len());
+        if len == 0 {
+            return self.error("expected a name");
+        }
+        self.pos += len;
+        Ok(rest[..len].into())
+    }
+
+    fn quoted(&mut self) -> Result<String, Error> {
+        let quote = match self.rest().chars().next() {
+            Some(c) if c == '\'' || c == '"' => c,
+            _ => return self.error("expected a quoted string")
+        };
+        self.pos += 1;
+        let mut out = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some((_, c)) => out.push(c),
+                    None => break
+                },
+                c if c == quote => {
+                    self.pos += i + 1;
+                    return Ok(out);
+                },
+                c => out.push(c)
+            }
+        }
+        self.error("unterminated string")
+    }
+
+    fn index(&mut self) -> Result<usize, Error> {
+        let rest = self.rest();
+        let len = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        match rest[..len].parse() {
+            Ok(v) => {
+                self.pos += len;
+                Ok(v)
+            },
+            Err(_) => self.error("expected an index")
+        }
+    }
+
+    fn literal(&mut self) -> Result<Literal, Error> {
+        self.skip_spaces();
+        if self.rest().starts_with(['\'', '"']) {
+            return self.quoted().map(Literal::String);
+        }
+        for (keyword, literal) in [("null", Literal::Null), ("true", Literal::Bool(true)), ("false", Literal::Bool(false))] {
+            if self.eat(keyword) {
+                return Ok(literal);
+            }
+        }
+        let rest = self.rest();
+        let len = rest.find([']', ' ']).unwrap_or(rest.len());
+        match rest[..len].parse() {
+            Ok(v) => {
+                self.pos += len;
+                Ok(Literal::Number(v))
+            },
+            Err(_) => self.error("expected a literal")
+        }
+    }
+
+    fn filter(&mut self) -> Result<Segment, Error> {
+        self.skip_spaces();
+        if !self.eat("@") {
+            return self.error("expected '@'");
+        }
+        let mut steps = Vec::new();
+        loop {
+            if self.eat(".") {
+                steps.push(Step::Name(self.name()?));
+            } else if self.rest().starts_with("['") || self.rest().starts_with("[\"") {
+                self.pos += 1;
+                steps.push(Step::Name(self.quoted()?));
+                if !self.eat("]") {
+                    return self.error("expected ']'");
+                }
+            } else if self.eat("[") {
+                steps.push(Step::Index(self.index()?));
+                if !self.eat("]") {
+                    return self.error("expected ']'");
+                }
+            } else {
+                break;
+            }
+        }
+        self.skip_spaces();
+        let ops = [("==", Op::Eq), ("!=", Op::Ne), ("<=", Op::Le), (">=", Op::Ge), ("<", Op::Lt), (">", Op::Gt)];
+        let op = ops.iter().find(|(s, _)| self.rest().starts_with(s)).copied();
+        let condition = match op {
+            Some((s, op)) => {
+                self.pos += s.len();
+                Some((op, self.literal()?))
+            },
+            None => None
+        };
+        self.skip_spaces();
+        Ok(Segment::Filter(steps, condition))
+    }
+
+    fn bracket(&mut self) -> Result<Segment, Error> {
+        self.skip_spaces();
+        let segment = if self.eat("*") {
+            Segment::Wildcard
+        } else if self.eat("?") {
+            self.filter()?
+        } else if self.rest().starts_with(['\'', '"']) {
+            Segment::Child(self.quoted()?)
+        } else {
+            Segment::Index(self.index()?)
+        };
+        self.skip_spaces();
+        if !self.eat("]") {
+            return self.error("expected ']'");
+        }
+        Ok(segment)
+    }
+
+    fn parse(mut self) -> Result<Vec<Segment>, Error> {
+        let mut segments = Vec::new();
+        self.skip_spaces();
+        self.eat("$");
+        if !self.rest().is_empty() && !self.rest().starts_with(['.', '[']) {
+            segments.push(Segment::Child(self.name()?));
+        }
+        while !self.rest().trim_end().is_empty() {
+            if self.eat("..") {
+                segments.push(match self.eat("*") {
+                    true => Segment::Descend(None),
+                    false => Segment::Descend(Some(self.name()?))
+                });
+            } else if self.eat(".") {
+                segments.push(match self.eat("*") {
+                    true => Segment::Wildcard,
+                    false => Segment::Child(self.name()?)
+                });
+            } else if self.eat("[") {
+                segments.push(self.bracket()?);
+            } else {
+                return self.error("expected '.' or '['");
+            }
+        }
+        Ok(segments)
+    }
+}
+
+fn compare(value: &Value, op: Op, literal: &Literal) -> bool {
+    let ordering = match (value, literal) {
+        (Value::Null, Literal::Null) => Some(std::cmp::Ordering::Equal),
+        (Value::Boolean(a), Literal::Bool(b)) => Some(a.cmp(b)),
+        (Value::String(a), Literal::String(b)) => Some(a.to_str().cmp(&**b)),
+        (Value::Null | Value::Boolean(_) | Value::String(_), _) => None,
+        (Value::Int64(a), Literal::Number(b)) => (*a as f64).partial_cmp(b),
+        (Value::UInt64(a), Literal::Number(b)) => (*a as f64).partial_cmp(b),
+        (v, Literal::Number(b)) => v.as_f64().partial_cmp(b),
+        _ => None
+    };
+    match ordering {
+        Some(ordering) => match op {
+            Op::Eq => ordering.is_eq(),
+            Op::Ne => ordering.is_ne(),
+            Op::Lt => ordering.is_lt(),
+            Op::Le => ordering.is_le(),
+            Op::Gt => ordering.is_gt(),
+            Op::Ge => ordering.is_ge()
+        },
+        None => op == Op::Ne
+    }
+}
+
+fn find_child<'a>(node: &'a Node, name: &str) -> Option<(usize, &'a Node)> {
+    node.children.iter().enumerate().find(|(_, v)| v.name.to_str() == name)
+}
+
+fn matches_filter(node: &Node, steps: &[Step], condition: &Option<(Op, Literal)>) -> bool {
+    let mut target = Some(node);
+    for step in steps {
+        target = target.and_then(|v| match step {
+            Step::Name(name) => find_child(v, name).map(|(_, v)| v),
+            Step::Index(index) => v.children.get(*index)
+        });
+    }
+    match (target, condition) {
+        (Some(node), Some((op, literal))) => compare(&node.value, *op, literal),
+        (Some(_), None) => true,
+        (None, _) => false
+    }
+}
+
+fn descend(node: &Node, route: &[usize], name: Option<&str>, out: &mut Vec<Vec<usize>>) {
+    for (index, child) in node.children.iter().enumerate() {
+        let mut route = route.to_vec();
+        route.push(index);
+        if name.is_none() || name == Some(child.name.to_str()) {
+            out.push(route.clone());
+        }
+        descend(child, &route, name, out);
+    }
+}
+
+fn resolve<'a>(root: &'a Node, route: &[usize]) -> &'a Node {
+    route.iter().fold(root, |node, index| &node.children[*index])
+}
+
+fn resolve_mut<'a>(root: &'a mut Node, route: &[usize]) -> &'a mut Node {
+    route.iter().fold(root, |node, index| &mut node.children[*index])
+}
+
+fn evaluate(root: &Node, segments: &[Segment]) -> Vec<Vec<usize>> {
+    let mut current = vec![Vec::new()];
+    for segment in segments {
+        let mut next = Vec::new();
+        for route in &current {
+            let node = resolve(root, route);
+            let mut push = |index: usize| {
+                let mut route = route.clone();
+                route.push(index);
+                next.push(route);
+            };
+            match segment {
+                Segment::Child(name) => {
+                    if let Some((index, _)) = find_child(node, name) {
+                        push(index);
+                    }
+                },
+                Segment::Index(index) => {
+                    if *index < node.children.len() {
+                        push(*index);
+                    }
+                },
+                Segment::Wildcard => (0..node.children.len()).for_each(push),
+                Segment::Filter(steps, condition) => node.children.iter().enumerate()
+                    .filter(|(_, v)| matches_filter(v, steps, condition))
+                    .for_each(|(index, _)| push(index)),
+                Segment::Descend(name) => descend(node, route, name.as_deref(), &mut next)
+            }
+        }
+        next.sort();
+        next.dedup();
+        current = next;
+    }
+    current
+}
+
+fn needs_quotes(name: &str) -> bool {
+    name.is_empty() || name == "*" || name.contains(['.', '[', ']', ' ', '=', '!', '<', '>', '\'', '"', '$'])
+}
+
+/// Appends the path segment selecting `child`, found at `index` in its parent, to `path`.
+pub fn push_segment(path: &mut String, index: usize, child: &Node) {
+    match child.name.to_str() {
+        "" => *path += &format!("[{}]", index),
+        name => push_name(path, name)
+    }
+}
+
+/// Appends the path segment selecting the child named `name` to `path`.
+pub fn push_name(path: &mut String, name: &str) {
+    match needs_quotes(name) {
+        true => *path += &format!("['{}']", name.replace('\\', "\\\\").replace('\'', "\\'")),
+        false => *path += &format!(".{}", name)
+    }
+}
+
+fn route_path(root: &Node, route: &[usize]) -> String {
+    let mut path = String::from("$");
+    let mut node = root;
+    for index in route {
+        node = &node.children[*index];
+        push_segment(&mut path, *index, node);
+    }
+    path
+}
+
+/// Runs a query and returns, for each matching node, its route of child indices from `root` and
+/// its path.
+pub fn query(root: &Node, query: &str) -> Result<Vec<(Vec<usize>, String)>, Error> {
+    let segments = Parser { text: query, pos: 0 }.parse()?;
+    Ok(evaluate(root, &segments).into_iter()
+        .map(|route| {
+            let path = route_path(root, &route);
+            (route, path)
+        })
+        .collect())
+}
+
+fn find_one(root: &Node, path: &str) -> Result<Vec<usize>, Error> {
+    let segments = Parser { text: path, pos: 0 }.parse()?;
+    let mut routes = evaluate(root, &segments);
+    match routes.len() {
+        0 => Err(Error::NotFound(path.into())),
+        1 => Ok(routes.remove(0)),
+        n => Err(Error::Ambiguous(n))
+    }
+}
+
+pub fn get<'a>(root: &'a Node, path: &str) -> Result<&'a Node, Error> {
+    find_one(root, path).map(|route| resolve(root, &route))
+}
+
+pub fn get_mut<'a>(root: &'a mut Node, path: &str) -> Result<&'a mut Node, Error> {
+    find_one(root, path).map(|route| resolve_mut(root, &route))
+}
+
+/// The last segment of a path naming a single node.
+pub enum Location {
+    Name(String),
+    Index(usize)
+}
+
+/// Resolves the parent of the node designated by `path`, which must end with a name or an
+/// index. The node itself does not need to exist.
+pub fn locate_mut<'a>(root: &'a mut Node, path: &str) -> Result<(&'a mut Node, Location), Error> {
+    let mut segments = Parser { text: path, pos: 0 }.parse()?;
+    let location = match segments.pop() {
+        Some(Segment::Child(name)) => Location::Name(name),
+        Some(Segment::Index(index)) => Location::Index(index),
+        _ => return Err(Error::Syntax(format!("'{}' does not end with a name or an index", path)))
+    };
+    let mut routes = evaluate(root, &segments);
+    match routes.len() {
+        0 => Err(Error::NotFound(path.into())),
+        1 => Ok((resolve_mut(root, &routes.remove(0)), location)),
+        n => Err(Error::Ambiguous(n))
+    }
+}
+
+/// The nodes matched by a query, kept as routes of child indices from the queried node. A node is
+/// looked up in the tree passed when it is accessed, so a result never points into a freed tree;
+/// a route which no longer exists after the tree changed gives no node.
+#[derive_ReprC]
+#[repr(opaque)]
+pub struct QueryResult {
+    routes: Vec<Vec<usize>>,
+    paths: Vec<char_p::Box>
+}
+
+#[ffi_export]
+pub fn bpx_node_query(node: &Node, query: char_p::Ref<'_>) -> Option<repr_c::Box<QueryResult>> {
+    let matches = unwrap_result(self::query(node, query.to_str()))?;
+    let (routes, paths) = matches.into_iter()
+        .map(|(route, path)| (route, std::ffi::CString::new(path).unwrap().into()))
+        .unzip();
+    Some(Box::new(QueryResult { routes, paths }).into())
+}
+
+#[ffi_export]
+pub fn bpx_query_result_get_count(result: &QueryResult) -> usize {
+    result.routes.len()
+}
+
+#[ffi_export]
+pub fn bpx_query_result_get_node<'a>(result: &QueryResult, root: &'a Node, index: usize) -> Option<&'a Node> {
+    result.routes.get(index)?.iter().try_fold(root, |node, index| node.children.get(*index))
+}
+
+#[ffi_export]
+pub fn bpx_query_result_get_path(result: &QueryResult, index: usize) -> Option<char_p::Ref<'_>> {
+    result.paths.get(index).map(|v| v.as_ref())
+}
+
+#[ffi_export]
+pub fn bpx_query_result_free(result: repr_c::Box<QueryResult>) {
+    drop(result);
+}
+
+#[ffi_export]
+pub fn bpx_node_get_by_path<'a>(node: &'a Node, path: char_p::Ref<'_>) -> Option<&'a Node> {
+    unwrap_result(get(node, path.to_str()))
+}
+
+#[ffi_export]
+pub fn bpx_node_get_value_by_path_mut<'a>(node: &'a mut Node, path: char_p::Ref<'_>) -> Option<&'a mut Value> {
+    unwrap_result(get_mut(node, path.to_str())).map(|v| &mut v.value)
+}
@@ -0,0 +1,405 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Schema validation for Node trees.
+//!
+//! A schema is described by a JSON object (or an equivalent BPXSD object) with the following keys,
+//! all optional:
+//!
+//! * `type`: a type name or a list of accepted type names. Type names are the value types (`null`,
+//!   `bool`, `int8`, `uint8`, `int16`, `uint16`, `int32`, `uint32`, `int64`, `uint64`, `float`,
+//!   `double`, `string`) as well as `integer`, `number`, `array`, `object` and `any`.
+//! * `min` and `max`: the inclusive range of numeric values.
+//! * `min_items` and `max_items`: the inclusive range of array lengths.
+//! * `items`: the schema of every array item.
+//! * `properties`: an object mapping key names to their schema.
+//! * `required`: the list of keys an object must have.
+//! * `additional`: false to reject keys which are not listed in `properties`.
+//!
+//! In BPXSD descriptions the keywords above are recognized without debug information, but
+//! property names need a `__debug__` table to be known.
+
+use std::ffi::CString;
+use bp3d_util::simple_error;
+use safer_ffi::prelude::*;
+use crate::bpxsd::dictionary::Dictionary;
+use crate::bpxsd::reader::{Reader, ReaderOptions};
+use crate::common::{read_all, try_with_section, Container};
+use crate::error::unwrap_result;
+use crate::tree::model::{Node, Value, ValueType};
+use crate::tree::query::{push_name, push_segment};
+
+simple_error! {
+    pub Error {
+        (impl From) Json(serde_json::Error) => "JSON error: {}",
+        (impl From) Reader(crate::bpxsd::reader::Error) => "BPXSD error: {}",
+        Invalid(String) => "invalid schema: {}"
+    }
+}
+
+const KEYWORDS: [&str; 9] = ["type", "min", "max", "min_items", "max_items", "items", "properties", "required", "additional"];
+
+#[derive(Copy, Clone, PartialEq)]
+enum Type {
+    Any,
+    Integer,
+    Number,
+    Array,
+    Object,
+    Value(ValueType)
+}
+
+const TYPES: [(&str, Type); 18] = [
+    ("any", Type::Any),
+    ("integer", Type::Integer),
+    ("number", Type::Number),
+    ("array", Type::Array),
+    ("object", Type::Object),
+    ("null", Type::Value(ValueType::Null)),
+    ("bool", Type::Value(ValueType::Boolean)),
+    ("int8", Type::Value(ValueType::Int8)),
+    ("uint8", Type::Value(ValueType::UInt8)),
+    ("int16", Type::Value(ValueType::Int16)),
+    ("uint16", Type::Value(ValueType::UInt16)),
+    ("int32", Type::Value(ValueType::Int32)),
+    ("uint32", Type::Value(ValueType::UInt32)),
+    ("int64", Type::Value(ValueType::Int64)),
+    ("uint64", Type::Value(ValueType::UInt64)),
+    ("float", Type::Value(ValueType::Float)),
+    ("double", Type::Value(ValueType::Double)),
+    ("string", Type::Value(ValueType::String))
+];
+
+#[derive(Copy, Clone, PartialEq)]
+enum Kind {
+    Scalar,
+    Array,
+    Object,
+    Empty
+}
+
+fn kind(node: &Node) -> Kind {
+    let ty = node.details.as_ref()
+        .and_then(|v| v.children.iter().find(|v| v.name.to_str() == "type"))
+        .map(|v| v.value.as_str());
+    match ty {
+        Some("array") => Kind::Array,
+        Some("object") => Kind::Object,
+        Some(_) => Kind::Scalar,
+        None if node.children.is_empty() && node.value.get_type() == ValueType::Null => Kind::Empty,
+        None if node.children.is_empty() => Kind::Scalar,
+        None if node.children.iter().all(|v| v.name.to_str().is_empty()) => Kind::Array,
+        None => Kind::Object
+    }
+}
+
+fn is_integer(ty: ValueType) -> bool {
+    matches!(ty, ValueType::Int8 | ValueType::UInt8 | ValueType::Int16 | ValueType::UInt16
+        | ValueType::Int32 | ValueType::UInt32 | ValueType::Int64 | ValueType::UInt64)
+}
+
+impl Type {
+    fn accepts(self, node: &Node) -> bool {
+        let kind = kind(node);
+        let ty = node.value.get_type();
+        match self {
+            Type::Any => true,
+            Type::Array => kind == Kind::Array || kind == Kind::Empty,
+            Type::Object => kind == Kind::Object || kind == Kind::Empty,
+            Type::Value(ValueType::Null) => kind == Kind::Empty || (kind == Kind::Scalar && ty == ValueType::Null),
+            _ if kind != Kind::Scalar => false,
+            Type::Integer => is_integer(ty),
+            Type::Number => is_integer(ty) || ty == ValueType::Float || ty == ValueType::Double,
+            Type::Value(v) => v == ty
+        }
+    }
+}
+
+#[derive_ReprC]
+#[repr(opaque)]
+pub struct Schema {
+    types: Vec<Type>,
+    min: Option<f64>,
+    max: Option<f64>,
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+    items: Option<Box<Schema>>,
+    properties: Vec<(String, Schema)>,
+    required: Vec<String>,
+    additional: bool
+}
+
+fn invalid<T>(path: &str, msg: &str) -> Result<T, Error> {
+    Err(Error::Invalid(format!("{} at {}", msg, path)))
+}
+
+fn parse_type(path: &str, value: &serde_json::Value) -> Result<Type, Error> {
+    match value.as_str().and_then(|v| TYPES.iter().find(|(name, _)| *name == v)) {
+        Some((_, ty)) => Ok(*ty),
+        None => invalid(path, &format!("unknown type {}", value))
+    }
+}
+
+impl Schema {
+    pub fn from_json(value: &serde_json::Value) -> Result<Self, Error> {
+        Self::parse("$", value)
+    }
+
+    fn parse(path: &str, value: &serde_json::Value) -> Result<Self, Error> {
+        let object = match value.as_object() {
+            Some(v) => v,
+            None => return invalid(path, "expected an object")
+        };
+        let number = |key: &str| match object.get(key) {
+            None => Ok(None),
+            Some(v) => v.as_f64().map(Some).ok_or_else(|| Error::Invalid(format!("'{}' is not a number at {}", key, path)))
+        };
+        let count = |key: &str| match object.get(key) {
+            None => Ok(None),
+            Some(v) => v.as_u64().map(|v| Some(v as usize)).ok_or_else(|| Error::Invalid(format!("'{}' is not a count at {}", key, path)))
+        };
+        let types = match object.get("type") {
+            None => vec![Type::Any],
+            Some(serde_json::Value::Array(v)) => v.iter().map(|v| parse_type(path, v)).collect::<Result<_, _>>()?,
+            Some(v) => vec![parse_type(path, v)?]
+        };
+        let items = match object.get("items") {
+            Some(v) => Some(Box::new(Self::parse(&format!("{}.items", path), v)?)),
+            None => None
+        };
+        let mut properties = Vec::new();
+        if let Some(v) = object.get("properties") {
+            let map = match v.as_object() {
+                Some(v) => v,
+                None => return invalid(path, "'properties' is not an object")
+            };
+            for (key, value) in map {
+                properties.push((key.clone(), Self::parse(&format!("{}.properties.{}", path, key), value)?));
+            }
+        }
+        let required = match object.get("required") {
+            None => Vec::new(),
+            Some(v) => match v.as_array().and_then(|v| v.iter().map(|v| v.as_str().map(String::from)).collect()) {
+                Some(v) => v,
+                None => return invalid(path, "'required' is not a list of names")
+            }
+        };
+        let additional = match object.get("additional") {
+            None => true,
+            Some(v) => match v.as_bool() {
+                Some(v) => v,
+                None => return invalid(path, "'additional' is not a boolean")
+            }
+        };
+        Ok(Self {
+            types,
+            min: number("min")?,
+            max: number("max")?,
+            min_items: count("min_items")?,
+            max_items: count("max_items")?,
+            items,
+            properties,
+            required,
+            additional
+        })
+    }
+
+    pub fn from_node(node: &Node) -> Result<Self, Error> {
+        Self::from_json(&node_to_json(node))
+    }
+
+    pub fn validate(&self, node: &Node) -> Vec<Violation> {
+        let mut out = Vec::new();
+        self.validate_node(node, &mut String::from("$"), &mut out);
+        out
+    }
+
+    fn validate_node(&self, node: &Node, path: &mut String, out: &mut Vec<Violation>) {
+        if !self.types.iter().any(|v| v.accepts(node)) {
+            let names: Vec<&str> = self.types.iter()
+                .map(|v| TYPES.iter().find(|(_, ty)| ty == v).unwrap().0)
+                .collect();
+            out.push(Violation::new(ViolationKind::Type, path, format!("expected {}", names.join(" or "))));
+            return;
+        }
+        let value = &node.value;
+        if kind(node) == Kind::Scalar && (is_integer(value.get_type()) || matches!(value, Value::Float(_) | Value::Double(_))) {
+            let v = value.as_f64();
+            if self.min.is_some_and(|min| v < min) || self.max.is_some_and(|max| v > max) {
+                let range = format!("[{}, {}]", self.min.map(|v| v.to_string()).unwrap_or_default(),
+                                    self.max.map(|v| v.to_string()).unwrap_or_default());
+                out.push(Violation::new(ViolationKind::Range, path, format!("{} is outside {}", value, range)));
+            }
+        }
+        match kind(node) {
+            Kind::Array | Kind::Empty if self.items.is_some() || self.min_items.is_some() || self.max_items.is_some() => {
+                let len = node.children.len();
+                if self.min_items.is_some_and(|v| len < v) || self.max_items.is_some_and(|v| len > v) {
+                    out.push(Violation::new(ViolationKind::Length, path, format!("array has {} items", len)));
+                }
+                if let Some(items) = &self.items {
+                    for (index, child) in node.children.iter().enumerate() {
+                        let len = path.len();
+                        push_segment(path, index, child);
+                        items.validate_node(child, path, out);
+                        path.truncate(len);
+                    }
+                }
+            },
+            Kind::Object | Kind::Empty => {
+                for key in &self.required {
+                    if !node.children.iter().any(|v| v.name.to_str() == key) {
+                        let len = path.len();
+                        push_name(path, key);
+                        out.push(Violation::new(ViolationKind::Missing, path, format!("missing required key '{}'", key)));
+                        path.truncate(len);
+                    }
+                }
+                for (index, child) in node.children.iter().enumerate() {
+                    let len = path.len();
+                    push_segment(path, index, child);
+                    match self.properties.iter().find(|(key, _)| key == child.name.to_str()) {
+                        Some((_, schema)) => schema.validate_node(child, path, out),
+                        None if !self.additional => out.push(Violation::new(ViolationKind::Unexpected, path, "unexpected key".into())),
+                        None => ()
+                    }
+                    path.truncate(len);
+                }
+            },
+            _ => ()
+        }
+    }
+}
+
+fn node_to_json(node: &Node) -> serde_json::Value {
+    match kind(node) {
+        Kind::Array => node.children.iter().map(node_to_json).collect(),
+        Kind::Object => node.children.iter()
+            .map(|v| (v.name.to_str().into(), node_to_json(v)))
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+        Kind::Empty => serde_json::Value::Null,
+        Kind::Scalar => match &node.value {
+            Value::Null => serde_json::Value::Null,
+            Value::Boolean(v) => (*v).into(),
+            Value::String(v) => v.to_str().into(),
+            Value::Float(_) | Value::Double(_) => node.value.as_f64().into(),
+            Value::UInt64(v) => (*v).into(),
+            v => v.as_i64().into()
+        }
+    }
+}
+
+#[derive_ReprC]
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum ViolationKind {
+    Type = 0,
+    Range,
+    Length,
+    Missing,
+    Unexpected
+}
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct Violation {
+    pub kind: ViolationKind,
+    pub path: char_p::Box,
+    pub message: char_p::Box
+}
+
+impl Violation {
+    fn new(kind: ViolationKind, path: &str, message: String) -> Self {
+        Self {
+            kind,
+            path: CString::new(path.replace('\0', "")).unwrap().into(),
+            message: CString::new(message.replace('\0', "")).unwrap().into()
+        }
+    }
+}
+
+#[derive_ReprC]
+#[repr(opaque)]
+pub struct Validation {
+    violations: Vec<Violation>
+}
+
+#[ffi_export]
+pub fn bpx_schema_load_json(json: char_p::Ref<'_>) -> Option<repr_c::Box<Schema>> {
+    let value = unwrap_result(serde_json::from_str(json.to_str()).map_err(Error::Json))?;
+    unwrap_result(Schema::from_json(&value)).map(|v| Box::new(v).into())
+}
+
+#[ffi_export]
+pub fn bpx_schema_load_bpxsd(buffer: c_slice::Ref<'_, u8>) -> Option<repr_c::Box<Schema>> {
+    let mut dictionary = Dictionary::default();
+    for key in KEYWORDS {
+        dictionary.add(key);
+    }
+    let node = unwrap_result(Reader::new(buffer.as_slice(), &ReaderOptions::default(), Some(&dictionary)).read_root())?;
+    unwrap_result(Schema::from_node(&node)).map(|v| Box::new(v).into())
+}
+
+#[ffi_export]
+pub fn bpx_schema_free(schema: repr_c::Box<Schema>) {
+    drop(schema);
+}
+
+#[ffi_export]
+pub fn bpx_schema_validate(schema: &Schema, node: &Node) -> repr_c::Box<Validation> {
+    Box::new(Validation { violations: schema.validate(node) }).into()
+}
+
+#[ffi_export]
+pub fn bpx_schema_validate_section(schema: &Schema, container: &Container, handle: u32) -> Option<repr_c::Box<Validation>> {
+    bpx_schema_validate_section_with_options(schema, container, handle, &ReaderOptions::default(), None)
+}
+
+#[ffi_export]
+pub fn bpx_schema_validate_section_with_options(schema: &Schema, container: &Container, handle: u32, options: &ReaderOptions,
+                                                dictionary: Option<&Dictionary>) -> Option<repr_c::Box<Validation>> {
+    let data = try_with_section(container, handle, read_all)?;
+    let node = unwrap_result(Reader::new(&data, options, dictionary).read_root())?;
+    Some(Box::new(Validation { violations: schema.validate(&node) }).into())
+}
+
+#[ffi_export]
+pub fn bpx_validation_is_valid(validation: &Validation) -> bool {
+    validation.violations.is_empty()
+}
+
+#[ffi_export]
+pub fn bpx_validation_get_violations(validation: &Validation) -> c_slice::Ref<'_, Violation> {
+    validation.violations.as_slice().into()
+}
+
+#[ffi_export]
+pub fn bpx_validation_free(validation: repr_c::Box<Validation>) {
+    drop(validation);
+}
@@ -0,0 +1,181 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::ffi::CString;
+use bp3d_util::simple_error;
+use safer_ffi::prelude::*;
+use crate::tree::model::{Value, ValueType};
+
+simple_error! {
+    pub Error {
+        InvalidNumber(String) => "invalid number: '{}'",
+        InvalidBoolean(String) => "invalid boolean: '{}'",
+        InvalidNull(String) => "invalid null: '{}'",
+        Nul => "string contains a NUL character",
+        Overflow(&'static str) => "value out of range for {}"
+    }
+}
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct FormatOptions {
+    pub hex: bool,
+    pub uppercase: bool,
+    pub prefix: bool,
+    pub exponent: bool,
+    pub precision: i32
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            hex: false,
+            uppercase: false,
+            prefix: true,
+            exponent: false,
+            precision: -1
+        }
+    }
+}
+
+fn parse_integer(text: &str, ty: ValueType) -> Result<i128, Error> {
+    let invalid = || Error::InvalidNumber(text.into());
+    let (negative, digits) = match text.as_bytes().first() {
+        Some(b'-') => (true, &text[1..]),
+        Some(b'+') => (false, &text[1..]),
+        _ => (false, text)
+    };
+    let lower = digits.to_ascii_lowercase();
+    let (radix, digits) = match lower.get(..2) {
+        Some("0x") => (16, &lower[2..]),
+        Some("0o") => (8, &lower[2..]),
+        Some("0b") => (2, &lower[2..]),
+        _ => (10, &*lower)
+    };
+    let digits = digits.replace('_', "");
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return Err(invalid());
+    }
+    let magnitude = u128::from_str_radix(&digits, radix).map_err(|_| Error::Overflow(ty.name()))?;
+    let magnitude = i128::try_from(magnitude).map_err(|_| Error::Overflow(ty.name()))?;
+    Ok(if negative { -magnitude } else { magnitude })
+}
+
+macro_rules! int {
+    ($text: expr, $ty: expr, $t: ty, $variant: ident) => {
+        <$t>::try_from(parse_integer($text, $ty)?).map(Value::$variant).map_err(|_| Error::Overflow($ty.name()))
+    };
+}
+
+fn parse_float<T: std::str::FromStr + Into<f64> + Copy>(text: &str, ty: ValueType) -> Result<T, Error> {
+    let v: T = text.parse().map_err(|_| Error::InvalidNumber(text.into()))?;
+    // Out of range literals parse as infinity; only accept that when infinity was asked for.
+    match v.into().is_infinite() && !text.to_ascii_lowercase().contains("inf") {
+        true => Err(Error::Overflow(ty.name())),
+        false => Ok(v)
+    }
+}
+
+/// Parses a value of the given type from text.
+///
+/// Integers accept an optional sign, a `0x`, `0o` or `0b` prefix and `_` separators. Floats accept
+/// `inf`, `infinity` and `nan` in any case. Strings are taken as-is, every other type ignores
+/// surrounding whitespace.
+pub fn parse(text: &str, ty: ValueType) -> Result<Value, Error> {
+    if ty == ValueType::String {
+        return CString::new(text).map(|v| Value::String(v.into())).map_err(|_| Error::Nul);
+    }
+    let text = text.trim();
+    match ty {
+        ValueType::Null => match text.is_empty() || text.eq_ignore_ascii_case("null") {
+            true => Ok(Value::Null),
+            false => Err(Error::InvalidNull(text.into()))
+        },
+        ValueType::Int8 => int!(text, ty, i8, Int8),
+        ValueType::UInt8 => int!(text, ty, u8, UInt8),
+        ValueType::Int16 => int!(text, ty, i16, Int16),
+        ValueType::UInt16 => int!(text, ty, u16, UInt16),
+        ValueType::Int32 => int!(text, ty, i32, Int32),
+        ValueType::UInt32 => int!(text, ty, u32, UInt32),
+        ValueType::Int64 => int!(text, ty, i64, Int64),
+        ValueType::UInt64 => int!(text, ty, u64, UInt64),
+        ValueType::Float => parse_float(text, ty).map(Value::Float),
+        ValueType::Double => parse_float(text, ty).map(Value::Double),
+        ValueType::Boolean => match &*text.to_ascii_lowercase() {
+            "true" | "1" => Ok(Value::Boolean(true)),
+            "false" | "0" => Ok(Value::Boolean(false)),
+            _ => Err(Error::InvalidBoolean(text.into()))
+        },
+        ValueType::String => unreachable!()
+    }
+}
+
+fn format_integer(v: i128, options: &FormatOptions) -> String {
+    if !options.hex {
+        return v.to_string();
+    }
+    let sign = if v < 0 { "-" } else { "" };
+    let prefix = if options.prefix { "0x" } else { "" };
+    match options.uppercase {
+        true => format!("{}{}{:X}", sign, prefix, v.unsigned_abs()),
+        false => format!("{}{}{:x}", sign, prefix, v.unsigned_abs())
+    }
+}
+
+fn format_float<T: std::fmt::Display + std::fmt::LowerExp + std::fmt::UpperExp>(v: T, options: &FormatOptions) -> String {
+    let precision = usize::try_from(options.precision).ok();
+    match (options.exponent, options.uppercase, precision) {
+        (false, _, None) => format!("{}", v),
+        (false, _, Some(p)) => format!("{:.*}", p, v),
+        (true, false, None) => format!("{:e}", v),
+        (true, false, Some(p)) => format!("{:.*e}", p, v),
+        (true, true, None) => format!("{:E}", v),
+        (true, true, Some(p)) => format!("{:.*E}", p, v)
+    }
+}
+
+/// Formats a value as text. With the default options [parse] reads the text back to the same
+/// value; a float precision rounds the value, and hex digits without the `0x` prefix no longer
+/// parse as the same integer.
+pub fn format(value: &Value, options: &FormatOptions) -> String {
+    match value {
+        Value::Null => "null".into(),
+        Value::Int8(v) => format_integer(*v as _, options),
+        Value::UInt8(v) => format_integer(*v as _, options),
+        Value::Int16(v) => format_integer(*v as _, options),
+        Value::UInt16(v) => format_integer(*v as _, options),
+        Value::Int32(v) => format_integer(*v as _, options),
+        Value::UInt32(v) => format_integer(*v as _, options),
+        Value::Int64(v) => format_integer(*v as _, options),
+        Value::UInt64(v) => format_integer(*v as _, options),
+        Value::Float(v) => format_float(*v, options),
+        Value::Double(v) => format_float(*v, options),
+        Value::Boolean(v) => v.to_string(),
+        Value::String(v) => v.to_str().into()
+    }
+}
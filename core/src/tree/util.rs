@@ -26,15 +26,13 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::ffi::CString;
 use bp3d_util::simple_error;
-use crate::tree::model::{Node, Value};
+use crate::tree::model::Value;
 
 simple_error! {
     pub Error {
         UnsupportedValue => "unsupported value",
-        InvalidString => "invalid string",
-        TypeError(bpx::sd::error::TypeError) => "BPXSD type error {}"
+        InvalidString => "invalid string"
     }
 }
 
@@ -61,72 +59,3 @@ impl TryFrom<&bpx::sd::Value> for Value {
         }
     }
 }
-
-impl TryFrom<&bpx::sd::Array> for Node {
-    type Error = Error;
-
-    fn try_from(value: &bpx::sd::Array) -> Result<Self, Self::Error> {
-        let mut node = Node {
-            name: CString::from(c"").into(),
-            value: Value::Null,
-            details: None,
-            children: Vec::new()
-        };
-        for v in value {
-            let n = match v {
-                bpx::sd::Value::Array(v) => Node::try_from(v)?,
-                bpx::sd::Value::Object(v) => Node::try_from(v)?,
-                v => Node {
-                    name: CString::from(c"").into(),
-                    value: v.try_into()?,
-                    details: None,
-                    children: Vec::new()
-                }
-            };
-            node.children.push(n);
-        }
-        Ok(node)
-    }
-}
-
-impl TryFrom<&bpx::sd::Object> for Node {
-    type Error = Error;
-
-    fn try_from(value: &bpx::sd::Object) -> Result<Self, Self::Error> {
-        let mut node = Node {
-            name: CString::from(c"root").into(),
-            value: Value::Null,
-            details: None,
-            children: Vec::new()
-        };
-        let debugger = bpx::sd::debug::Debugger::attach(value).map_err(Error::TypeError)?;
-        for (name, hash, value) in &debugger {
-            let child = match value {
-                bpx::sd::Value::Array(v) => {
-                    let mut node: Node = v.try_into()?;
-                    node.name = name.map(String::from)
-                        .unwrap_or_else(|| format!("{:X}", hash.into_inner())).try_into()
-                        .map_err(|_| Error::InvalidString)?;
-                    node
-                },
-                bpx::sd::Value::Object(v) => {
-                    let mut node: Node = v.try_into()?;
-                    node.name = name.map(String::from)
-                        .unwrap_or_else(|| format!("{:X}", hash.into_inner())).try_into()
-                        .map_err(|_| Error::InvalidString)?;
-                    node
-                },
-                v => Node {
-                    value: v.try_into()?,
-                    name: name.map(String::from)
-                        .unwrap_or_else(|| format!("{:X}", hash.into_inner())).try_into()
-                        .map_err(|_| Error::InvalidString)?,
-                    details: None,
-                    children: Vec::new()
-                }
-            };
-            node.children.push(child);
-        }
-        Ok(node)
-    }
-}
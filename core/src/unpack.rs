@@ -0,0 +1,451 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fs;
+use std::io::{Seek, SeekFrom, Write};
+use std::path::Path;
+use bp3d_util::simple_error;
+use bpx::core::Handle;
+use bpx::core::header::{Struct, SECTION_TYPE_SD, SECTION_TYPE_TABLE};
+use bpx::table::column::{Column, Type};
+use bpx::table::core::RawTable;
+use bpx::table::header::Header;
+use safer_ffi::prelude::*;
+use serde_json::json;
+use crate::bpxsd::json::{from_json, to_json};
+use crate::bpxsd::reader::ReaderOptions;
+use crate::common::{read_all, Container, MainHeader};
+use crate::container::{CreateOptions, FLAG_REVERT_ON_SAVE_FAIL};
+use crate::error::unwrap_result;
+use crate::section::SectionOptions;
+use crate::stream::Stream;
+use crate::strings::{resolve_strings, StringPool};
+use crate::table::layout::{open_raw, Layout};
+use crate::table::row::{element_count, read_elements};
+
+simple_error! {
+    pub Error {
+        (impl From) Bpx(bpx::core::error::Error) => "BPX error: {}",
+        (impl From) Io(std::io::Error) => "io error: {}",
+        (impl From) Table(bpx::table::error::Error) => "table error: {}",
+        (impl From) Json(serde_json::Error) => "JSON error: {}",
+        (impl From) Sd(crate::bpxsd::json::Error) => "BPXSD error: {}",
+        Manifest(&'static str) => "invalid manifest: {}",
+        Csv(String) => "invalid CSV: {}"
+    }
+}
+
+const MANIFEST: &str = "manifest.json";
+
+const TYPES: [(Type, &str); 13] = [
+    (Type::Null, "null"),
+    (Type::Boolean, "bool"),
+    (Type::Uint8, "uint8"),
+    (Type::Uint16, "uint16"),
+    (Type::Uint32, "uint32"),
+    (Type::Uint64, "uint64"),
+    (Type::Int8, "int8"),
+    (Type::Int16, "int16"),
+    (Type::Int32, "int32"),
+    (Type::Int64, "int64"),
+    (Type::Float, "float"),
+    (Type::Double, "double"),
+    (Type::Varchar, "varchar")
+];
+
+fn type_name(ty: Type) -> &'static str {
+    TYPES.iter().find(|(v, _)| *v == ty).unwrap().1
+}
+
+fn parse_type(name: &str) -> Option<Type> {
+    TYPES.iter().find(|(_, v)| *v == name).map(|(v, _)| *v)
+}
+
+fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|v| format!("{:02X}", v)).collect()
+}
+
+fn from_hex<const N: usize>(s: &str) -> Option<[u8; N]> {
+    if s.len() != N * 2 {
+        return None;
+    }
+    let mut out = [0; N];
+    for (i, v) in out.iter_mut().enumerate() {
+        *v = u8::from_str_radix(s.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(out)
+}
+
+fn write_csv_field(out: &mut String, field: &str) {
+    if field.contains([',', '"', '\n', '\r']) {
+        out.push('"');
+        out.push_str(&field.replace('"', "\"\""));
+        out.push('"');
+    } else {
+        out.push_str(field);
+    }
+}
+
+fn write_csv_record<'a>(out: &mut String, fields: impl IntoIterator<Item = &'a str>) {
+    for (i, field) in fields.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_csv_field(out, field);
+    }
+    out.push('\n');
+}
+
+fn read_csv(text: &str) -> Result<Vec<Vec<String>>, Error> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut chars = text.chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            },
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => (),
+            (false, '\n') => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            },
+            (false, c) => field.push(c)
+        }
+    }
+    if quoted {
+        return Err(Error::Csv("unterminated quoted field".into()));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn format_cell(column: &Column, cell: &[u8]) -> Option<String> {
+    if column.ty == Type::Varchar {
+        let end = cell.iter().rposition(|v| *v != 0).map(|v| v + 1).unwrap_or(0);
+        let s = std::str::from_utf8(&cell[..end]).ok()?;
+        return match s.contains('\0') {
+            true => None,
+            false => Some(s.into())
+        };
+    }
+    if column.get_size() == 0 {
+        return Some(String::new());
+    }
+    let elements = read_elements(column.ty, element_count(column), cell).ok()?;
+    Some(elements.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(";"))
+}
+
+fn parse_element(ty: Type, text: &str, out: &mut [u8]) -> Option<()> {
+    let bytes: Vec<u8> = match ty {
+        Type::Null => Vec::new(),
+        Type::Boolean => vec![text.parse::<bool>().ok()? as u8],
+        Type::Uint8 => text.parse::<u8>().ok()?.to_le_bytes().into(),
+        Type::Uint16 => text.parse::<u16>().ok()?.to_le_bytes().into(),
+        Type::Uint32 => text.parse::<u32>().ok()?.to_le_bytes().into(),
+        Type::Uint64 => text.parse::<u64>().ok()?.to_le_bytes().into(),
+        Type::Int8 => text.parse::<i8>().ok()?.to_le_bytes().into(),
+        Type::Int16 => text.parse::<i16>().ok()?.to_le_bytes().into(),
+        Type::Int32 => text.parse::<i32>().ok()?.to_le_bytes().into(),
+        Type::Int64 => text.parse::<i64>().ok()?.to_le_bytes().into(),
+        Type::Float => text.parse::<f32>().ok()?.to_le_bytes().into(),
+        Type::Double => text.parse::<f64>().ok()?.to_le_bytes().into(),
+        Type::Varchar => text.as_bytes().into()
+    };
+    out.get_mut(..bytes.len())?.copy_from_slice(&bytes);
+    Some(())
+}
+
+fn parse_cell(column: &Column, text: &str, out: &mut [u8]) -> Option<()> {
+    if column.ty == Type::Varchar || column.get_size() == 0 {
+        return parse_element(column.ty, text, out);
+    }
+    let size = column.get_size() / column.len as usize;
+    let elements: Vec<&str> = text.split(';').collect();
+    if elements.len() != column.len as usize {
+        return None;
+    }
+    for (element, cell) in elements.into_iter().zip(out.chunks_mut(size)) {
+        parse_element(column.ty, element, cell)?;
+    }
+    Some(())
+}
+
+fn encode_rows(layout: &Layout, table: &RawTable, records: &[Vec<String>], out: &mut Vec<u8>) -> Result<(), Error> {
+    let row_size = table.get_actual_row_size();
+    for (index, record) in records.iter().enumerate() {
+        if record.len() != layout.columns.len() + 1 {
+            return Err(Error::Csv(format!("row {} has {} fields", index, record.len())));
+        }
+        let mut row = vec![0; row_size];
+        row[table.get_row_size()] = match &*record[0] {
+            "0" => 0,
+            "1" => 1,
+            v => return Err(Error::Csv(format!("row {} has invalid free flag '{}'", index, v)))
+        };
+        let mut offset = 0;
+        for (column, text) in layout.columns.iter().zip(&record[1..]) {
+            let size = column.get_size();
+            parse_cell(column, text, &mut row[offset..offset + size])
+                .ok_or_else(|| Error::Csv(format!("row {} has invalid value '{}'", index, text)))?;
+            offset += size;
+        }
+        out.extend_from_slice(&row);
+    }
+    Ok(())
+}
+
+fn decode_rows(layout: &Layout, table: &RawTable, pool: Option<&StringPool>, data: &[u8]) -> Option<String> {
+    let row_size = table.get_actual_row_size();
+    let mut out = String::new();
+    let names: Vec<String> = layout.columns.iter().enumerate()
+        .map(|(i, v)| pool.and_then(|p| p.get(v.name).ok()).map(String::from).unwrap_or_else(|| format!("#{}", i)))
+        .collect();
+    write_csv_record(&mut out, std::iter::once("$free").chain(names.iter().map(|v| &**v)));
+    for row in data.chunks(row_size) {
+        let mut fields = vec![row.get(table.get_row_size())?.to_string()];
+        let mut offset = 0;
+        for column in &layout.columns {
+            let size = column.get_size();
+            fields.push(format_cell(column, row.get(offset..offset + size)?)?);
+            offset += size;
+        }
+        write_csv_record(&mut out, fields.iter().map(|v| &**v));
+    }
+    Some(out)
+}
+
+fn unpack_table(container: &Container, handle: Handle, data: &[u8], pool: Option<&StringPool>, entry: &mut serde_json::Value) -> Option<String> {
+    let layout = Layout::read(data).ok()?;
+    let table = open_raw(container, handle).ok()?;
+    let text = decode_rows(&layout, &table, pool, &data[layout.size()..])?;
+    let mut rebuilt = data[..layout.size()].to_vec();
+    encode_rows(&layout, &table, &read_csv(&text).ok()?[1..], &mut rebuilt).ok()?;
+    if rebuilt != data {
+        return None;
+    }
+    let name = |address: u32| pool.and_then(|p| p.get(address).ok());
+    entry["name"] = json!({ "text": name(layout.header.name), "address": layout.header.name });
+    entry["columns"] = layout.columns.iter().map(|v| json!({
+        "name": { "text": name(v.name), "address": v.name },
+        "type": type_name(v.ty),
+        "len": v.len
+    })).collect();
+    Some(text)
+}
+
+fn unpack_sd(data: &[u8]) -> Option<String> {
+    let (value, size) = to_json(data, &ReaderOptions::default()).ok()?;
+    let text = serde_json::to_string_pretty(&value).ok()?;
+    let rebuilt = from_json(&serde_json::from_str(&text).ok()?, &ReaderOptions::default()).ok()?;
+    match size == data.len() && rebuilt == data {
+        true => Some(text),
+        false => None
+    }
+}
+
+pub fn unpack(container: &Container, path: &Path, strings: u32) -> Result<(), Error> {
+    fs::create_dir_all(path)?;
+    let strings = resolve_strings(container, strings);
+    let pool = strings.map(|v| StringPool::load(container, v)).transpose()?;
+    let mut sections = Vec::new();
+    let mut strings_index = None;
+    for (index, handle) in container.underlying.sections().iter().enumerate() {
+        let header = container.underlying.sections()[handle].header();
+        let data = read_all(&mut *container.underlying.sections().load(handle)?)?;
+        if Some(handle) == strings {
+            strings_index = Some(index);
+        }
+        let mut entry = json!({ "type": header.ty, "flags": header.flags });
+        let (file, format, text) = match header.ty {
+            SECTION_TYPE_SD => match unpack_sd(&data) {
+                Some(text) => (format!("{}.json", index), "bpxsd", Some(text)),
+                None => (format!("{}.bin", index), "raw", None)
+            },
+            SECTION_TYPE_TABLE => match unpack_table(container, handle, &data, pool.as_ref(), &mut entry) {
+                Some(text) => (format!("{}.csv", index), "table", Some(text)),
+                None => (format!("{}.bin", index), "raw", None)
+            },
+            _ => (format!("{}.bin", index), "raw", None)
+        };
+        match text {
+            Some(text) => fs::write(path.join(&file), text)?,
+            None => fs::write(path.join(&file), &data)?
+        }
+        entry["file"] = file.into();
+        entry["format"] = format.into();
+        sections.push(entry);
+    }
+    let header = &container.main_header;
+    let manifest = json!({
+        "main_header": {
+            "type": header.ty,
+            "version": header.version,
+            "type_ext": to_hex(&header.type_ext)
+        },
+        "strings": strings_index,
+        "sections": sections
+    });
+    fs::write(path.join(MANIFEST), serde_json::to_string_pretty(&manifest)?)?;
+    Ok(())
+}
+
+fn read_manifest(path: &Path) -> Result<serde_json::Value, Error> {
+    let manifest: serde_json::Value = serde_json::from_str(&fs::read_to_string(path.join(MANIFEST))?)?;
+    if !manifest["sections"].is_array() {
+        return Err(Error::Manifest("missing section list"));
+    }
+    Ok(manifest)
+}
+
+fn read_main_header(manifest: &serde_json::Value, header: &mut MainHeader) -> Result<(), Error> {
+    let value = &manifest["main_header"];
+    header.ty = value["type"].as_u64().and_then(|v| u8::try_from(v).ok())
+        .ok_or(Error::Manifest("invalid main header type"))?;
+    header.version = value["version"].as_u64().and_then(|v| u32::try_from(v).ok())
+        .ok_or(Error::Manifest("invalid main header version"))?;
+    header.type_ext = value["type_ext"].as_str().and_then(from_hex)
+        .ok_or(Error::Manifest("invalid main header type_ext"))?;
+    Ok(())
+}
+
+fn resolve_name(pool: &mut StringPool, value: &serde_json::Value) -> Result<u32, Error> {
+    let address = value["address"].as_u64().and_then(|v| u32::try_from(v).ok());
+    match value["text"].as_str() {
+        Some(text) if address.and_then(|v| pool.get(v).ok()) == Some(text) => Ok(address.unwrap()),
+        Some(text) => Ok(pool.put(text)),
+        None => address.ok_or(Error::Manifest("invalid name"))
+    }
+}
+
+fn read_layout(entry: &serde_json::Value, mut pool: Option<&mut StringPool>) -> Result<Layout, Error> {
+    let mut name = |value: &serde_json::Value| match pool.as_deref_mut() {
+        Some(pool) => resolve_name(pool, value),
+        None => value["address"].as_u64().and_then(|v| u32::try_from(v).ok())
+            .ok_or(Error::Manifest("invalid name"))
+    };
+    let mut columns = Vec::new();
+    for column in entry["columns"].as_array().ok_or(Error::Manifest("missing table columns"))? {
+        columns.push(Column {
+            name: name(&column["name"])?,
+            ty: column["type"].as_str().and_then(parse_type).ok_or(Error::Manifest("invalid column type"))?,
+            len: column["len"].as_u64().and_then(|v| u16::try_from(v).ok()).ok_or(Error::Manifest("invalid column length"))?
+        });
+    }
+    let header = Header {
+        name: name(&entry["name"])?,
+        columns: columns.len() as _
+    };
+    Ok(Layout { header, columns })
+}
+
+fn write_section(container: &Container, handle: Handle, buffer: &[u8]) -> Result<(), bpx::core::error::Error> {
+    let mut data = container.underlying.sections().open(handle)?;
+    data.seek(SeekFrom::Start(0))?;
+    data.write_all(buffer)?;
+    data.seek(SeekFrom::Start(0))?;
+    Ok(())
+}
+
+pub fn pack(container: &mut Container, path: &Path) -> Result<(), Error> {
+    let manifest = read_manifest(path)?;
+    let entries = manifest["sections"].as_array().unwrap();
+    let mut handles = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let ty = entry["type"].as_u64().and_then(|v| u8::try_from(v).ok())
+            .ok_or(Error::Manifest("invalid section type"))?;
+        let flags = entry["flags"].as_u64().and_then(|v| u8::try_from(v).ok())
+            .ok_or(Error::Manifest("invalid section flags"))?;
+        let file = path.join(entry["file"].as_str().ok_or(Error::Manifest("missing section file"))?);
+        let data = match entry["format"].as_str() {
+            Some("raw") => fs::read(file)?,
+            Some("bpxsd") => from_json(&serde_json::from_str(&fs::read_to_string(file)?)?, &ReaderOptions::default())?,
+            Some("table") => Vec::new(),
+            _ => return Err(Error::Manifest("invalid section format"))
+        };
+        let handle = container.underlying.sections_mut().create(SectionOptions::new(ty, flags).to_options());
+        write_section(container, handle, &data)?;
+        handles.push(handle);
+    }
+    let strings: Option<Handle> = match manifest["strings"].as_u64() {
+        Some(index) => Some(*handles.get(index as usize).ok_or(Error::Manifest("invalid strings section"))?),
+        None => None
+    };
+    let mut pool = strings.map(|v| StringPool::load(container, v)).transpose()?;
+    for (entry, handle) in entries.iter().zip(&handles) {
+        if entry["format"].as_str() != Some("table") {
+            continue;
+        }
+        let layout = read_layout(entry, pool.as_mut())?;
+        let text = fs::read_to_string(path.join(entry["file"].as_str().unwrap()))?;
+        let records = read_csv(&text)?;
+        let mut data = vec![0; layout.size()];
+        layout.write(&mut data);
+        write_section(container, *handle, &data)?;
+        let table = open_raw(container, *handle)?;
+        encode_rows(&layout, &table, records.get(1..).unwrap_or_default(), &mut data)?;
+        write_section(container, *handle, &data)?;
+    }
+    if let (Some(pool), Some(strings)) = (pool, strings) {
+        pool.save(container, strings)?;
+    }
+    Ok(())
+}
+
+#[ffi_export]
+pub fn bpx_container_unpack(container: &Container, path: char_p::Ref<'_>, strings: u32) -> bool {
+    unwrap_result(unpack(container, Path::new(path.to_str()), strings)).is_some()
+}
+
+#[ffi_export]
+pub fn bpx_container_pack(stream: repr_c::Box<Stream>, options: &CreateOptions, path: char_p::Ref<'_>) -> Option<repr_c::Box<Container>> {
+    let path = Path::new(path.to_str());
+    let mut main_header = MainHeader::from(bpx::core::header::MainHeader::new());
+    unwrap_result(read_manifest(path).and_then(|v| read_main_header(&v, &mut main_header)))?;
+    let opts = bpx::core::options::CreateOptions::new(*stream.into())
+        .ty(main_header.ty)
+        .version(main_header.version)
+        .type_ext(main_header.type_ext)
+        .memory_threshold(options.memory_threshold)
+        .compression_threshold(options.compression_threshold)
+        .revert_on_save_failure((options.flags & FLAG_REVERT_ON_SAVE_FAIL) != 0);
+    let mut container = Container::from(bpx::core::Container::create(opts));
+    unwrap_result(pack(&mut container, path))?;
+    container.refresh();
+    Some(Box::new(container).into())
+}